@@ -3,20 +3,28 @@
 use crate::models::WithBaseURL;
 use crate::{errors::*, models::*, tokens::*};
 use base64::{engine::general_purpose::STANDARD, Engine as _};
-use futures_util::TryStreamExt;
-use reqwest::header::CONTENT_TYPE;
+use futures_util::future::BoxFuture;
+use futures_util::{stream, Stream, StreamExt, TryStreamExt};
+use reqwest::header::{CONTENT_TYPE, RETRY_AFTER};
 use reqwest::{
     header::{HeaderMap, ACCEPT, AUTHORIZATION},
     multipart::{Form, Part},
-    Client, ClientBuilder, Method, RequestBuilder, Response,
+    Client, ClientBuilder, Method, RequestBuilder, Response, StatusCode,
 };
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
 use sha1::{Digest, Sha1};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::{Display, Formatter};
+use std::future::{Future, IntoFuture};
 use std::io::{BufWriter, Write};
-use std::path::Path;
-use std::{fs::File, io::Read};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::task::{ready, Context, Poll};
+use std::time::{Duration, Instant};
+use std::{fs::File, io::Seek};
+use tokio::io::{AsyncRead, AsyncReadExt, ReadBuf};
 use url::Url;
 
 ///
@@ -29,6 +37,18 @@ pub struct SzurubooruClient {
     base_url: Url,
     client: Client,
     auth: SzurubooruAuth,
+    max_url_length: usize,
+    max_search_terms: Option<usize>,
+    json_accept_header: bool,
+    timeout: Option<Duration>,
+    retry: Option<RetryPolicy>,
+    strict_deserialization: bool,
+    block_unsafe: bool,
+    response_cache: Option<ResponseCache>,
+    rate_limiter: Option<RateLimiter>,
+    upload_size_check: bool,
+    max_upload_size: OnceLock<Option<u64>>,
+    custom_client: bool,
 }
 
 impl SzurubooruClient {
@@ -36,15 +56,15 @@ impl SzurubooruClient {
     /// Construct a new `SzurubooruClient` using a username and token.
     ///
     /// * `host` - The host to connect to, including `http` or `https`. Any trailing slashes will
-    ///             be stripped
+    ///   be stripped
     /// * `username` - The username to authenticate as
     /// * `token` - The token used to authenticate as `username`
     /// * `allow_insecure` - Whether to disable SSL verification
     ///
     /// ## Returns
     ///
-    /// A [SzurubooruResult] containing the client. May return a [SzurubooruClientError::UrlParseError]
-    /// if the host URL isn't a proper URL.
+    /// A [SzurubooruResult] containing the client. May return a
+    /// [SzurubooruClientError::InvalidBaseUrl] if the host isn't an absolute `http` or `https` URL.
     ///
     /// ```no_run
     /// use szurubooru_client::SzurubooruClient;
@@ -56,9 +76,7 @@ impl SzurubooruClient {
         token: &str,
         allow_insecure: bool,
     ) -> SzurubooruResult<Self> {
-        let encoded_auth = STANDARD.encode(format!("{username}:{token}").as_bytes());
-        let token_header_value = format!("Token {encoded_auth}");
-        let auth = SzurubooruAuth::TokenAuth(token_header_value);
+        let auth = SzurubooruAuth::token(username, token);
         SzurubooruClient::new(host, auth, allow_insecure)
     }
 
@@ -72,8 +90,8 @@ impl SzurubooruClient {
     ///
     /// ## Returns
     ///
-    /// A [SzurubooruResult] containing the client. May return a [SzurubooruClientError::UrlParseError]
-    /// if the host URL isn't a proper URL.
+    /// A [SzurubooruResult] containing the client. May return a
+    /// [SzurubooruClientError::InvalidBaseUrl] if the host isn't an absolute `http` or `https` URL.
     ///
     /// ```no_run
     /// use szurubooru_client::SzurubooruClient;
@@ -86,7 +104,7 @@ impl SzurubooruClient {
         password: &str,
         allow_insecure: bool,
     ) -> SzurubooruResult<Self> {
-        let auth = SzurubooruAuth::BasicAuth(username.to_string(), password.to_string());
+        let auth = SzurubooruAuth::basic(username, password);
         SzurubooruClient::new(host, auth, allow_insecure)
     }
 
@@ -96,21 +114,43 @@ impl SzurubooruClient {
         SzurubooruClient::new(host, auth, allow_insecure)
     }
 
+    /// Construct a new `SzurubooruClient` that sends its requests through an existing
+    /// [reqwest::Client], so it shares that client's connection pool, TLS configuration,
+    /// user agent and default headers.
+    ///
+    /// Authentication is still added to each request by this crate rather than stored on
+    /// `client`, so the same `client` can be shared with the rest of an application. TLS
+    /// verification is entirely up to `client`; there is no `allow_insecure` here.
+    /// Timeouts and retries are also left to `client`: [with_timeout](Self::with_timeout) and
+    /// [with_retry](Self::with_retry) are ignored on a client constructed this way.
+    ///
+    /// ```no_run
+    /// use szurubooru_client::SzurubooruClient;
+    /// use szurubooru_client::client::SzurubooruAuth;
+    /// let http = reqwest::Client::builder()
+    ///     .user_agent("my-app/1.0")
+    ///     .build()
+    ///     .unwrap();
+    /// let client = SzurubooruClient::with_reqwest_client(http, "http://localhost:5001",
+    ///     SzurubooruAuth::token("myuser", "sz-123456")).unwrap();
+    /// ```
+    pub fn with_reqwest_client(
+        client: Client,
+        host: &str,
+        auth: SzurubooruAuth,
+    ) -> SzurubooruResult<Self> {
+        let base_url = Self::parse_host(host)?;
+        Ok(Self {
+            custom_client: true,
+            ..Self::from_parts(base_url, client, auth)
+        })
+    }
+
     fn new(host: &str, auth: SzurubooruAuth, allow_insecure: bool) -> SzurubooruResult<Self> {
-        let host = if host.ends_with("/") {
-            &host[0..host.len() - 1]
-        } else {
-            host
-        };
-        let mut base_url = Url::parse(host).map_err(|e| SzurubooruClientError::UrlParseError {
-            source: e,
-            url: host.to_string(),
-        })?;
-        base_url.set_fragment(None);
+        let base_url = Self::parse_host(host)?;
 
         let mut header_map = HeaderMap::new();
         //header_map.append(AUTHORIZATION, token_header_value.parse().unwrap());
-        header_map.append(ACCEPT, "application/json".parse().unwrap());
         header_map.append(CONTENT_TYPE, "application/json".parse().unwrap());
 
         let client = ClientBuilder::new()
@@ -119,11 +159,288 @@ impl SzurubooruClient {
             .build()
             .unwrap();
 
-        Ok(Self {
+        Ok(Self::from_parts(base_url, client, auth))
+    }
+
+    fn parse_host(host: &str) -> SzurubooruResult<Url> {
+        let invalid = |reason: String| SzurubooruClientError::InvalidBaseUrl {
+            url: host.to_string(),
+            reason,
+        };
+        let trimmed = host.trim().trim_end_matches('/');
+        let mut base_url = Url::parse(trimmed).map_err(|e| invalid(e.to_string()))?;
+        if !matches!(base_url.scheme(), "http" | "https") {
+            return Err(invalid(format!(
+                "unsupported scheme {}, expected http or https",
+                base_url.scheme()
+            )));
+        }
+        if !base_url.has_host() {
+            return Err(invalid("missing host".to_string()));
+        }
+        base_url.set_fragment(None);
+        Ok(base_url)
+    }
+
+    fn from_parts(base_url: Url, client: Client, auth: SzurubooruAuth) -> Self {
+        Self {
             base_url,
             client,
             auth,
-        })
+            max_url_length: Self::DEFAULT_MAX_URL_LENGTH,
+            max_search_terms: Some(Self::DEFAULT_MAX_SEARCH_TERMS),
+            json_accept_header: true,
+            timeout: None,
+            retry: None,
+            strict_deserialization: false,
+            block_unsafe: false,
+            response_cache: None,
+            rate_limiter: None,
+            upload_size_check: false,
+            max_upload_size: OnceLock::new(),
+            custom_client: false,
+        }
+    }
+
+    /// The timeout set on each request, unless the client came from
+    /// [with_reqwest_client](Self::with_reqwest_client)
+    fn request_timeout(&self) -> Option<Duration> {
+        self.timeout.filter(|_| !self.custom_client)
+    }
+
+    /// The policy `GET` requests are retried with, unless the client came from
+    /// [with_reqwest_client](Self::with_reqwest_client)
+    fn retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry.as_ref().filter(|_| !self.custom_client)
+    }
+
+    /// The base URL requests are sent to, as parsed from the `host` given at construction.
+    /// Any trailing `/` and fragment are removed, so `http://host` and `http://host/` are
+    /// equivalent
+    ///
+    /// ```
+    /// use szurubooru_client::SzurubooruClient;
+    /// let client = SzurubooruClient::new_anonymous("http://localhost:5001/", true).unwrap();
+    /// assert_eq!(client.base_url().as_str(), "http://localhost:5001/");
+    /// ```
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    /// The default value for [with_max_url_length](Self::with_max_url_length)
+    pub const DEFAULT_MAX_URL_LENGTH: usize = 2048;
+
+    /// Set the longest URL that helpers which batch large OR queries, such as
+    /// [get_tags](SzurubooruRequest::get_tags), will send. Queries that would go over this
+    /// length are split into multiple requests. Lower this if a proxy in front of the server
+    /// rejects long URLs
+    ///
+    /// ```no_run
+    /// use szurubooru_client::SzurubooruClient;
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true)
+    ///     .unwrap()
+    ///     .with_max_url_length(1024);
+    /// ```
+    pub fn with_max_url_length(mut self, max_url_length: usize) -> Self {
+        self.max_url_length = max_url_length;
+        self
+    }
+
+    /// The default value for [with_max_search_terms](Self::with_max_search_terms). Szurubooru
+    /// has no setting for this; queries are sent in the URL, and the stock Szurubooru setup
+    /// serves the API through nginx, whose default `large_client_header_buffers` rejects request
+    /// lines over 8 KiB. 100 terms keeps queries with tag-sized terms well under that
+    pub const DEFAULT_MAX_SEARCH_TERMS: usize = 100;
+
+    /// Set how many terms a search query may have before it's rejected locally with
+    /// [SzurubooruClientError::TooManySearchTerms], instead of being sent to the server. Large
+    /// queries can hit server-side limits that come back as an unhelpful `400`, so this fails
+    /// fast with the actual term count. See [QueryTokenList::check_term_count] for what counts
+    /// as a term. Pass `None` to turn the check off
+    ///
+    /// The check is on by default with a limit of
+    /// [DEFAULT_MAX_SEARCH_TERMS](Self::DEFAULT_MAX_SEARCH_TERMS), so a query with more terms
+    /// than that fails with [TooManySearchTerms](SzurubooruClientError::TooManySearchTerms)
+    /// even if the server would accept it. Raise the limit or turn the check off for servers
+    /// behind a proxy that allows longer URLs
+    ///
+    /// ```no_run
+    /// use szurubooru_client::SzurubooruClient;
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true)
+    ///     .unwrap()
+    ///     .with_max_search_terms(Some(20));
+    /// ```
+    pub fn with_max_search_terms(mut self, max_search_terms: Option<usize>) -> Self {
+        self.max_search_terms = max_search_terms;
+        self
+    }
+
+    /// Set whether API calls explicitly send `Accept: application/json`. This is on by default
+    /// and keeps reverse proxies that content-negotiate from returning HTML error pages.
+    /// Content downloads such as [get_image_bytestream](SzurubooruRequest::get_image_bytestream)
+    /// never send it
+    pub fn with_json_accept_header(mut self, enabled: bool) -> Self {
+        self.json_accept_header = enabled;
+        self
+    }
+
+    /// Set how long any single request may take, from connecting until the response body has
+    /// been read, before it fails with a [SzurubooruClientError::RequestError] whose source
+    /// [is_timeout](reqwest::Error::is_timeout). Applies to every request made through
+    /// [SzurubooruRequest], including content downloads, so leave enough room for large files.
+    /// There is no timeout by default. Ignored for a client from
+    /// [with_reqwest_client](Self::with_reqwest_client), set the timeout on that client instead
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use szurubooru_client::SzurubooruClient;
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true)
+    ///     .unwrap()
+    ///     .with_timeout(Duration::from_secs(30));
+    /// ```
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Retry `GET` requests that fail with one of the policy's
+    /// [retryable statuses](RetryPolicy::retry_statuses), waiting longer between each attempt.
+    /// Requests that change data are never retried. Retries are off by default, and ignored for
+    /// a client from [with_reqwest_client](Self::with_reqwest_client)
+    ///
+    /// ```no_run
+    /// use szurubooru_client::SzurubooruClient;
+    /// use szurubooru_client::client::RetryPolicy;
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true)
+    ///     .unwrap()
+    ///     .with_retry(RetryPolicy {
+    ///         max_attempts: 5,
+    ///         ..Default::default()
+    ///     });
+    /// ```
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Set whether responses with fields the [models](crate::models) don't know about are
+    /// rejected with a [SzurubooruClientError::ResponseParsingError] naming the field. Unknown
+    /// fields are ignored by default so the client keeps working against newer servers; turn
+    /// this on in tests to catch models drifting from the API
+    ///
+    /// ```no_run
+    /// use szurubooru_client::SzurubooruClient;
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true)
+    ///     .unwrap()
+    ///     .with_strict_deserialization(true);
+    /// ```
+    pub fn with_strict_deserialization(mut self, strict: bool) -> Self {
+        self.strict_deserialization = strict;
+        self
+    }
+
+    /// Set whether [unsafe](PostSafety::Unsafe) posts are kept out of post searches, e.g. for a
+    /// viewer embedded somewhere NSFW content shouldn't appear. When on, every post search made
+    /// through [list_posts](SzurubooruRequest::list_posts), and the streams and helpers built on
+    /// it, drops `unsafe` from any `safety:` tokens, adds `-safety:unsafe`, and filters any
+    /// unsafe posts the server still returns out of the results. A search that only asks for
+    /// unsafe posts returns an empty page without a request being made. Off by default.
+    ///
+    /// Fetching a post directly, e.g. with [get_post](SzurubooruRequest::get_post), isn't
+    /// affected.
+    ///
+    /// ```no_run
+    /// use szurubooru_client::SzurubooruClient;
+    /// let client = SzurubooruClient::new_anonymous("http://localhost:5001", true)
+    ///     .unwrap()
+    ///     .with_block_unsafe(true);
+    /// ```
+    pub fn with_block_unsafe(mut self, block: bool) -> Self {
+        self.block_unsafe = block;
+        self
+    }
+
+    /// Cache the responses to API `GET` requests for `ttl`, so repeating a request with the
+    /// same URL, query and fields within that time is answered without contacting the server.
+    /// Content downloads aren't cached. There is no cache by default.
+    ///
+    /// Any other request, such as a create, update or delete, clears the whole cache once it
+    /// completes, whether or not it succeeded. Mutations often change resources embedded in
+    /// others, e.g. renaming a tag changes every post using it, so nothing cached before the
+    /// change is trusted after it. Use [clear_response_cache](Self::clear_response_cache) after
+    /// changes made outside this client.
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use szurubooru_client::SzurubooruClient;
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true)
+    ///     .unwrap()
+    ///     .with_response_cache(Duration::from_secs(60));
+    /// ```
+    pub fn with_response_cache(mut self, ttl: Duration) -> Self {
+        self.response_cache = Some(ResponseCache::new(ttl));
+        self
+    }
+
+    /// Send at most `requests_per_second` requests per second, waiting before a request that
+    /// would go over the limit. This keeps bulk jobs under a server's or proxy's rate limits
+    /// instead of tripping them and relying on [with_retry](Self::with_retry) to recover from
+    /// the `429`s. Requests are spaced out evenly, e.g. `2.0` sends one every half second, and
+    /// retries and content downloads count towards the limit too. Responses served from the
+    /// [response cache](Self::with_response_cache) don't. There is no limit by default.
+    ///
+    /// Returns a [ValidationError](SzurubooruClientError::ValidationError) if
+    /// `requests_per_second` isn't a positive, finite number, or is so small that the time
+    /// between requests doesn't fit in a [Duration].
+    ///
+    /// ```no_run
+    /// use szurubooru_client::SzurubooruClient;
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true)
+    ///     .unwrap()
+    ///     .with_rate_limit(5.0)
+    ///     .unwrap();
+    /// ```
+    pub fn with_rate_limit(mut self, requests_per_second: f64) -> SzurubooruResult<Self> {
+        let invalid = || {
+            SzurubooruClientError::ValidationError(format!(
+                "Rate limit must be a positive number of requests per second, got \
+                 {requests_per_second}"
+            ))
+        };
+        if !requests_per_second.is_finite() || requests_per_second <= 0.0 {
+            return Err(invalid());
+        }
+        let interval =
+            Duration::try_from_secs_f64(1.0 / requests_per_second).map_err(|_| invalid())?;
+        self.rate_limiter = Some(RateLimiter::new(interval));
+        Ok(self)
+    }
+
+    /// Check the size of files against the server's
+    /// [maximum upload size](crate::models::GlobalInfoConfig::max_upload_size) before uploading
+    /// them, failing with [FileTooLarge](SzurubooruClientError::FileTooLarge) instead of sending
+    /// a file the server will reject anyway. The maximum is fetched with
+    /// [get_info](SzurubooruRequest::get_info) before the first upload and remembered for the
+    /// lifetime of the client. Servers that don't report a maximum accept any size. Content
+    /// streamed with [create_post_from_reader](SzurubooruRequest::create_post_from_reader) has no
+    /// known size and isn't checked. Disabled by default.
+    ///
+    /// ```no_run
+    /// use szurubooru_client::SzurubooruClient;
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true)
+    ///     .unwrap()
+    ///     .with_upload_size_check(true);
+    /// ```
+    pub fn with_upload_size_check(mut self, enabled: bool) -> Self {
+        self.upload_size_check = enabled;
+        self
+    }
+
+    /// Drops every response cached by [with_response_cache](Self::with_response_cache)
+    pub fn clear_response_cache(&self) {
+        if let Some(cache) = &self.response_cache {
+            cache.clear();
+        }
     }
 
     /// Construct a new request using the existing client auth and base URL
@@ -143,10 +460,30 @@ impl SzurubooruClient {
     /// # };
     /// # ()
     /// ```
-    pub fn request(&self) -> SzurubooruRequest {
+    pub fn request(&self) -> SzurubooruRequest<'_> {
         SzurubooruRequest::new(self)
     }
 
+    /// Checks that the server is reachable by fetching `/api/info`, returning how long the
+    /// round trip took. Handy for warming up the connection pool before a batch job.
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let latency = client.ping().await.expect("Server is down");
+    /// println!("Server responded in {}ms", latency.as_millis());
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn ping(&self) -> SzurubooruResult<Duration> {
+        let start = Instant::now();
+        self.request()
+            .do_request::<Value, _, _>(Method::GET, "/api/info", None, None::<&String>)
+            .await?;
+        Ok(start.elapsed())
+    }
+
     /// Construct a new request while selecting only the given fields
     /// The Szurubooru API supports selecting a subset of fields for a given resource.
     /// Most resource [models](crate::models) have [Option] fields because of that.
@@ -154,23 +491,23 @@ impl SzurubooruClient {
     /// See [here](https://github.com/rr-/szurubooru/blob/master/doc/API.md#field-selecting) for
     /// more details
     ///
-    /// For example, to select only the `version`, `id` and `content_url` fields of a
+    /// For example, to select only the `version`, `id` and `contentUrl` fields of a
     /// [PostResource]
     /// ```no_run
     /// # use szurubooru_client::SzurubooruClient;
     /// # #[allow(unused)]
     /// # async {
     /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
-    /// let new_request = client.request().with_fields(vec!["version".to_string(), "id".to_string(), "content_url".to_string()]);
+    /// let new_request = client.request().with_fields(vec!["version".to_string(), "id".to_string(), "contentUrl".to_string()]);
     /// # };
     /// # ()
     /// ```
-    pub fn with_fields(&self, fields: Vec<String>) -> SzurubooruRequest {
+    pub fn with_fields(&self, fields: Vec<String>) -> SzurubooruRequest<'_> {
         self.request().with_fields(fields)
     }
 
     /// The same as [with_fields](SzurubooruClient::with_fields), but accepts an Option type instead
-    pub fn with_optional_fields(&self, fields: Option<Vec<String>>) -> SzurubooruRequest {
+    pub fn with_optional_fields(&self, fields: Option<Vec<String>>) -> SzurubooruRequest<'_> {
         self.request().with_optional_fields(fields)
     }
 
@@ -191,12 +528,12 @@ impl SzurubooruClient {
     /// # };
     /// # ()
     /// ```
-    pub fn with_limit(&self, limit: u32) -> SzurubooruRequest {
+    pub fn with_limit(&self, limit: u32) -> SzurubooruRequest<'_> {
         self.request().with_limit(limit)
     }
 
     /// The same as [with_limit](SzurubooruClient::with_limit), but accepts an Option type instead
-    pub fn with_optional_limit(&self, limit: Option<u32>) -> SzurubooruRequest {
+    pub fn with_optional_limit(&self, limit: Option<u32>) -> SzurubooruRequest<'_> {
         self.request().with_optional_limit(limit)
     }
 
@@ -218,17 +555,164 @@ impl SzurubooruClient {
     /// # };
     /// # ()
     /// ```
-    pub fn with_offset(&self, offset: u32) -> SzurubooruRequest {
+    pub fn with_offset(&self, offset: u32) -> SzurubooruRequest<'_> {
         self.request().with_offset(offset)
     }
 
     /// The same as [with_offset](SzurubooruClient::with_offset), but accepts an Option type instead
-    pub fn with_optional_offset(&self, offset: Option<u32>) -> SzurubooruRequest {
+    pub fn with_optional_offset(&self, offset: Option<u32>) -> SzurubooruRequest<'_> {
         self.request().with_optional_offset(offset)
     }
 }
 
+#[derive(Debug, Clone)]
+/// How [with_retry](SzurubooruClient::with_retry) retries failed `GET` requests.
+///
+/// The delay before retry `n` is `base_delay * 2^(n - 1)`, capped at `max_delay`. If the server
+/// sends a `Retry-After` header, that delay is used instead, under the same cap
+pub struct RetryPolicy {
+    /// How many times to send a request in total, including the first attempt
+    pub max_attempts: u32,
+    /// The delay before the first retry
+    pub base_delay: Duration,
+    /// The longest the client will wait between attempts
+    pub max_delay: Duration,
+    /// The response statuses worth retrying
+    pub retry_statuses: Vec<StatusCode>,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts starting at a 500ms delay, retrying on `429`, `502`, `503` and `504`
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            retry_statuses: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// How long to wait before retrying after attempt number `attempt` got `response`
+    fn delay_for(&self, attempt: u32, response: &Response) -> Duration {
+        retry_after(response)
+            .unwrap_or_else(|| self.backoff(attempt))
+            .min(self.max_delay)
+    }
+
+    /// The exponential backoff after attempt number `attempt`, ignoring any `Retry-After`
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay)
+    }
+
+    /// Whether `e` is worth retrying an upload for: the request never got a reply, or the
+    /// reply had one of the [retryable statuses](Self::retry_statuses)
+    fn is_transient(&self, e: &SzurubooruClientError) -> bool {
+        match e {
+            SzurubooruClientError::RequestError(_) => true,
+            SzurubooruClientError::ResponseError(status, _) => self.retry_statuses.contains(status),
+            _ => false,
+        }
+    }
+}
+
+/// Response bodies of `GET` requests keyed by URL, for
+/// [with_response_cache](SzurubooruClient::with_response_cache)
+#[derive(Debug)]
+struct ResponseCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, String)>>,
+}
+
+impl ResponseCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<String> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        match entries.get(key) {
+            Some((stored, body)) if stored.elapsed() < self.ttl => Some(body.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, key: String, body: String) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|_, (stored, _)| stored.elapsed() < self.ttl);
+        entries.insert(key, (Instant::now(), body));
+    }
+
+    fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+/// Spaces out requests for [with_rate_limit](SzurubooruClient::with_rate_limit). This is a token
+/// bucket holding a single token, refilled every `interval`: each request reserves the next
+/// free slot and waits for it, so requests are evenly spaced and never sent in a burst
 #[derive(Debug)]
+struct RateLimiter {
+    interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            next_slot: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Waits until the next request may be sent
+    async fn acquire(&self) {
+        let wait = {
+            let mut next_slot = self.next_slot.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let slot = (*next_slot).max(now);
+            *next_slot = slot + self.interval;
+            slot - now
+        };
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Parses a `Retry-After` header given either as a number of seconds or as an HTTP date
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+#[derive(Debug, Clone)]
 /// A type that represents a single Szurubooru request.
 pub struct SzurubooruRequest<'a> {
     /// The currently selected fields to return (if applicable)
@@ -238,6 +722,8 @@ pub struct SzurubooruRequest<'a> {
     /// The number of resource to skip before returning any results
     /// (if supported by the API endpoint)
     pub offset: Option<u32>,
+    /// Tags that must already exist before a post is created
+    pub required_tags: Option<Vec<String>>,
     client: &'a SzurubooruClient,
 }
 
@@ -248,6 +734,7 @@ impl<'a> SzurubooruRequest<'a> {
             fields: None,
             limit: None,
             offset: None,
+            required_tags: None,
         }
     }
 
@@ -258,14 +745,19 @@ impl<'a> SzurubooruRequest<'a> {
     /// See [here](https://github.com/rr-/szurubooru/blob/master/doc/API.md#field-selecting) for
     /// more details
     ///
-    /// For example, to select only the `version`, `id` and `content_url` fields of a
+    /// Selecting fields can shrink responses considerably when listing many resources, at the
+    /// cost of every field that wasn't selected deserializing as `None`. Code reading the
+    /// results has to cope with that, so only select fields when the savings matter. Field
+    /// names are the API's camelCase ones, not the Rust field names.
+    ///
+    /// For example, to select only the `version`, `id` and `contentUrl` fields of a
     /// [PostResource]
     /// ```no_run
     /// # use szurubooru_client::SzurubooruClient;
     /// # #[allow(unused)]
     /// # async {
     /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
-    /// let new_request = client.request().with_fields(vec!["version".to_string(), "id".to_string(), "content_url".to_string()]);
+    /// let new_request = client.request().with_fields(vec!["version".to_string(), "id".to_string(), "contentUrl".to_string()]);
     /// # };
     /// # ()
     /// ```
@@ -343,6 +835,78 @@ impl<'a> SzurubooruRequest<'a> {
         }
     }
 
+    /// Require the given tags to already exist before creating a post, to enforce a controlled
+    /// vocabulary. Szurubooru normally creates any unknown tags a new post is tagged with. With
+    /// this set, the tags are looked up with [get_tags](Self::get_tags) first and the upload
+    /// fails with [MissingRequiredTags](SzurubooruClientError::MissingRequiredTags) if any don't
+    /// exist, without sending the post. Only post creation is checked, updates are sent as usual.
+    /// Names must match an existing tag or alias exactly, so `foo*` isn't satisfied by `foobar`.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # use szurubooru_client::models::PostSafety;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let post = client
+    ///     .request()
+    ///     .with_required_tags(vec!["cat".to_string(), "tabby".to_string()])
+    ///     .create_post_from_path("/tmp/cat.jpg", &["cat", "tabby"], PostSafety::Safe)
+    ///     .await;
+    /// # };
+    /// # ()
+    /// ```
+    pub fn with_required_tags(mut self, tags: Vec<String>) -> Self {
+        self.required_tags = Some(tags);
+        self
+    }
+
+    /// Fails with [FileTooLarge](SzurubooruClientError::FileTooLarge) if
+    /// [upload size checks](SzurubooruClient::with_upload_size_check) are enabled and `size` is
+    /// over the server's maximum
+    async fn check_upload_size(&self, size: u64) -> SzurubooruResult<()> {
+        if !self.client.upload_size_check {
+            return Ok(());
+        }
+        let max = match self.client.max_upload_size.get() {
+            Some(max) => *max,
+            None => {
+                let max = self
+                    .client
+                    .request()
+                    .get_info()
+                    .await?
+                    .config
+                    .max_upload_size;
+                *self.client.max_upload_size.get_or_init(|| max)
+            }
+        };
+        match max {
+            Some(max) if size > max => Err(SzurubooruClientError::FileTooLarge { size, max }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fails with [MissingRequiredTags](SzurubooruClientError::MissingRequiredTags) if any of the
+    /// [required tags](Self::with_required_tags) don't exist
+    async fn check_required_tags(&self) -> SzurubooruResult<()> {
+        let Some(required) = self.required_tags.as_ref().filter(|tags| !tags.is_empty()) else {
+            return Ok(());
+        };
+        let names = required.iter().map(String::as_str).collect::<Vec<_>>();
+        let found = self.client.request().get_tags(&names).await?;
+        let missing = required
+            .iter()
+            .filter(|name| !found.contains_key(name.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(SzurubooruClientError::MissingRequiredTags(missing))
+        }
+    }
+
     #[doc(hidden)]
     fn prep_request<T>(
         &self,
@@ -353,12 +917,63 @@ impl<'a> SzurubooruRequest<'a> {
     where
         T: AsRef<str> + Display,
     {
-        let mut req_url = if !path.as_ref().contains(&self.client.base_url.to_string()) {
-            let mut url = self.client.base_url.clone();
-            url.set_path(path.as_ref());
-            url
+        let req = self.prep_content_request(method, path, query);
+        if self.client.json_accept_header {
+            req.header(ACCEPT, "application/json")
+        } else {
+            req
+        }
+    }
+
+    /// Like [prep_request](Self::prep_request), but for fetching post content rather than
+    /// calling the API
+    fn prep_content_request<T>(
+        &self,
+        method: Method,
+        path: T,
+        query: Option<&Vec<QueryToken>>,
+    ) -> reqwest::RequestBuilder
+    where
+        T: AsRef<str> + Display,
+    {
+        let req_url = self.build_url(path.as_ref(), query);
+
+        let mut req = self.client.client.request(method, req_url);
+        if let Some(timeout) = self.client.request_timeout() {
+            req = req.timeout(timeout);
+        }
+        self.with_auth(req)
+    }
+
+    /// Adds the client's authentication to `req`
+    fn with_auth(&self, req: RequestBuilder) -> RequestBuilder {
+        match &self.client.auth {
+            SzurubooruAuth::TokenAuth(t) => {
+                let mut header_map = HeaderMap::new();
+                header_map.append(AUTHORIZATION, t.parse().unwrap());
+
+                req.headers(header_map)
+            }
+            SzurubooruAuth::BasicAuth(u, p) => req.basic_auth(u, Some(p)),
+            SzurubooruAuth::None => req,
+        }
+    }
+
+    /// Joins the API `path` onto the base URL, keeping any path the base URL already has so
+    /// servers hosted under a subdirectory like `https://example.com/booru` work. `path` is
+    /// used as is, without escaping anything but characters that can't appear in a path
+    fn endpoint_url(&self, path: &str) -> Url {
+        let mut url = self.client.base_url.clone();
+        let base_path = url.path().trim_end_matches('/').to_string();
+        url.set_path(&format!("{base_path}/{}", path.trim_start_matches('/')));
+        url
+    }
+
+    fn build_url(&self, path: &str, query: Option<&Vec<QueryToken>>) -> Url {
+        let mut req_url = if !path.contains(&self.client.base_url.to_string()) {
+            self.endpoint_url(path)
         } else {
-            Url::parse(path.as_ref()).unwrap()
+            Url::parse(path).unwrap()
         };
 
         if let Some(query_vec) = query {
@@ -383,19 +998,51 @@ impl<'a> SzurubooruRequest<'a> {
             qpm.append_pair("offset", &offset.to_string());
         }
 
-        // This doesn't detect the required `mut` for some reason
-        #[allow(unused_mut)]
-        let mut req = self.client.client.request(method, req_url);
-        match &self.client.auth {
-            SzurubooruAuth::TokenAuth(t) => {
-                let mut header_map = HeaderMap::new();
-                header_map.append(AUTHORIZATION, t.parse().unwrap());
+        req_url
+    }
 
-                req.headers(header_map)
+    /// Fails with a [ValidationError](SzurubooruClientError::ValidationError) naming `action`
+    /// if the client is anonymous
+    fn require_auth(&self, action: &str) -> SzurubooruResult<()> {
+        match self.client.auth {
+            SzurubooruAuth::None => Err(SzurubooruClientError::ValidationError(format!(
+                "{action} requires an authenticated client"
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Splits `values` into groups for `key:a,b,c` OR searches against `path`. Each group is
+    /// escaped and small enough that the full request URL, including this request's fields,
+    /// limit and offset, stays within the client's [max URL length](SzurubooruClient::with_max_url_length).
+    /// Groups also have at most `max_count` values.
+    fn chunk_or_query(
+        &self,
+        path: &str,
+        key: &str,
+        values: &[&str],
+        max_count: usize,
+    ) -> Vec<Vec<String>> {
+        let placeholder = vec![QueryToken {
+            key: key.to_string(),
+            value: "_".to_string(),
+        }];
+        let base_length = self.build_url(path, Some(&placeholder)).as_str().len() - 1;
+        let max_length = self.client.max_url_length.saturating_sub(base_length);
+        chunk_or_values(values, max_length, max_count)
+    }
+
+    /// A copy of this request that also selects `required`, if only some fields are selected
+    fn with_required_fields(&self, required: &[&str]) -> SzurubooruRequest<'a> {
+        let mut request = self.clone();
+        if let Some(fields) = request.fields.as_mut() {
+            for field in required {
+                if !fields.iter().any(|f| f == field) {
+                    fields.push(field.to_string());
+                }
             }
-            SzurubooruAuth::BasicAuth(u, p) => req.basic_auth(u, Some(p)),
-            SzurubooruAuth::None => req,
         }
+        request
     }
 
     #[tracing::instrument(skip(self), fields(base_url=self.client.base_url.to_string()))]
@@ -411,12 +1058,17 @@ impl<'a> SzurubooruRequest<'a> {
         B: Serialize + std::fmt::Debug,
         P: AsRef<str> + Display + std::fmt::Debug,
     {
+        if let (Some(query), Some(max_terms)) = (query, self.client.max_search_terms) {
+            query.check_term_count(max_terms)?;
+        }
         let mut request = self.prep_request(method, path, query);
 
         if let Some(b) = body {
             let b_str =
                 serde_json::to_string(b).map_err(SzurubooruClientError::JSONSerializationError)?;
-            request = request.body(b_str);
+            // Set per request as well, since a client from `with_reqwest_client` won't have it
+            // as a default header
+            request = request.header(CONTENT_TYPE, "application/json").body(b_str);
         }
 
         self.handle_request(request).await
@@ -430,9 +1082,10 @@ impl<'a> SzurubooruRequest<'a> {
                 .await
                 .map_err(SzurubooruClientError::RequestError)?;
 
-            let server_error = serde_json::from_str::<SzurubooruServerError>(&resp_json)
+            let mut server_error = serde_json::from_str::<SzurubooruServerError>(&resp_json)
                 .map_err(|_e| SzurubooruClientError::ResponseError(status, resp_json))?;
-            Err(SzurubooruClientError::SzurubooruServerError(server_error))
+            server_error.status = Some(status);
+            Err(server_error.into())
         } else {
             Ok(response)
         }
@@ -446,28 +1099,145 @@ impl<'a> SzurubooruRequest<'a> {
             .build()
             .map_err(SzurubooruClientError::RequestBuilderError)?;
 
-        let response = self.client.client.execute(request).await;
-
-        let response = self
-            .handle_response(response.map_err(SzurubooruClientError::RequestError)?)
-            .await?;
-
-        let response_text = response
-            .text()
-            .await
-            .map_err(SzurubooruClientError::RequestError)?;
+        let response_text = match &self.client.response_cache {
+            Some(cache) if request.method() == Method::GET => {
+                let key = request.url().to_string();
+                match cache.get(&key) {
+                    Some(cached) => cached,
+                    None => {
+                        let response_text = self.send_for_text(request).await?;
+                        cache.insert(key, response_text.clone());
+                        response_text
+                    }
+                }
+            }
+            Some(cache) => {
+                let response_text = self.send_for_text(request).await;
+                cache.clear();
+                response_text?
+            }
+            None => self.send_for_text(request).await?,
+        };
 
+        // Deletes may answer with `204 No Content`, which is as good as an empty object
+        if response_text.trim().is_empty() {
+            return serde_json::from_value(Value::Null)
+                .map_err(|e| SzurubooruClientError::ResponseParsingError(e, response_text));
+        }
+        if self.client.strict_deserialization {
+            return parse_strict(response_text);
+        }
         serde_json::from_str::<SzuruEither<T, SzurubooruServerError>>(&response_text)
             .map_err(|e| SzurubooruClientError::ResponseParsingError(e, response_text))?
             .into_result()
     }
 
-    fn propagate_urls<T>(&self, wbu: T) -> T
-    where
+    /// Sends `request`, returning the body of a successful response
+    async fn send_for_text(&self, request: reqwest::Request) -> SzurubooruResult<String> {
+        let response = self.execute_with_retry(request).await?;
+        let response = self.handle_response(response).await?;
+        response
+            .text()
+            .await
+            .map_err(SzurubooruClientError::RequestError)
+    }
+
+    /// Sends `request` once, after waiting for the [rate limit](SzurubooruClient::with_rate_limit)
+    async fn execute(&self, request: reqwest::Request) -> SzurubooruResult<Response> {
+        if let Some(rate_limiter) = &self.client.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+        self.client
+            .client
+            .execute(request)
+            .await
+            .map_err(SzurubooruClientError::RequestError)
+    }
+
+    /// Sends `request`, retrying it according to the client's [RetryPolicy] if it's a `GET`
+    async fn execute_with_retry(&self, request: reqwest::Request) -> SzurubooruResult<Response> {
+        let policy = match self.client.retry_policy() {
+            Some(policy) if request.method() == Method::GET => policy,
+            _ => return self.execute(request).await,
+        };
+
+        let mut attempt = 1;
+        loop {
+            // The last attempt, or a request whose body can't be replayed, is sent as-is
+            let Some(attempt_request) = (attempt < policy.max_attempts)
+                .then(|| request.try_clone())
+                .flatten()
+            else {
+                return self.execute(request).await;
+            };
+            let response = self.execute(attempt_request).await?;
+            if !policy.retry_statuses.contains(&response.status()) {
+                return Ok(response);
+            }
+
+            let delay = policy.delay_for(attempt, &response);
+            tracing::debug!(
+                "Retrying after {} (attempt {attempt}), waiting {delay:?}",
+                response.status()
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    fn propagate_urls<T>(&self, wbu: T) -> T
+    where
         T: WithBaseURL,
     {
-        #[allow(clippy::unnecessary_to_owned)]
-        wbu.with_base_url(&self.client.base_url.to_string())
+        // Content URLs are relative to the base URL's directory, so a base URL with a path
+        // like `https://example.com/booru` needs a trailing `/` to put them under it
+        let mut base_url = self.client.base_url.to_string();
+        if !base_url.ends_with('/') {
+            base_url.push('/');
+        }
+        wbu.with_base_url(&base_url)
+    }
+
+    /// Turns a paged search into a stream of individual results. `fetch` is called with a copy
+    /// of this request for each page, starting at this request's offset. Each page uses this
+    /// request's limit, or the server default if none is set. Iteration stops after an empty
+    /// page, once the offset reaches the reported total, or after the first error.
+    fn paginate<T, F, Fut>(&self, fetch: F) -> impl Stream<Item = SzurubooruResult<T>> + 'a
+    where
+        T: 'a,
+        F: Fn(SzurubooruRequest<'a>) -> Fut + 'a,
+        Fut: Future<Output = SzurubooruResult<PagedSearchResult<T>>> + 'a,
+    {
+        let state = PageState {
+            request: self.clone(),
+            fetch,
+            offset: self.offset.unwrap_or(0),
+            page: VecDeque::new(),
+            done: false,
+        };
+        stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(item) = state.page.pop_front() {
+                    return Some((Ok(item), state));
+                }
+                if state.done {
+                    return None;
+                }
+
+                let request = state.request.clone().with_offset(state.offset);
+                match (state.fetch)(request).await {
+                    Ok(page) => {
+                        state.offset += page.results.len() as u32;
+                        state.done = page.results.is_empty() || state.offset >= page.total;
+                        state.page.extend(page.results);
+                    }
+                    Err(e) => {
+                        state.done = true;
+                        return Some((Err(e), state));
+                    }
+                }
+            }
+        })
     }
 
     /// Lists all tag categories. Doesn't use paging.
@@ -538,6 +1308,35 @@ impl<'a> SzurubooruRequest<'a> {
             .await
     }
 
+    /// Lists all tag categories along with the number of tags in each category.
+    /// The tag count is fetched with a separate `category:<name>` tag search per category, using
+    /// the [total](crate::models::PagedSearchResult::total) of the search result. If
+    /// [fields](Self::with_fields) are selected, `name` is always added to them. A category the
+    /// server still returns without a name is left out, as there's nothing to search for.
+    pub async fn tag_category_stats(&self) -> SzurubooruResult<Vec<(TagCategoryResource, u32)>> {
+        let categories = self
+            .with_required_fields(&["name"])
+            .list_tag_categories()
+            .await?;
+        let mut stats = Vec::with_capacity(categories.results.len());
+        for category in categories.results {
+            let Some(name) = category.name.as_deref() else {
+                continue;
+            };
+            let query = vec![QueryToken::token_literal(TagNamedToken::Category, name)];
+            let tag_count = self
+                .client
+                .request()
+                .with_fields(vec!["names".to_string()])
+                .with_limit(1)
+                .list_tags(Some(&query))
+                .await?
+                .total;
+            stats.push((category, tag_count));
+        }
+        Ok(stats)
+    }
+
     /// Searches for tags.
     /// See the [named tokens](crate::tokens::TagNamedToken) and
     /// [sort tokens](crate::tokens::TagSortToken) for all possible query tokens, or use
@@ -550,6 +1349,28 @@ impl<'a> SzurubooruRequest<'a> {
             .await
     }
 
+    /// Starts a search for tags configured with chainable methods, as an alternative to
+    /// [list_tags](Self::list_tags). See [ListBuilder]
+    pub fn list_tags_builder(&self) -> ListTagsBuilder<'a> {
+        ListBuilder::new(self.clone(), |request, query| {
+            Box::pin(async move { request.list_tags(query.as_ref()).await })
+        })
+    }
+
+    /// Streams every result of [list_tags](Self::list_tags), fetching the next page once the
+    /// current one is used up. The page size is this request's [limit](Self::with_limit), or the
+    /// server default if none is set. Errors are returned as stream items and end the stream.
+    pub fn stream_tags(
+        &self,
+        query: Option<&Vec<QueryToken>>,
+    ) -> impl Stream<Item = SzurubooruResult<TagResource>> + 'a {
+        let query = query.cloned();
+        self.paginate(move |request| {
+            let query = query.clone();
+            async move { request.list_tags(query.as_ref()).await }
+        })
+    }
+
     /// Creates a new tag using specified parameters. Names, suggestions and implications must
     /// match `tag_name_regex` from server's configuration. Category must exist and is the same
     /// as the `name` field within [TagCategoryResource] resource.
@@ -569,6 +1390,10 @@ impl<'a> SzurubooruRequest<'a> {
     /// not exist yet, they will be automatically created. Tags created automatically have no
     /// implications, no suggestions, one name and their category is set to the first tag category
     /// found. If there are no tag categories established yet, an error will be thrown.
+    ///
+    /// `update_tag` must carry the tag's current [version](CreateUpdateTag::version). If the tag
+    /// was changed since, this fails with a
+    /// [VersionConflict](SzurubooruClientError::VersionConflict).
     pub async fn update_tag<T>(
         &self,
         name: T,
@@ -577,7 +1402,7 @@ impl<'a> SzurubooruRequest<'a> {
     where
         T: AsRef<str> + Display,
     {
-        let path = format!("/api/tag/{name}");
+        let path = tag_path("tag", name.as_ref());
         self.do_request(Method::PUT, &path, None, Some(update_tag))
             .await
     }
@@ -587,17 +1412,90 @@ impl<'a> SzurubooruRequest<'a> {
     where
         T: AsRef<str> + Display,
     {
-        let path = format!("/api/tag/{name}");
+        let path = tag_path("tag", name.as_ref());
         self.do_request(Method::GET, &path, None, None::<&String>)
             .await
     }
 
+    /// Looks up the tag with exactly the name `name`, or one of its aliases, returning `None` if
+    /// there isn't one. Unlike a `name:` search, `*` in `name` is matched literally rather than
+    /// as a wildcard, so looking up `foo` never returns `foobar`. Names are compared ignoring
+    /// ASCII case, the same as the server does.
+    ///
+    /// If [fields](Self::with_fields) are selected, they must include `names` and `version`.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// if client.request().get_tag_exact("foo").await.unwrap().is_none() {
+    ///     println!("No tag called foo yet");
+    /// }
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn get_tag_exact(&self, name: &str) -> SzurubooruResult<Option<TagResource>> {
+        let query = vec![QueryToken::token_literal(TagNamedToken::Name, name)];
+        let found = self.list_tags(Some(&query)).await?;
+        Ok(found.results.into_iter().find(|tag| {
+            tag.names
+                .iter()
+                .flatten()
+                .any(|tag_name| tag_name.eq_ignore_ascii_case(name))
+        }))
+    }
+
+    /// Resolves a list of tag names to their [TagResource]s using as few requests as possible.
+    /// The names are batched into `name:a,b,c` searches, split up so no request URL gets longer
+    /// than the client's [max URL length](SzurubooruClient::with_max_url_length). The returned
+    /// map is keyed by the requested name. Names that don't match any tag are left out of the map.
+    /// Like [get_tag_exact](Self::get_tag_exact), names are matched literally, so `*`, `,`, `:`
+    /// or a leading `-` in a name doesn't change the search.
+    ///
+    /// If [fields](Self::with_fields) are selected, they must include `names` and `version`.
+    pub async fn get_tags(&self, names: &[&str]) -> SzurubooruResult<HashMap<String, TagResource>> {
+        let mut tags = HashMap::with_capacity(names.len());
+        let request = self
+            .clone()
+            .with_offset(0)
+            .with_limit(MAX_TAG_BATCH_SIZE as u32);
+        let key = TagNamedToken::Name.as_ref();
+        for chunk in request.chunk_or_query("/api/tags", key, names, MAX_TAG_BATCH_SIZE) {
+            let query = vec![QueryToken {
+                key: key.to_string(),
+                value: chunk.join(","),
+            }];
+            let found = request
+                .clone()
+                .with_limit(chunk.len() as u32)
+                .list_tags(Some(&query))
+                .await?;
+            for tag in found.results {
+                let tag_names = tag.names.clone().unwrap_or_default();
+                for name in names {
+                    if tag_names.iter().any(|tn| tn.eq_ignore_ascii_case(name)) {
+                        tags.insert(name.to_string(), tag.clone());
+                    }
+                }
+            }
+        }
+        Ok(tags)
+    }
+
     /// Deletes existing tag. The tag to be deleted must have no usages.
+    ///
+    /// `version` must be the tag's current version, otherwise this fails with a
+    /// [VersionConflict](SzurubooruClientError::VersionConflict). A tag that doesn't exist fails
+    /// with a [SzurubooruServerError](SzurubooruClientError::SzurubooruServerError) named
+    /// [TagNotFoundError](SzurubooruServerErrorType::TagNotFoundError). Like everywhere else a
+    /// tag is looked up by name, `name` is URL-encoded, so names containing characters such as
+    /// `/`, `?` or `%` are sent as-is.
     pub async fn delete_tag<T>(&self, name: T, version: u32) -> SzurubooruResult<()>
     where
         T: AsRef<str> + Display,
     {
-        let path = format!("/api/tag/{name}");
+        let path = tag_path("tag", name.as_ref());
         let version_obj = ResourceVersion { version };
         self.do_request::<Value, _, _>(Method::DELETE, &path, None, Some(&version_obj))
             .await
@@ -606,7 +1504,31 @@ impl<'a> SzurubooruRequest<'a> {
 
     /// Removes source tag and merges all of its usages, suggestions and implications to the
     /// target tag. Other tag properties such as category and aliases do not get transferred
-    /// and are discarded.
+    /// and are discarded. Returns the surviving tag.
+    ///
+    /// Both tags' current versions must be given. If either tag doesn't exist, this fails with
+    /// a [SzurubooruServerError](SzurubooruClientError::SzurubooruServerError) named
+    /// [TagNotFoundError](SzurubooruServerErrorType::TagNotFoundError), and with a
+    /// [VersionConflict](SzurubooruClientError::VersionConflict) if either version is out of
+    /// date.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # use szurubooru_client::models::MergeTagsBuilder;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let merge = MergeTagsBuilder::default()
+    ///     .remove_tag("kitty".to_string())
+    ///     .remove_tag_version(1)
+    ///     .merge_to_tag("cat".to_string())
+    ///     .merge_to_version(4)
+    ///     .build()
+    ///     .unwrap();
+    /// let cat = client.request().merge_tags(&merge).await;
+    /// # };
+    /// # ()
+    /// ```
     pub async fn merge_tags(&self, merge_opts: &MergeTags) -> SzurubooruResult<TagResource> {
         self.do_request(Method::POST, "/api/tag-merge", None, Some(merge_opts))
             .await
@@ -623,7 +1545,7 @@ impl<'a> SzurubooruRequest<'a> {
     where
         T: AsRef<str> + Display,
     {
-        let path = format!("/api/tag-siblings/{name}");
+        let path = tag_path("tag-siblings", name.as_ref());
         self.do_request(Method::GET, &path, None, None::<&String>)
             .await
     }
@@ -631,15 +1553,485 @@ impl<'a> SzurubooruRequest<'a> {
     /// Searches for posts.
     /// See [PostNamedToken], [PostSortToken] and [PostSpecialToken] for valid tokens to use with
     /// this method, or use [QueryToken] to construct a custom token
+    ///
+    /// If [fields](Self::with_fields) are selected, `version` is always added to them, so the
+    /// listed posts can be passed straight to [update_post](Self::update_post) without fetching
+    /// them again.
     pub async fn list_posts(
         &self,
         query: Option<&Vec<QueryToken>>,
     ) -> SzurubooruResult<PagedSearchResult<PostResource>> {
-        self.do_request(Method::GET, "/api/posts", query, None::<&String>)
+        let mut page = self.list_posts_page(query).await?;
+        page.results.retain(|post| !self.is_blocked(post));
+        Ok(page)
+    }
+
+    /// Fetches a page of posts like [list_posts](Self::list_posts), but leaves any unsafe posts
+    /// the server returns in it, so the streams built on it can page by what the server sent
+    async fn list_posts_page(
+        &self,
+        query: Option<&Vec<QueryToken>>,
+    ) -> SzurubooruResult<PagedSearchResult<PostResource>> {
+        if !self.client.block_unsafe {
+            return self
+                .with_required_fields(&["version"])
+                .do_request(Method::GET, "/api/posts", query, None::<&String>)
+                .await
+                .map(|pr| self.propagate_urls(pr));
+        }
+
+        let Some(query) = without_unsafe(query) else {
+            return Ok(self.empty_page(query));
+        };
+        self.with_required_fields(&["version", "safety"])
+            .do_request(Method::GET, "/api/posts", Some(&query), None::<&String>)
             .await
             .map(|pr| self.propagate_urls(pr))
     }
 
+    /// Whether `post` is kept out of results because the client
+    /// [blocks unsafe posts](SzurubooruClient::with_block_unsafe)
+    fn is_blocked(&self, post: &PostResource) -> bool {
+        self.client.block_unsafe && post.safety == Some(PostSafety::Unsafe)
+    }
+
+    /// The page for a search that can't match anything, so doesn't need sending
+    fn empty_page<T>(&self, query: Option<&Vec<QueryToken>>) -> PagedSearchResult<T> {
+        PagedSearchResult {
+            query: query.map(|q| q.to_query_string()).unwrap_or_default(),
+            offset: self.offset.unwrap_or(0),
+            limit: self.limit.unwrap_or(0),
+            total: 0,
+            results: Vec::new(),
+        }
+    }
+
+    /// Starts a search for posts configured with chainable methods, as an alternative to
+    /// [list_posts](Self::list_posts). See [ListBuilder]
+    pub fn list_posts_builder(&self) -> ListPostsBuilder<'a> {
+        ListBuilder::new(self.clone(), |request, query| {
+            Box::pin(async move { request.list_posts(query.as_ref()).await })
+        })
+    }
+
+    /// Searches for posts of the given [PostType], using its canonical name as the `type:` token
+    /// value (e.g. `type:video`). Any `extra` tokens are added after it, such as a
+    /// [Safety](PostNamedToken::Safety) token to cover the other common filter
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # use szurubooru_client::models::{PostSafety, PostType};
+    /// # use szurubooru_client::tokens::{PostNamedToken, QueryToken};
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let safe = vec![QueryToken::token(PostNamedToken::Safety, PostSafety::Safe)];
+    /// let safe_videos = client.request()
+    ///     .list_posts_of_type(PostType::Video, Some(&safe))
+    ///     .await;
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn list_posts_of_type(
+        &self,
+        post_type: PostType,
+        extra: Option<&Vec<QueryToken>>,
+    ) -> SzurubooruResult<PagedSearchResult<PostResource>> {
+        let mut query = vec![QueryToken::token(PostNamedToken::Type, post_type)];
+        query.extend(extra.into_iter().flatten().cloned());
+        self.list_posts(Some(&query)).await
+    }
+
+    /// Returns up to `limit` of the highest scored posts, e.g. to pick candidates for the
+    /// featured post. Any `extra` tokens are added after the `sort:score` token, such as a
+    /// [Safety](PostNamedToken::Safety) token to keep unsafe posts out of the running
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # use szurubooru_client::models::PostSafety;
+    /// # use szurubooru_client::tokens::{PostNamedToken, QueryToken};
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let safe = vec![QueryToken::token(PostNamedToken::Safety, PostSafety::Safe)];
+    /// let candidates = client.request()
+    ///     .top_scored_posts(10, Some(&safe))
+    ///     .await;
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn top_scored_posts(
+        &self,
+        limit: u32,
+        extra: Option<&Vec<QueryToken>>,
+    ) -> SzurubooruResult<Vec<PostResource>> {
+        let mut query = vec![QueryToken::sort(PostSortToken::Score)];
+        query.extend(extra.into_iter().flatten().cloned());
+        self.clone()
+            .with_limit(limit)
+            .list_posts(Some(&query))
+            .await
+            .map(|page| page.results)
+    }
+
+    /// Streams every result of [list_posts](Self::list_posts), fetching the next page once the
+    /// current one is used up. The page size is this request's [limit](Self::with_limit), or the
+    /// server default if none is set. Errors are returned as stream items and end the stream.
+    pub fn stream_posts(
+        &self,
+        query: Option<&Vec<QueryToken>>,
+    ) -> impl Stream<Item = SzurubooruResult<PostResource>> + 'a {
+        let query = query.cloned();
+        let request = self.clone();
+        self.paginate(move |request| {
+            let query = query.clone();
+            async move { request.list_posts_page(query.as_ref()).await }
+        })
+        .try_filter(move |post| std::future::ready(!request.is_blocked(post)))
+    }
+
+    /// Streams every post matching `query` like [stream_posts](Self::stream_posts), but as the
+    /// raw JSON objects returned by the server instead of [PostResource]s. Nothing is dropped or
+    /// renamed, so fields the models don't know about, e.g. from a newer server or a plugin, are
+    /// kept. URLs are left relative to the server as sent, and
+    /// [strict deserialization](SzurubooruClient::with_strict_deserialization) doesn't apply.
+    /// [Blocking unsafe posts](SzurubooruClient::with_block_unsafe) still does.
+    ///
+    /// See [raw_json](Self::raw_json) for other endpoints.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # #[allow(unused)]
+    /// # async {
+    /// use futures_util::StreamExt;
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let request = client.with_limit(100);
+    /// let mut posts = std::pin::pin!(request.list_posts_raw(None));
+    /// while let Some(post) = posts.next().await {
+    ///     let post = post.unwrap();
+    ///     println!("{}: {}", post["id"], post);
+    /// }
+    /// # };
+    /// # ()
+    /// ```
+    pub fn list_posts_raw(
+        &self,
+        query: Option<&Vec<QueryToken>>,
+    ) -> impl Stream<Item = SzurubooruResult<Value>> + 'a {
+        let query = query.cloned();
+        let block_unsafe = self.client.block_unsafe;
+        let unsafe_value = PostSafety::Unsafe.as_ref();
+        self.paginate(move |request| {
+            let query = query.clone();
+            async move { request.list_posts_raw_page(query.as_ref()).await }
+        })
+        .try_filter(move |post| {
+            let safety = post.get("safety").and_then(Value::as_str);
+            std::future::ready(!(block_unsafe && safety == Some(unsafe_value)))
+        })
+    }
+
+    async fn list_posts_raw_page(
+        &self,
+        query: Option<&Vec<QueryToken>>,
+    ) -> SzurubooruResult<PagedSearchResult<Value>> {
+        if !self.client.block_unsafe {
+            return self
+                .do_request(Method::GET, "/api/posts", query, None::<&String>)
+                .await;
+        }
+
+        let Some(query) = without_unsafe(query) else {
+            return Ok(self.empty_page(query));
+        };
+        self.with_required_fields(&["safety"])
+            .do_request(Method::GET, "/api/posts", Some(&query), None::<&String>)
+            .await
+    }
+
+    /// Streams the posts uploaded by `username`, newest first. Pages are fetched as the stream
+    /// is consumed, using this request's [limit](Self::with_limit) as the page size.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # #[allow(unused)]
+    /// # async {
+    /// use futures_util::StreamExt;
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let request = client.with_limit(50);
+    /// let mut uploads = std::pin::pin!(request.user_uploads("someone"));
+    /// while let Some(post) = uploads.next().await {
+    ///     println!("{:?}", post.unwrap().id);
+    /// }
+    /// # };
+    /// # ()
+    /// ```
+    pub fn user_uploads(
+        &self,
+        username: &str,
+    ) -> impl Stream<Item = SzurubooruResult<PostResource>> + 'a {
+        let query = vec![
+            QueryToken::token(PostNamedToken::Uploader, username),
+            QueryToken::sort(PostSortToken::CreationDate),
+        ];
+        self.stream_posts(Some(&query))
+    }
+
+    /// Streams the posts that have no tags at all (`tag-count:0`), newest first, for tidying up
+    /// uploads that still need tagging. Pages are fetched as the stream is consumed, using this
+    /// request's [limit](Self::with_limit) as the page size.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # #[allow(unused)]
+    /// # async {
+    /// use futures_util::StreamExt;
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let request = client.with_fields(vec!["id".to_string()]);
+    /// let mut untagged = std::pin::pin!(request.untagged_posts());
+    /// while let Some(post) = untagged.next().await {
+    ///     println!("{:?}", post.unwrap().id);
+    /// }
+    /// # };
+    /// # ()
+    /// ```
+    pub fn untagged_posts(&self) -> impl Stream<Item = SzurubooruResult<PostResource>> + 'a {
+        let query = vec![
+            QueryToken::token(PostNamedToken::TagCount, "0"),
+            QueryToken::sort(PostSortToken::CreationDate),
+        ];
+        self.stream_posts(Some(&query))
+    }
+
+    /// Streams the posts matching `query` from newest to oldest, paging by post ID instead of
+    /// by offset. Each page asks for posts with an ID below the last one seen
+    /// (`id:..<last - 1> sort:id`), so posts uploaded or deleted while iterating don't shift
+    /// the pages and nothing is returned twice or skipped.
+    ///
+    /// The trade-offs compared to offset-based paging such as
+    /// [user_uploads](Self::user_uploads) are:
+    /// * Results are always sorted by ID, so any sort tokens in `query` are dropped
+    /// * Posts uploaded after iteration starts aren't included
+    /// * This request's [offset](Self::with_offset) is ignored
+    /// * The `id` field is always requested, even if other [fields](Self::with_fields) are selected
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # #[allow(unused)]
+    /// # async {
+    /// use futures_util::StreamExt;
+    /// use szurubooru_client::tokens::QueryToken;
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let query = vec![QueryToken::anonymous("cat")];
+    /// let request = client.with_limit(100);
+    /// let mut posts = std::pin::pin!(request.list_posts_cursor(Some(&query)));
+    /// while let Some(post) = posts.next().await {
+    ///     println!("{:?}", post.unwrap().id);
+    /// }
+    /// # };
+    /// # ()
+    /// ```
+    pub fn list_posts_cursor(
+        &self,
+        query: Option<&Vec<QueryToken>>,
+    ) -> impl Stream<Item = SzurubooruResult<PostResource>> + 'a {
+        let mut request = self.with_required_fields(&["id"]);
+        request.offset = None;
+        let query: Vec<QueryToken> = query
+            .map(|q| q.iter().filter(|qt| !qt.is_sort()).cloned().collect())
+            .unwrap_or_default();
+        let state = CursorState {
+            request,
+            query,
+            cursor: None,
+            page: VecDeque::new(),
+            done: false,
+        };
+        let filter = self.clone();
+        stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(post) = state.page.pop_front() {
+                    return Some((Ok(post), state));
+                }
+                if state.done {
+                    return None;
+                }
+
+                let mut query = state.query.clone();
+                if let Some(cursor) = state.cursor {
+                    let below_cursor = RangeToken::at_most(cursor.saturating_sub(1));
+                    query.push(QueryToken::token(PostNamedToken::Id, below_cursor));
+                }
+                query.push(QueryToken::sort(PostSortToken::Id));
+                match state.request.list_posts_page(Some(&query)).await {
+                    Ok(page) => {
+                        state.done = page.results.len() as u32 >= page.total;
+                        match page.results.last().and_then(|post| post.id) {
+                            Some(last_id) => state.cursor = Some(last_id),
+                            None => state.done = true,
+                        }
+                        state.page.extend(page.results);
+                    }
+                    Err(e) => {
+                        state.done = true;
+                        return Some((Err(e), state));
+                    }
+                }
+            }
+        })
+        .try_filter(move |post| std::future::ready(!filter.is_blocked(post)))
+    }
+
+    /// Streams groups of likely-duplicate posts among those matching `query`, for cleaning up
+    /// an instance. Posts are checked one at a time from newest to oldest using
+    /// [list_posts_cursor](Self::list_posts_cursor), so apart from the IDs of posts already
+    /// checked or grouped, memory use stays bounded no matter how many posts there are, and
+    /// clusters are returned as soon as they're found.
+    ///
+    /// Each post is grouped with the other posts that have the same content checksum, including
+    /// newer ones that don't match `query`. If `similarity_threshold` is set, image and animation
+    /// posts are also downloaded and [reverse searched](Self::reverse_search_bytes), grouping them
+    /// with older posts whose similarity (`1 - distance`) is at least the threshold. Every post
+    /// appears in at most one cluster: the first one found, reported by its newest post matching
+    /// `query`. A post already grouped with a newer one isn't checked itself, so it doesn't start a
+    /// second cluster for posts that only look like it. This costs one extra request per post, or
+    /// three with reverse search, so narrow `query` or stop early with
+    /// [take](futures_util::StreamExt::take) on large instances.
+    ///
+    /// Errors while checking a post are returned as stream items and the search carries on;
+    /// errors while listing posts end the stream.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # #[allow(unused)]
+    /// # async {
+    /// use futures_util::StreamExt;
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let request = client.request();
+    /// let mut clusters = std::pin::pin!(request.find_duplicates(Some(0.9), None));
+    /// while let Some(cluster) = clusters.next().await {
+    ///     let cluster = cluster.unwrap();
+    ///     println!("{:?} has {} exact and {} similar duplicates",
+    ///         cluster.post.id, cluster.exact.len(), cluster.similar.len());
+    /// }
+    /// # };
+    /// # ()
+    /// ```
+    pub fn find_duplicates(
+        &self,
+        similarity_threshold: Option<f32>,
+        query: Option<&Vec<QueryToken>>,
+    ) -> impl Stream<Item = SzurubooruResult<DuplicateCluster>> + 'a {
+        let request = self.with_required_fields(&["checksum", "type", "mimeType"]);
+        let mut checker = request.clone();
+        checker.offset = None;
+        let visited = Arc::new(Mutex::new(HashSet::new()));
+        let grouped = Arc::new(Mutex::new(HashSet::new()));
+        request
+            .list_posts_cursor(query)
+            .and_then(move |post| {
+                let checker = checker.clone();
+                let visited = visited.clone();
+                let grouped = grouped.clone();
+                async move {
+                    checker
+                        .duplicates_of(post, similarity_threshold, &visited, &grouped)
+                        .await
+                }
+            })
+            .try_filter_map(|cluster| async move { Ok(cluster) })
+    }
+
+    /// Finds the duplicates of `post` that aren't in `grouped` yet, or `None` if it has none, is
+    /// already grouped or a newer post with its checksum was already in `visited`. `post` is
+    /// added to `visited`, and the posts of a returned cluster to `grouped`
+    async fn duplicates_of(
+        &self,
+        post: PostResource,
+        similarity_threshold: Option<f32>,
+        visited: &Mutex<HashSet<u32>>,
+        grouped: &Mutex<HashSet<u32>>,
+    ) -> SzurubooruResult<Option<DuplicateCluster>> {
+        let Some(post_id) = post.id else {
+            return Ok(None);
+        };
+        visited.lock().unwrap().insert(post_id);
+        if grouped.lock().unwrap().contains(&post_id) {
+            return Ok(None);
+        }
+
+        let mut exact = Vec::new();
+        if let Some(checksum) = post.checksum.as_deref() {
+            let qt = QueryToken::token(PostNamedToken::ContentChecksum, checksum);
+            let matches = self
+                .clone()
+                .with_limit(MAX_DUPLICATE_LOOKUP_SIZE)
+                .list_posts(Some(&vec![qt]))
+                .await?
+                .results;
+            let visited = visited.lock().unwrap();
+            if matches
+                .iter()
+                .any(|m| m.id.is_some_and(|id| id > post_id && visited.contains(&id)))
+            {
+                // A newer copy matching the query already reported this cluster
+                return Ok(None);
+            }
+            drop(visited);
+            exact = matches
+                .into_iter()
+                .filter(|m| m.id != Some(post_id))
+                .collect();
+        }
+
+        let mut similar = Vec::new();
+        let searchable = matches!(
+            post.post_type,
+            Some(PostType::Image | PostType::Animation | PostType::Animated | PostType::Anim)
+        );
+        if let (Some(threshold), true) = (similarity_threshold, searchable) {
+            let content = self
+                .get_image_bytestream(post_id)
+                .await?
+                .try_fold(Vec::new(), |mut content, chunk| async move {
+                    content.extend_from_slice(&chunk);
+                    Ok(content)
+                })
+                .await
+                .map_err(SzurubooruClientError::RequestError)?;
+            let mime = post
+                .mime_type
+                .as_deref()
+                .unwrap_or("application/octet-stream");
+            similar = self
+                .reverse_search_bytes(content, mime)
+                .await?
+                .similar_posts
+                .into_iter()
+                .filter(|s| s.post.id.is_some_and(|id| id < post_id))
+                .filter(|s| 1.0 - s.distance >= threshold)
+                .collect();
+            similar.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        }
+
+        let mut grouped = grouped.lock().unwrap();
+        let ungrouped = |id: Option<u32>| id.is_some_and(|id| !grouped.contains(&id));
+        exact.retain(|p| ungrouped(p.id));
+        similar.retain(|s| ungrouped(s.post.id));
+        if exact.is_empty() && similar.is_empty() {
+            Ok(None)
+        } else {
+            grouped.insert(post_id);
+            grouped.extend(exact.iter().filter_map(|p| p.id));
+            grouped.extend(similar.iter().filter_map(|s| s.post.id));
+            Ok(Some(DuplicateCluster {
+                post,
+                exact,
+                similar,
+            }))
+        }
+    }
+
     async fn create_update_post_from_url(
         &self,
         path: &str,
@@ -651,6 +2043,9 @@ impl<'a> SzurubooruRequest<'a> {
                 "Safety must be set".to_string(),
             ));
         }
+        if method == Method::POST {
+            self.check_required_tags().await?;
+        }
         self.do_request(method, path, None, Some(cupost)).await
     }
 
@@ -665,6 +2060,27 @@ impl<'a> SzurubooruRequest<'a> {
     /// Sending empty thumbnail will cause the post to use default thumbnail. If `anonymous` is set
     /// to `true`, the uploader name won't be recorded (privilege verification still applies;
     /// it's possible to disallow anonymous uploads completely from config.)
+    ///
+    /// Nothing is downloaded by the client. If the server can't fetch the URL the error is a
+    /// [DownloadError](SzurubooruServerErrorType::DownloadError), and content it can't use is
+    /// reported as [InvalidPostContentError](SzurubooruServerErrorType::InvalidPostContentError).
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # use szurubooru_client::models::{CreateUpdatePostBuilder, PostSafety};
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let new_post = CreateUpdatePostBuilder::default()
+    ///     .content_url("https://example.com/cat.jpg".to_string())
+    ///     .tags(vec!["cat".to_string()])
+    ///     .safety(PostSafety::Safe)
+    ///     .build()
+    ///     .unwrap();
+    /// let post = client.request().create_post_from_url(&new_post).await;
+    /// # };
+    /// # ()
+    /// ```
     pub async fn create_post_from_url(
         &self,
         new_post: &CreateUpdatePost,
@@ -688,29 +2104,319 @@ impl<'a> SzurubooruRequest<'a> {
             .map(|pr| self.propagate_urls(pr))
     }
 
-    /// Update an existing post from a given URL
-    /// See [SzurubooruRequest::create_post_from_url] for more details about the fields in
-    /// [CreateUpdatePost]
-    pub async fn update_post_from_url(
+    /// Adds and removes tags on every post matching `query`, updating up to `concurrency` posts
+    /// at a time. The matching post IDs are collected before any post is modified, so changing
+    /// the tags doesn't shift the pages of the search. Each post is refetched right before it's
+    /// updated and retried a few times if someone else edits it at the same time.
+    ///
+    /// Posts are processed from the highest ID down, so any sort tokens in `query` are ignored.
+    /// After each post, in order, `on_progress` is called with a [BulkCursor] for it. Save the
+    /// latest cursor and pass it as `resume_from` to pick an interrupted job back up without
+    /// going through the posts before it again. Posts that failed are skipped when resuming as
+    /// well, so retry the ones in [failed](RetagSummary::failed) separately.
+    ///
+    /// Only failing to search for posts returns an error, per-post failures are collected in
+    /// the [RetagSummary].
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # #[allow(unused)]
+    /// # async {
+    /// use szurubooru_client::models::BulkCursor;
+    /// use szurubooru_client::tokens::QueryToken;
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let query = vec![QueryToken::anonymous("kitten")];
+    /// let summary = client.request()
+    ///     .retag_search(Some(&query), &["cat"], &["kitten"], 4, None, None)
+    ///     .await
+    ///     .unwrap();
+    /// println!("Retagged {} posts", summary.updated.len());
+    ///
+    /// // Pick up where an earlier run left off, saving progress to disk as it goes
+    /// let resume_from: Option<BulkCursor> = std::fs::read("retag.cursor")
+    ///     .ok()
+    ///     .and_then(|saved| serde_json::from_slice(&saved).ok());
+    /// let mut save_progress = |cursor: BulkCursor| {
+    ///     std::fs::write("retag.cursor", serde_json::to_vec(&cursor).unwrap()).unwrap();
+    /// };
+    /// client.request()
+    ///     .retag_search(Some(&query), &["cat"], &["kitten"], 4, resume_from,
+    ///         Some(&mut save_progress))
+    ///     .await
+    ///     .unwrap();
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn retag_search(
         &self,
-        post_id: u32,
-        update_post: &CreateUpdatePost,
-    ) -> SzurubooruResult<PostResource> {
-        assert!(update_post.content_url.is_some());
-        let path = format!("/api/post/{post_id}");
-        self.create_update_post_from_url(&path, Method::PUT, update_post)
-            .await
-            .map(|pr| self.propagate_urls(pr))
-    }
-
-    // Create function to upload by byte array in the future
+        query: Option<&Vec<QueryToken>>,
+        add: &[&str],
+        remove: &[&str],
+        concurrency: usize,
+        resume_from: Option<BulkCursor>,
+        mut on_progress: Option<&mut (dyn FnMut(BulkCursor) + Send)>,
+    ) -> SzurubooruResult<RetagSummary> {
+        let mut query: Vec<QueryToken> = query
+            .map(|q| q.iter().filter(|qt| !qt.is_sort()).cloned().collect())
+            .unwrap_or_default();
+        if let Some(cursor) = resume_from {
+            let below_cursor = RangeToken::at_most(cursor.last_post_id.saturating_sub(1));
+            query.push(QueryToken::token(PostNamedToken::Id, below_cursor));
+        }
+        query.push(QueryToken::sort(PostSortToken::Id));
+        let matches: Vec<PostResource> = self
+            .clone()
+            .with_fields(vec!["id".to_string()])
+            .stream_posts(Some(&query))
+            .try_collect()
+            .await?;
 
+        let mut summary = RetagSummary::default();
+        let mut results = stream::iter(matches.into_iter().filter_map(|post| post.id))
+            .map(|post_id| async move { (post_id, self.retag_post(post_id, add, remove).await) })
+            .buffered(concurrency.max(1));
+        while let Some((post_id, result)) = results.next().await {
+            summary.matched += 1;
+            match result {
+                Ok(true) => summary.updated.push(post_id),
+                Ok(false) => summary.unchanged += 1,
+                Err(e) => summary.failed.push((post_id, e)),
+            }
+            let cursor = BulkCursor {
+                last_post_id: post_id,
+            };
+            summary.cursor = Some(cursor);
+            if let Some(on_progress) = on_progress.as_mut() {
+                on_progress(cursor);
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Applies a tag delta to a single post, returning whether the post had to be changed
+    async fn retag_post(
+        &self,
+        post_id: u32,
+        add: &[&str],
+        remove: &[&str],
+    ) -> SzurubooruResult<bool> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let post = self
+                .client
+                .request()
+                .with_fields(vec!["version".to_string(), "tags".to_string()])
+                .get_post(post_id)
+                .await?;
+            let current = post.tags.unwrap_or_default();
+            let Some(tags) = edited_tag_names(&current, add, remove) else {
+                return Ok(false);
+            };
+
+            let mut update = CreateUpdatePostBuilder::default();
+            update.tags(tags);
+            if let Some(version) = post.version {
+                update.version(version);
+            }
+            let update = update.build()?;
+            match self
+                .client
+                .request()
+                .with_fields(vec!["id".to_string()])
+                .update_post(post_id, &update)
+                .await
+            {
+                Ok(_) => return Ok(true),
+                Err(e) if is_version_conflict(&e) && attempt < MAX_CONFLICT_RETRIES => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Adds `tags` to the post, keeping the tags it already has, and returns the updated post
+    /// with its new version. Tags the post already has, under any of their names, aren't added
+    /// again. If that leaves nothing to change, no update is sent and the post is returned as it
+    /// was fetched.
+    ///
+    /// The post's current tags are fetched first, then the update is sent with the given
+    /// `version`. If the post was edited since that version was fetched, this fails with a
+    /// [VersionConflict](SzurubooruClientError::VersionConflict) rather than overwriting the
+    /// other edit, so fetch the post again and retry. See
+    /// [retag_search](Self::retag_search) for editing tags on many posts.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let post = client.request().get_post(42).await.unwrap();
+    /// let post = client.request()
+    ///     .add_post_tags(42, post.version.unwrap(), &["cat", "tabby"])
+    ///     .await
+    ///     .unwrap();
+    /// println!("Post is now at version {:?}", post.version);
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn add_post_tags(
+        &self,
+        post_id: u32,
+        version: u32,
+        tags: &[&str],
+    ) -> SzurubooruResult<PostResource> {
+        self.edit_post_tags(post_id, version, tags, &[]).await
+    }
+
+    /// Removes `tags` from the post and returns the updated post with its new version. A tag is
+    /// removed if any of its names is listed. Version conflicts are handled as in
+    /// [add_post_tags](Self::add_post_tags)
+    pub async fn remove_post_tags(
+        &self,
+        post_id: u32,
+        version: u32,
+        tags: &[&str],
+    ) -> SzurubooruResult<PostResource> {
+        self.edit_post_tags(post_id, version, &[], tags).await
+    }
+
+    async fn edit_post_tags(
+        &self,
+        post_id: u32,
+        version: u32,
+        add: &[&str],
+        remove: &[&str],
+    ) -> SzurubooruResult<PostResource> {
+        let post = self
+            .with_required_fields(&["tags", "version"])
+            .get_post(post_id)
+            .await?;
+        let current = post.tags.as_deref().unwrap_or_default();
+        let Some(tags) = edited_tag_names(current, add, remove) else {
+            return Ok(post);
+        };
+        let update = CreateUpdatePostBuilder::default()
+            .version(version)
+            .tags(tags)
+            .build()?;
+        self.update_post(post_id, &update).await
+    }
+
+    /// Replaces the posts related to the post, e.g. sequels or alternate versions, with
+    /// `related_ids`, and returns the updated post. Only the relations are sent, so the post's
+    /// tags, notes and other properties are left alone. An empty slice clears the relations.
+    ///
+    /// Szurubooru keeps relations symmetric, so the related posts get this post added to their
+    /// relations as well, and posts that are no longer related have it removed. If
+    /// [fields](Self::with_fields) are selected, `relations` is added to them so it's always
+    /// included in the returned post. Version conflicts are handled as in
+    /// [add_post_tags](Self::add_post_tags).
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let post = client.request().get_post(42).await.unwrap();
+    /// let post = client.request()
+    ///     .set_post_relations(42, post.version.unwrap(), &[40, 41])
+    ///     .await
+    ///     .unwrap();
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn set_post_relations(
+        &self,
+        post_id: u32,
+        version: u32,
+        related_ids: &[u32],
+    ) -> SzurubooruResult<PostResource> {
+        let update = CreateUpdatePostBuilder::default()
+            .version(version)
+            .relations(related_ids.to_vec())
+            .build()?;
+        self.with_required_fields(&["relations"])
+            .update_post(post_id, &update)
+            .await
+    }
+
+    /// Update an existing post from a given URL
+    /// See [SzurubooruRequest::create_post_from_url] for more details about the fields in
+    /// [CreateUpdatePost]
+    pub async fn update_post_from_url(
+        &self,
+        post_id: u32,
+        update_post: &CreateUpdatePost,
+    ) -> SzurubooruResult<PostResource> {
+        assert!(update_post.content_url.is_some());
+        let path = format!("/api/post/{post_id}");
+        self.create_update_post_from_url(&path, Method::PUT, update_post)
+            .await
+            .map(|pr| self.propagate_urls(pr))
+    }
+
+    // Create function to upload by byte array in the future
+
+    /// Streams the rest of `file` as a multipart part, rather than reading it all into memory
     fn part_from_file(&self, file: &mut File) -> SzurubooruResult<Part> {
-        let mut bytes = vec![];
-        file.read_to_end(&mut bytes)
-            .map_err(SzurubooruClientError::IOError)?;
+        let length = remaining_length(file)?;
+        let file = file.try_clone().map_err(SzurubooruClientError::IOError)?;
+
+        Ok(Part::stream_with_length(
+            tokio::fs::File::from_std(file),
+            length,
+        ))
+    }
 
-        Ok(Part::stream(bytes))
+    /// Streams `file` in chunks, adding each chunk's size to `progress.bytes_sent` and reporting
+    /// it to `on_progress` as the chunk is handed to the request body
+    fn part_from_file_with_progress(
+        &self,
+        file: File,
+        progress: UploadProgress,
+        on_progress: UploadProgressCallback,
+    ) -> SzurubooruResult<Part> {
+        let length = file
+            .metadata()
+            .map_err(SzurubooruClientError::IOError)?
+            .len();
+        let file = tokio::fs::File::from_std(file);
+        let chunks = stream::try_unfold((file, progress), move |(mut file, mut progress)| {
+            let on_progress = on_progress.clone();
+            async move {
+                let mut buf = vec![0u8; UPLOAD_CHUNK_SIZE];
+                let read = file.read(&mut buf).await?;
+                if read == 0 {
+                    return Ok::<_, std::io::Error>(None);
+                }
+                buf.truncate(read);
+                progress.bytes_sent += read as u64;
+                on_progress(&progress);
+                Ok(Some((bytes::Bytes::from(buf), (file, progress))))
+            }
+        });
+
+        Ok(Part::stream_with_length(
+            reqwest::Body::wrap_stream(chunks),
+            length,
+        ))
+    }
+
+    /// Names an upload part, setting its MIME type to `mime`, or one guessed from `file_name`
+    fn named_part(
+        &self,
+        part: Part,
+        file_name: String,
+        mime: Option<&str>,
+    ) -> SzurubooruResult<Part> {
+        let mime = mime.or_else(|| mime_for_file_name(&file_name));
+        let part = part.file_name(file_name);
+        match mime {
+            Some(mime) => part
+                .mime_str(mime)
+                .map_err(SzurubooruClientError::RequestBuilderError),
+            None => Ok(part),
+        }
     }
 
     async fn create_update_post_from_file<T>(
@@ -725,6 +2431,38 @@ impl<'a> SzurubooruRequest<'a> {
     where
         T: AsRef<str>,
     {
+        let content = match file {
+            Some(file) => {
+                self.check_upload_size(remaining_length(file)?).await?;
+                let file_name = file_name.as_ref().unwrap().as_ref().to_string();
+                Some(self.named_part(self.part_from_file(file)?, file_name, None)?)
+            }
+            None => None,
+        };
+        let thumbnail = match thumbnail {
+            Some(thumbnail) => {
+                let file_name = format!("thumbnail_{}", file_name.unwrap().as_ref());
+                Some(self.named_part(self.part_from_file(thumbnail)?, file_name, None)?)
+            }
+            None => None,
+        };
+
+        self.create_update_post_multipart(content, thumbnail, path, method, cupost)
+            .await
+    }
+
+    /// Sends `cupost` as the multipart `metadata` alongside the optional content and thumbnail
+    async fn create_update_post_multipart(
+        &self,
+        content: Option<Part>,
+        thumbnail: Option<Part>,
+        path: &str,
+        method: Method,
+        cupost: &CreateUpdatePost,
+    ) -> SzurubooruResult<PostResource> {
+        if method == Method::POST {
+            self.check_required_tags().await?;
+        }
         let request = self.prep_request(method, path, None);
 
         let metadata_str =
@@ -732,19 +2470,11 @@ impl<'a> SzurubooruRequest<'a> {
         let metadata_part = Part::text(metadata_str);
 
         let mut form = Form::new().part("metadata", metadata_part);
-
-        if let Some(file) = file {
-            let content_part = self
-                .part_from_file(file)?
-                .file_name(file_name.as_ref().unwrap().as_ref().to_string());
-            form = form.part("content", content_part);
+        if let Some(content) = content {
+            form = form.part("content", content);
         }
-
         if let Some(thumbnail) = thumbnail {
-            let thumbnail_part = self
-                .part_from_file(thumbnail)?
-                .file_name(format!("thumbnail_{}", file_name.unwrap().as_ref()));
-            form = form.part("thumbnail", thumbnail_part);
+            form = form.part("thumbnail", thumbnail);
         }
 
         self.handle_request(request.multipart(form)).await
@@ -784,10 +2514,10 @@ impl<'a> SzurubooruRequest<'a> {
         thumbnail: Option<impl AsRef<Path>>,
         new_post: &CreateUpdatePost,
     ) -> SzurubooruResult<PostResource> {
-        let mut file = File::open(&file_path).map_err(SzurubooruClientError::IOError)?;
+        let mut file = open_file(&file_path)?;
         let filename = file_path.as_ref().file_name().unwrap().to_str().unwrap();
         let mut thumbnail_file = if let Some(t) = thumbnail {
-            Some(File::open(t).map_err(SzurubooruClientError::IOError)?)
+            Some(open_file(t)?)
         } else {
             None
         };
@@ -796,6 +2526,406 @@ impl<'a> SzurubooruRequest<'a> {
             .map(|pr| self.propagate_urls(pr))
     }
 
+    /// Uploads the file at `path` as a new post with the given tags and safety. This is a
+    /// shortcut for [create_post_from_file_path](Self::create_post_from_file_path) when no other
+    /// post properties are needed.
+    ///
+    /// The file is streamed to the server rather than read into memory, and its MIME type is
+    /// guessed from the extension. Returns a [SzurubooruClientError::FileNotFound] if `path`
+    /// doesn't exist, and a [SzurubooruServerError](SzurubooruClientError::SzurubooruServerError)
+    /// with [PostAlreadyUploadedError](SzurubooruServerErrorType::PostAlreadyUploadedError) if
+    /// the server already has a post with the same content.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # use szurubooru_client::models::PostSafety;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let post = client.request()
+    ///     .create_post_from_path("/tmp/cat.jpg", &["cat", "sleeping"], PostSafety::Safe)
+    ///     .await;
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn create_post_from_path(
+        &self,
+        path: impl AsRef<Path>,
+        tags: &[&str],
+        safety: PostSafety,
+    ) -> SzurubooruResult<PostResource> {
+        let new_post = CreateUpdatePostBuilder::default()
+            .tags(tags.iter().map(|t| t.to_string()).collect())
+            .safety(safety)
+            .build()?;
+        self.create_post_from_file_path(path, None::<&Path>, &new_post)
+            .await
+    }
+
+    /// Like [create_post_from_path](Self::create_post_from_path), but calls `on_progress` as the
+    /// file's content is streamed to the server and once more when the upload has finished.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # use szurubooru_client::models::PostSafety;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let post = client.request()
+    ///     .create_post_from_path_with_progress("/tmp/cat.mp4", &["cat"], PostSafety::Safe, |progress| {
+    ///         println!("{}/{} bytes", progress.bytes_sent, progress.bytes_total);
+    ///     })
+    ///     .await;
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn create_post_from_path_with_progress(
+        &self,
+        path: impl AsRef<Path>,
+        tags: &[&str],
+        safety: PostSafety,
+        on_progress: impl Fn(&UploadProgress) + Send + Sync + 'static,
+    ) -> SzurubooruResult<PostResource> {
+        let mut results = self
+            .create_posts_from_paths(&[path], tags, safety, on_progress)
+            .await;
+        results.remove(0).1
+    }
+
+    /// Uploads each file in `paths` as a new post with the given tags and safety, one at a time.
+    /// A file that fails to upload doesn't stop the rest, so the result for each file is
+    /// returned alongside its path, in the same order as `paths`.
+    ///
+    /// `on_progress` is called as each file's content is streamed to the server, and again
+    /// each time a file finishes uploading, successfully or not. To upload a directory, collect
+    /// its entries first:
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # use szurubooru_client::models::PostSafety;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let paths = std::fs::read_dir("/tmp/cats").unwrap()
+    ///     .map(|entry| entry.unwrap().path())
+    ///     .collect::<Vec<_>>();
+    /// let results = client.request()
+    ///     .create_posts_from_paths(&paths, &["cat"], PostSafety::Safe, |progress| {
+    ///         println!("{}/{} files", progress.items_done, progress.items_total);
+    ///     })
+    ///     .await;
+    /// for (path, result) in results {
+    ///     if let Err(e) = result {
+    ///         eprintln!("Unable to upload {}: {e}", path.display());
+    ///     }
+    /// }
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn create_posts_from_paths<P: AsRef<Path>>(
+        &self,
+        paths: &[P],
+        tags: &[&str],
+        safety: PostSafety,
+        on_progress: impl Fn(&UploadProgress) + Send + Sync + 'static,
+    ) -> Vec<(PathBuf, SzurubooruResult<PostResource>)> {
+        let on_progress: UploadProgressCallback = Arc::new(on_progress);
+        let sizes = paths
+            .iter()
+            .map(|path| std::fs::metadata(path).map(|m| m.len()).unwrap_or(0))
+            .collect::<Vec<_>>();
+        let mut progress = UploadProgress {
+            items_total: paths.len(),
+            bytes_total: sizes.iter().sum(),
+            ..Default::default()
+        };
+
+        let mut results = Vec::with_capacity(paths.len());
+        for (path, size) in paths.iter().zip(sizes) {
+            let path = path.as_ref().to_path_buf();
+            let bytes_before = progress.bytes_sent;
+            progress.current_item = Some(path.clone());
+            let result = self
+                .create_post_from_path_reporting(
+                    &path,
+                    tags,
+                    safety.clone(),
+                    &progress,
+                    &on_progress,
+                )
+                .await;
+            progress.items_done += 1;
+            progress.bytes_sent = bytes_before + size;
+            on_progress(&progress);
+            results.push((path, result));
+        }
+        results
+    }
+
+    async fn create_post_from_path_reporting(
+        &self,
+        path: &Path,
+        tags: &[&str],
+        safety: PostSafety,
+        progress: &UploadProgress,
+        on_progress: &UploadProgressCallback,
+    ) -> SzurubooruResult<PostResource> {
+        let new_post = CreateUpdatePostBuilder::default()
+            .tags(tags.iter().map(|t| t.to_string()).collect())
+            .safety(safety)
+            .build()?;
+        let mut file = open_file(path)?;
+        self.check_upload_size(remaining_length(&mut file)?).await?;
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("upload")
+            .to_string();
+        let content =
+            self.part_from_file_with_progress(file, progress.clone(), on_progress.clone())?;
+        let content = self.named_part(content, file_name, None)?;
+        self.create_update_post_multipart(
+            Some(content),
+            None,
+            "/api/posts",
+            Method::POST,
+            &new_post,
+        )
+        .await
+        .map(|pr| self.propagate_urls(pr))
+    }
+
+    /// Uploads in-memory `bytes` as a new post with the given tags and safety, for content
+    /// that was generated or transformed without ever touching the disk.
+    ///
+    /// `content_type` is sent as the upload's MIME type, and the file name sent alongside it is
+    /// `upload.<ext>` with the extension derived from it, e.g. `upload.png` for `image/png`.
+    /// Returns a [SzurubooruClientError::RequestBuilderError] if `content_type` isn't a valid
+    /// MIME type.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # use szurubooru_client::models::PostSafety;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let png: Vec<u8> = vec![/* ... */];
+    /// let post = client.request()
+    ///     .create_post_from_bytes(png, "image/png", &["generated"], PostSafety::Safe)
+    ///     .await;
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn create_post_from_bytes(
+        &self,
+        bytes: impl Into<bytes::Bytes>,
+        content_type: &str,
+        tags: &[&str],
+        safety: PostSafety,
+    ) -> SzurubooruResult<PostResource> {
+        let new_post = CreateUpdatePostBuilder::default()
+            .tags(tags.iter().map(|t| t.to_string()).collect())
+            .safety(safety)
+            .build()?;
+        let file_name = match extension_for_mime(content_type) {
+            Some(extension) => format!("upload.{extension}"),
+            None => "upload".to_string(),
+        };
+        let bytes: bytes::Bytes = bytes.into();
+        self.check_upload_size(bytes.len() as u64).await?;
+        let content = self.named_part(Part::stream(bytes), file_name, Some(content_type))?;
+
+        self.create_update_post_multipart(
+            Some(content),
+            None,
+            "/api/posts",
+            Method::POST,
+            &new_post,
+        )
+        .await
+        .map(|pr| self.propagate_urls(pr))
+    }
+
+    /// Creates a new post by streaming its content from `reader`, verifying the upload by
+    /// checksum.
+    ///
+    /// The content is wrapped in a [StreamingUpload] and hashed as it's sent, so it's never held
+    /// in memory as a whole. Szurubooru doesn't accept a checksum alongside uploads, so instead
+    /// the computed checksum is compared with the one the server reports for the new post. If
+    /// they differ the content was corrupted in transit and
+    /// [ChecksumMismatch](SzurubooruClientError::ChecksumMismatch) is returned. The post has
+    /// been created by then, so it should be deleted or its content replaced.
+    ///
+    /// The MIME type is guessed from the extension of `file_name`.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # use szurubooru_client::models::{CreateUpdatePostBuilder, PostSafety};
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let file = tokio::fs::File::open("/tmp/large-video.mp4").await.unwrap();
+    /// let new_post = CreateUpdatePostBuilder::default()
+    ///     .tags(vec!["video".to_string()])
+    ///     .safety(PostSafety::Safe)
+    ///     .build()
+    ///     .unwrap();
+    /// let post = client.request()
+    ///     .create_post_from_reader(file, "large-video.mp4", &new_post)
+    ///     .await;
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn create_post_from_reader<R>(
+        &self,
+        reader: R,
+        file_name: impl AsRef<str>,
+        new_post: &CreateUpdatePost,
+    ) -> SzurubooruResult<PostResource>
+    where
+        R: AsyncRead + Unpin + Send + Sync + 'static,
+    {
+        let upload = StreamingUpload::new(reader);
+        let checksum = upload.checksum.clone();
+        let chunks = stream::try_unfold(upload, |mut upload| async move {
+            let mut buf = vec![0u8; UPLOAD_CHUNK_SIZE];
+            let read = upload.read(&mut buf).await?;
+            if read == 0 {
+                return Ok::<_, std::io::Error>(None);
+            }
+            buf.truncate(read);
+            Ok(Some((bytes::Bytes::from(buf), upload)))
+        });
+        let content = self.named_part(
+            Part::stream(reqwest::Body::wrap_stream(chunks)),
+            file_name.as_ref().to_string(),
+            None,
+        )?;
+
+        let post = self
+            .create_update_post_multipart(Some(content), None, "/api/posts", Method::POST, new_post)
+            .await?;
+        if let (Some(expected), Some(actual)) = (checksum.get(), post.checksum.as_ref()) {
+            if !expected.eq_ignore_ascii_case(actual) {
+                return Err(SzurubooruClientError::ChecksumMismatch {
+                    expected: expected.clone(),
+                    actual: actual.clone(),
+                });
+            }
+        }
+        Ok(self.propagate_urls(post))
+    }
+
+    /// Like [create_post_from_file_path](Self::create_post_from_file_path), but retries uploads
+    /// that time out or fail with one of the [RetryPolicy] statuses without creating duplicates.
+    ///
+    /// A failed upload may still have created the post server-side, for instance when a proxy
+    /// gives up waiting on a slow upload. So before each retry the file's SHA1 checksum is
+    /// looked up, and if a post with that content already exists it's returned instead of
+    /// uploading again. Attempts and delays follow the client's
+    /// [with_retry](SzurubooruClient::with_retry) policy, or [RetryPolicy::default] if none is set
+    /// or the client came from [with_reqwest_client](SzurubooruClient::with_reqwest_client)
+    pub async fn create_post_from_file_path_with_retry(
+        &self,
+        file_path: impl AsRef<Path>,
+        thumbnail: Option<impl AsRef<Path>>,
+        new_post: &CreateUpdatePost,
+    ) -> SzurubooruResult<PostResource> {
+        let policy = self.client.retry_policy().cloned().unwrap_or_default();
+        let mut file = open_file(&file_path)?;
+        let checksum = file_checksum(&mut file)?;
+
+        let mut attempt = 1;
+        loop {
+            if attempt > 1 {
+                if let Some(existing) = self.post_for_checksum(&checksum).await? {
+                    return Ok(existing);
+                }
+            }
+            match self
+                .create_post_from_file_path(&file_path, thumbnail.as_ref(), new_post)
+                .await
+            {
+                Err(e) if attempt < policy.max_attempts && policy.is_transient(&e) => {
+                    let delay = policy.backoff(attempt);
+                    tracing::debug!("Upload attempt {attempt} failed ({e}), retrying in {delay:?}");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Like [create_post_from_file_path](Self::create_post_from_file_path), but if the server
+    /// already has a post with the same content, that post is returned instead of an error. This
+    /// makes imports safe to run again. [already_existed](CreatedPost::already_existed) tells the
+    /// two cases apart.
+    ///
+    /// The existing post is looked up by the file's SHA1 checksum when the upload fails with
+    /// [PostAlreadyUploadedError](SzurubooruServerErrorType::PostAlreadyUploadedError), or with a
+    /// [VersionConflict](SzurubooruClientError::VersionConflict) from a concurrent upload of the
+    /// same file. If no post with that checksum can be found, e.g. because it's
+    /// [blocked](SzurubooruClient::with_block_unsafe), the original error is returned.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # use szurubooru_client::models::{CreateUpdatePostBuilder, PostSafety};
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let new_post = CreateUpdatePostBuilder::default()
+    ///     .tags(vec!["cat".to_string()])
+    ///     .safety(PostSafety::Safe)
+    ///     .build()
+    ///     .unwrap();
+    /// let created = client
+    ///     .request()
+    ///     .create_or_get_post_from_file_path("/tmp/cat.jpg", None::<String>, &new_post)
+    ///     .await
+    ///     .unwrap();
+    /// if created.already_existed {
+    ///     println!("Already uploaded as post {:?}", created.post.id);
+    /// }
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn create_or_get_post_from_file_path(
+        &self,
+        file_path: impl AsRef<Path>,
+        thumbnail: Option<impl AsRef<Path>>,
+        new_post: &CreateUpdatePost,
+    ) -> SzurubooruResult<CreatedPost> {
+        let error = match self
+            .create_post_from_file_path(&file_path, thumbnail, new_post)
+            .await
+        {
+            Ok(post) => {
+                return Ok(CreatedPost {
+                    post,
+                    already_existed: false,
+                })
+            }
+            Err(SzurubooruClientError::SzurubooruServerError(e))
+                if e.name == SzurubooruServerErrorType::PostAlreadyUploadedError =>
+            {
+                SzurubooruClientError::SzurubooruServerError(e)
+            }
+            Err(e @ SzurubooruClientError::VersionConflict(_)) => e,
+            Err(e) => return Err(e),
+        };
+
+        let mut file = open_file(&file_path)?;
+        let checksum = file_checksum(&mut file)?;
+        match self.post_for_checksum(&checksum).await? {
+            Some(post) => Ok(CreatedPost {
+                post,
+                already_existed: true,
+            }),
+            None => Err(error),
+        }
+    }
+
     /// Create a post from a token previously generated by
     /// [upload_temporary_file_from_path](SzurubooruRequest::upload_temporary_file_from_path)
     pub async fn create_post_from_token(
@@ -860,7 +2990,7 @@ impl<'a> SzurubooruRequest<'a> {
                     .unwrap()
                     .to_string(),
             );
-            Some(File::open(f).map_err(SzurubooruClientError::IOError)?)
+            Some(open_file(f)?)
         } else {
             None
         };
@@ -876,7 +3006,7 @@ impl<'a> SzurubooruRequest<'a> {
                         .to_string(),
                 );
             }
-            Some(File::open(t).map_err(SzurubooruClientError::IOError)?)
+            Some(open_file(t)?)
         } else {
             None
         };
@@ -925,17 +3055,12 @@ impl<'a> SzurubooruRequest<'a> {
             post_resource.content_url.unwrap()
         };
 
-        let req = self.prep_request(Method::GET, content_path, None);
+        let req = self.prep_content_request(Method::GET, content_path, None);
         let request = req
             .build()
             .map_err(SzurubooruClientError::RequestBuilderError)?;
 
-        let resp_res = self
-            .client
-            .client
-            .execute(request)
-            .await
-            .map_err(SzurubooruClientError::RequestError)?;
+        let resp_res = self.execute(request).await?;
         self.handle_response(resp_res).await
     }
 
@@ -981,15 +3106,17 @@ impl<'a> SzurubooruRequest<'a> {
             .map_err(SzurubooruClientError::RequestError)
     }
 
+    /// Writes `stream` to `file`, returning the number of bytes written
     async fn write_content_to_file<S>(
         &self,
         file: &mut File,
         stream: &mut S,
-    ) -> SzurubooruResult<()>
+    ) -> SzurubooruResult<u64>
     where
         S: futures_util::Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Unpin,
     {
         let mut writer = BufWriter::new(file);
+        let mut written = 0;
 
         while let Some(bytes) = stream
             .try_next()
@@ -999,19 +3126,120 @@ impl<'a> SzurubooruRequest<'a> {
             writer
                 .write_all(bytes.as_ref())
                 .map_err(SzurubooruClientError::IOError)?;
+            written += bytes.len() as u64;
         }
+        writer.flush().map_err(SzurubooruClientError::IOError)?;
 
-        Ok(())
-    }
-
-    ///Downloads a post's image and writes it to the given file handle
-    pub async fn download_image_to_file(
-        &self,
-        post_id: u32,
-        file: &mut File,
-    ) -> SzurubooruResult<()> {
-        let mut stream = self.get_image_bytestream(post_id).await?;
-        self.write_content_to_file(file, &mut stream).await
+        Ok(written)
+    }
+
+    /// Downloads the content of `post`, such as its image or video, to the file at `dest`,
+    /// returning the number of bytes written. Unlike
+    /// [download_image_to_path](Self::download_image_to_path) this uses the post's
+    /// [content_url](PostResource::content_url) as is rather than fetching the post again, so
+    /// `post` must include it if only some [fields](Self::with_fields) were selected.
+    ///
+    /// Relative content URLs are resolved against the client's
+    /// [base URL](SzurubooruClient::base_url). The client's authentication is sent along if the
+    /// content is served from the same origin as the API, but not to other hosts. `dest` is
+    /// created if it doesn't exist and overwritten if it does.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let post = client.request().get_post(42).await.unwrap();
+    /// let written = client.request()
+    ///     .download_post_content(&post, "/tmp/42.jpg")
+    ///     .await
+    ///     .unwrap();
+    /// println!("Saved {written} bytes");
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn download_post_content(
+        &self,
+        post: &PostResource,
+        dest: impl AsRef<Path>,
+    ) -> SzurubooruResult<u64> {
+        let mut stream = self.fetch_post_content(post).await?.bytes_stream();
+        let mut file = File::options()
+            .write(true)
+            .truncate(true)
+            .create(true)
+            .open(dest.as_ref())
+            .map_err(SzurubooruClientError::IOError)?;
+        self.write_content_to_file(&mut file, &mut stream).await
+    }
+
+    /// Like [download_post_content](Self::download_post_content), but returns the content in
+    /// memory instead of writing it to a file
+    pub async fn download_post_content_bytes(
+        &self,
+        post: &PostResource,
+    ) -> SzurubooruResult<bytes::Bytes> {
+        self.fetch_post_content(post)
+            .await?
+            .bytes()
+            .await
+            .map_err(SzurubooruClientError::RequestError)
+    }
+
+    async fn fetch_post_content(&self, post: &PostResource) -> SzurubooruResult<Response> {
+        let content_url = post.content_url.as_deref().ok_or_else(|| {
+            SzurubooruClientError::ValidationError(
+                "Post has no content URL, was the contentUrl field selected?".to_string(),
+            )
+        })?;
+        let url = self.resolve_content_url(content_url)?;
+        let same_origin = url.origin() == self.client.base_url.origin();
+
+        let mut req = self.client.client.get(url);
+        if let Some(timeout) = self.client.request_timeout() {
+            req = req.timeout(timeout);
+        }
+        if same_origin {
+            req = self.with_auth(req);
+        }
+        let request = req
+            .build()
+            .map_err(SzurubooruClientError::RequestBuilderError)?;
+        let response = self.execute_with_retry(request).await?;
+        self.handle_response(response).await
+    }
+
+    /// Resolves a content URL from the server, which is either absolute or relative to the
+    /// base URL
+    fn resolve_content_url(&self, content_url: &str) -> SzurubooruResult<Url> {
+        let url_error = |source| SzurubooruClientError::UrlParseError {
+            source,
+            url: content_url.to_string(),
+        };
+        match Url::parse(content_url) {
+            Ok(url) => Ok(url),
+            Err(url::ParseError::RelativeUrlWithoutBase) => {
+                let mut base = self.client.base_url.clone();
+                if !base.path().ends_with('/') {
+                    base.set_path(&format!("{}/", base.path()));
+                }
+                base.join(content_url.trim_start_matches('/'))
+                    .map_err(url_error)
+            }
+            Err(e) => Err(url_error(e)),
+        }
+    }
+
+    ///Downloads a post's image and writes it to the given file handle
+    pub async fn download_image_to_file(
+        &self,
+        post_id: u32,
+        file: &mut File,
+    ) -> SzurubooruResult<()> {
+        let mut stream = self.get_image_bytestream(post_id).await?;
+        self.write_content_to_file(file, &mut stream)
+            .await
+            .map(|_| ())
     }
 
     ///Downloads a post's image and writes it to the given path
@@ -1027,7 +3255,9 @@ impl<'a> SzurubooruRequest<'a> {
             .create(true)
             .open(path.as_ref())
             .map_err(SzurubooruClientError::IOError)?;
-        self.write_content_to_file(&mut file, &mut stream).await
+        self.write_content_to_file(&mut file, &mut stream)
+            .await
+            .map(|_| ())
     }
 
     ///Downloads a post's thumbnail and writes it to the given file handle
@@ -1037,7 +3267,9 @@ impl<'a> SzurubooruRequest<'a> {
         file: &mut File,
     ) -> SzurubooruResult<()> {
         let mut stream = self.get_thumbnail_bytestream(post_id).await?;
-        self.write_content_to_file(file, &mut stream).await
+        self.write_content_to_file(file, &mut stream)
+            .await
+            .map(|_| ())
     }
 
     ///Downloads a post's thumbnail and writes it to the given path
@@ -1047,8 +3279,15 @@ impl<'a> SzurubooruRequest<'a> {
         path: impl AsRef<Path>,
     ) -> SzurubooruResult<()> {
         let mut stream = self.get_thumbnail_bytestream(post_id).await?;
-        let mut file = File::open(path.as_ref()).map_err(SzurubooruClientError::IOError)?;
-        self.write_content_to_file(&mut file, &mut stream).await
+        let mut file = File::options()
+            .write(true)
+            .truncate(true)
+            .create(true)
+            .open(path.as_ref())
+            .map_err(SzurubooruClientError::IOError)?;
+        self.write_content_to_file(&mut file, &mut stream)
+            .await
+            .map(|_| ())
     }
 
     /// Retrieves posts that look like the input image
@@ -1057,11 +3296,33 @@ impl<'a> SzurubooruRequest<'a> {
         file: &mut File,
         file_path: impl AsRef<str>,
     ) -> SzurubooruResult<ImageSearchResult> {
-        let request = self.prep_request(Method::POST, "/api/posts/reverse-search", None);
+        let image_part = self.named_part(
+            self.part_from_file(file)?,
+            file_path.as_ref().to_string(),
+            None,
+        )?;
+        self.reverse_search_part(image_part).await
+    }
 
-        let image_part = self
-            .part_from_file(file)?
-            .file_name(file_path.as_ref().to_string());
+    /// Retrieves posts that look like in-memory image `bytes` of the given MIME type, e.g. to
+    /// check for duplicates of a generated image before uploading it with
+    /// [create_post_from_bytes](Self::create_post_from_bytes)
+    pub async fn reverse_search_bytes(
+        &self,
+        bytes: impl Into<bytes::Bytes>,
+        content_type: &str,
+    ) -> SzurubooruResult<ImageSearchResult> {
+        let file_name = match extension_for_mime(content_type) {
+            Some(extension) => format!("upload.{extension}"),
+            None => "upload".to_string(),
+        };
+        let bytes: bytes::Bytes = bytes.into();
+        let image_part = self.named_part(Part::stream(bytes), file_name, Some(content_type))?;
+        self.reverse_search_part(image_part).await
+    }
+
+    async fn reverse_search_part(&self, image_part: Part) -> SzurubooruResult<ImageSearchResult> {
+        let request = self.prep_request(Method::POST, "/api/posts/reverse-search", None);
         let form = Form::new().part("content", image_part);
 
         self.handle_request(request.multipart(form))
@@ -1074,26 +3335,21 @@ impl<'a> SzurubooruRequest<'a> {
         &self,
         file_path: impl AsRef<Path>,
     ) -> SzurubooruResult<ImageSearchResult> {
-        let mut file = File::open(&file_path).map_err(SzurubooruClientError::IOError)?;
+        let mut file = open_file(&file_path)?;
         let filename = file_path.as_ref().file_name().unwrap().to_str().unwrap();
         self.reverse_search_file(&mut file, filename)
             .await
             .map(|isr| self.propagate_urls(isr))
     }
 
-    // Need to add a reverse search for bytes
-
     /// Searches for an exact match of a file based on the SHA1 checksum
-    pub async fn post_for_file(
-        &self,
-        mut file: &mut File,
-    ) -> SzurubooruResult<Option<PostResource>> {
-        let mut hasher = Sha1::new();
-        std::io::copy(&mut file, &mut hasher).map_err(SzurubooruClientError::IOError)?;
-        let hash = hasher.finalize();
-        let hex_string = hex::encode(hash);
+    pub async fn post_for_file(&self, file: &mut File) -> SzurubooruResult<Option<PostResource>> {
+        let checksum = file_checksum(file)?;
+        self.post_for_checksum(&checksum).await
+    }
 
-        let qt = QueryToken::token(PostNamedToken::ContentChecksum, hex_string);
+    async fn post_for_checksum(&self, checksum: &str) -> SzurubooruResult<Option<PostResource>> {
+        let qt = QueryToken::token(PostNamedToken::ContentChecksum, checksum);
         let psr = self
             .list_posts(Some(&vec![qt]))
             .await
@@ -1106,12 +3362,16 @@ impl<'a> SzurubooruRequest<'a> {
         &self,
         file_path: impl AsRef<Path>,
     ) -> SzurubooruResult<Option<PostResource>> {
-        let mut file = File::open(file_path).map_err(SzurubooruClientError::IOError)?;
+        let mut file = open_file(file_path)?;
 
         self.post_for_file(&mut file).await
     }
 
     /// Retrieves information about an existing post.
+    ///
+    /// If there's no post with that ID the error is a
+    /// [SzurubooruServerError](SzurubooruClientError::SzurubooruServerError) with
+    /// [PostNotFoundError](SzurubooruServerErrorType::PostNotFoundError) and a `404` status.
     pub async fn get_post(&self, post_id: u32) -> SzurubooruResult<PostResource> {
         let path = format!("/api/post/{post_id}");
         self.do_request(Method::GET, &path, None, None::<&String>)
@@ -1119,6 +3379,102 @@ impl<'a> SzurubooruRequest<'a> {
             .map(|pr| self.propagate_urls(pr))
     }
 
+    /// Retrieves several posts by ID using as few requests as possible. The IDs are batched into
+    /// `id:1,2,3` searches, split up so no request URL gets longer than the client's
+    /// [max URL length](SzurubooruClient::with_max_url_length). The returned map is keyed by
+    /// post ID. IDs that don't match any post are left out of the map, as are unsafe posts if
+    /// the client [blocks them](SzurubooruClient::with_block_unsafe).
+    ///
+    /// If the server rejects the combined search, e.g. because it's an older version or a proxy
+    /// in front of it filters queries, this falls back to fetching the posts individually,
+    /// a few at a time.
+    ///
+    /// If [fields](Self::with_fields) are selected, `id` is always added to them.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let posts = client.request().get_posts_by_ids(&[1, 2, 3]).await.unwrap();
+    /// if let Some(post) = posts.get(&2) {
+    ///     println!("Post 2 has {} tags", post.tag_count.unwrap_or_default());
+    /// }
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn get_posts_by_ids(
+        &self,
+        ids: &[u32],
+    ) -> SzurubooruResult<HashMap<u32, PostResource>> {
+        let mut unique_ids = ids.to_vec();
+        unique_ids.sort_unstable();
+        unique_ids.dedup();
+        let id_values = unique_ids.iter().map(u32::to_string).collect::<Vec<_>>();
+        let id_values = id_values.iter().map(String::as_str).collect::<Vec<_>>();
+
+        let mut posts = HashMap::with_capacity(unique_ids.len());
+        let request = self
+            .with_required_fields(&["id"])
+            .with_offset(0)
+            .with_limit(MAX_POST_BATCH_SIZE as u32);
+        let key = PostNamedToken::Id.as_ref();
+        for chunk in request.chunk_or_query("/api/posts", key, &id_values, MAX_POST_BATCH_SIZE) {
+            let query = vec![QueryToken {
+                key: key.to_string(),
+                value: chunk.join(","),
+            }];
+            let found = request
+                .clone()
+                .with_limit(chunk.len() as u32)
+                .list_posts(Some(&query))
+                .await;
+            match found {
+                Ok(found) => posts.extend(
+                    found
+                        .results
+                        .into_iter()
+                        .filter_map(|post| Some((post.id?, post))),
+                ),
+                Err(e) if posts.is_empty() && is_rejected_search(&e) => {
+                    return self.get_posts_individually(&unique_ids).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(posts)
+    }
+
+    /// The fallback for [get_posts_by_ids](Self::get_posts_by_ids) when the server doesn't
+    /// accept `id:1,2,3` searches
+    async fn get_posts_individually(
+        &self,
+        ids: &[u32],
+    ) -> SzurubooruResult<HashMap<u32, PostResource>> {
+        let request = self.with_required_fields(&["id", "safety"]);
+        let mut results = stream::iter(ids.iter().copied())
+            .map(|post_id| {
+                let request = &request;
+                async move { (post_id, request.get_post(post_id).await) }
+            })
+            .buffer_unordered(FALLBACK_CONCURRENCY);
+
+        let mut posts = HashMap::with_capacity(ids.len());
+        while let Some((post_id, result)) = results.next().await {
+            match result {
+                Ok(post) => {
+                    if !self.is_blocked(&post) {
+                        posts.insert(post_id, post);
+                    }
+                }
+                Err(SzurubooruClientError::SzurubooruServerError(e))
+                    if e.name == SzurubooruServerErrorType::PostNotFoundError => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(posts)
+    }
+
     /// Retrieves information about posts that are before or after an existing post.
     pub async fn get_around_post(&self, post_id: u32) -> SzurubooruResult<AroundPostResult> {
         let path = format!("/api/post/{post_id}/around");
@@ -1126,7 +3482,25 @@ impl<'a> SzurubooruRequest<'a> {
             .await
     }
 
-    /// Deletes existing post. Related posts and tags are kept.
+    /// Permanently deletes existing post, along with its content, comments, scores and favorites.
+    /// This can't be undone. Related posts and tags are kept.
+    ///
+    /// `version` must be the post's current version, otherwise this fails with a
+    /// [VersionConflict](SzurubooruClientError::VersionConflict), so a post that changed since it
+    /// was last looked at isn't deleted by accident. A post that doesn't exist fails with a
+    /// [SzurubooruServerError](SzurubooruClientError::SzurubooruServerError) named
+    /// [PostNotFoundError](SzurubooruServerErrorType::PostNotFoundError).
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let post = client.request().get_post(12).await.unwrap();
+    /// client.request().delete_post(12, post.version.unwrap()).await.unwrap();
+    /// # };
+    /// # ()
+    /// ```
     pub async fn delete_post(&self, post_id: u32, version: u32) -> SzurubooruResult<()> {
         let path = format!("/api/post/{post_id}");
         let version_obj = ResourceVersion { version };
@@ -1148,14 +3522,70 @@ impl<'a> SzurubooruRequest<'a> {
             .map(|pr| self.propagate_urls(pr))
     }
 
-    /// Updates score of authenticated user for given post. Valid scores are -1, 0 and 1.
-    pub async fn rate_post(&self, post_id: u32, score: i8) -> SzurubooruResult<PostResource> {
-        if !(-1..=1).contains(&score) {
-            return Err(SzurubooruClientError::ValidationError(
-                "Score must be -1, 0 or 1".to_string(),
-            ));
-        }
-        let rating_obj = RateResource { score };
+    /// Merges the post `remove_id` into the post `merge_to_id`, like
+    /// [merge_post](Self::merge_post) without building a [MergePost] first. Useful to fold a
+    /// duplicate, e.g. one found with [reverse_search_file_path](Self::reverse_search_file_path),
+    /// into the original while keeping its favorites, scores and comments. With `replace_content`
+    /// the surviving post adopts the removed post's content, otherwise it keeps its own. Returns
+    /// the surviving post.
+    ///
+    /// Both versions must be current, otherwise this fails with a
+    /// [VersionConflict](SzurubooruClientError::VersionConflict) and neither post is changed.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let duplicate = client.request().get_post(12).await.unwrap();
+    /// let original = client.request().get_post(3).await.unwrap();
+    /// let merged = client
+    ///     .request()
+    ///     .merge_posts(12, duplicate.version.unwrap(), 3, original.version.unwrap(), false)
+    ///     .await
+    ///     .unwrap();
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn merge_posts(
+        &self,
+        remove_id: u32,
+        remove_version: u32,
+        merge_to_id: u32,
+        merge_to_version: u32,
+        replace_content: bool,
+    ) -> SzurubooruResult<PostResource> {
+        let merge_opts = MergePostBuilder::default()
+            .remove_post(remove_id)
+            .remove_post_version(remove_version)
+            .merge_to_post(merge_to_id)
+            .merge_to_version(merge_to_version)
+            .replace_post_content(replace_content)
+            .build()?;
+        self.merge_post(&merge_opts).await
+    }
+
+    /// Updates score of authenticated user for given post. [PostRating::Neutral] clears any
+    /// vote the user gave the post before.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # use szurubooru_client::models::PostRating;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let post = client.request().rate_post(42, PostRating::Up).await;
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn rate_post(
+        &self,
+        post_id: u32,
+        rating: PostRating,
+    ) -> SzurubooruResult<PostResource> {
+        let rating_obj = RateResource {
+            score: rating.into(),
+        };
         let path = format!("/api/post/{post_id}/score");
         self.do_request(Method::PUT, &path, None, Some(&rating_obj))
             .await
@@ -1163,15 +3593,21 @@ impl<'a> SzurubooruRequest<'a> {
     }
 
     /// Marks the post as favorite for authenticated user.
+    ///
+    /// Favorites belong to a user, so an anonymous client fails with a
+    /// [ValidationError](SzurubooruClientError::ValidationError) without sending a request.
     pub async fn favorite_post(&self, post_id: u32) -> SzurubooruResult<PostResource> {
+        self.require_auth("Favoriting a post")?;
         let path = format!("/api/post/{post_id}/favorite");
         self.do_request(Method::POST, &path, None, None::<&String>)
             .await
             .map(|pr| self.propagate_urls(pr))
     }
 
-    /// Unmarks the post as favorite for authenticated user.
+    /// Unmarks the post as favorite for authenticated user. Like
+    /// [favorite_post](Self::favorite_post), this needs an authenticated client.
     pub async fn unfavorite_post(&self, post_id: u32) -> SzurubooruResult<PostResource> {
+        self.require_auth("Unfavoriting a post")?;
         let path = format!("/api/post/{post_id}/favorite");
         self.do_request(Method::DELETE, &path, None, None::<&String>)
             .await
@@ -1182,13 +3618,31 @@ impl<'a> SzurubooruRequest<'a> {
     /// featured, the result will be [Option::None]. Note that this method exists mostly for
     /// compatibility with setting featured post - most of the time, you'd want to use query global
     /// info which contains more information.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// match client.request().get_featured_post().await.unwrap() {
+    ///     Some(post) => println!("Post {:?} is featured", post.id),
+    ///     None => println!("No post is featured yet"),
+    /// }
+    /// # };
+    /// # ()
+    /// ```
     pub async fn get_featured_post(&self) -> SzurubooruResult<Option<PostResource>> {
         self.do_request(Method::GET, "/api/featured-post", None, None::<&String>)
             .await
             .map(|r| self.propagate_urls(r))
     }
 
-    /// Features a post on the main page
+    /// Features a post on the main page, replacing the currently featured post. Returns the
+    /// newly featured post. A post that doesn't exist fails with a
+    /// [SzurubooruServerError](SzurubooruClientError::SzurubooruServerError) named
+    /// [PostNotFoundError](SzurubooruServerErrorType::PostNotFoundError), and featuring the post
+    /// that's already featured fails with
+    /// [PostAlreadyFeaturedError](SzurubooruServerErrorType::PostAlreadyFeaturedError).
     pub async fn set_featured_post(&self, post_id: u32) -> SzurubooruResult<PostResource> {
         let id_object = PostId { id: post_id };
         self.do_request(Method::POST, "/api/featured-post", None, Some(&id_object))
@@ -1286,6 +3740,28 @@ impl<'a> SzurubooruRequest<'a> {
             .map(|r| self.propagate_urls(r))
     }
 
+    /// Starts a search for pools configured with chainable methods, as an alternative to
+    /// [list_pools](Self::list_pools). See [ListBuilder]
+    pub fn list_pools_builder(&self) -> ListPoolsBuilder<'a> {
+        ListBuilder::new(self.clone(), |request, query| {
+            Box::pin(async move { request.list_pools(query.as_ref()).await })
+        })
+    }
+
+    /// Streams every result of [list_pools](Self::list_pools), fetching the next page once the
+    /// current one is used up. The page size is this request's [limit](Self::with_limit), or the
+    /// server default if none is set. Errors are returned as stream items and end the stream.
+    pub fn stream_pools(
+        &self,
+        query: Option<&Vec<QueryToken>>,
+    ) -> impl Stream<Item = SzurubooruResult<PoolResource>> + 'a {
+        let query = query.cloned();
+        self.paginate(move |request| {
+            let query = query.clone();
+            async move { request.list_pools(query.as_ref()).await }
+        })
+    }
+
     /// Creates a new pool using specified parameters. Names, suggestions and implications must
     /// match `pool_name_regex` from server's configuration. Category must exist and is the same as
     /// [name](crate::models::PoolCategoryResource::name) field.
@@ -1308,7 +3784,8 @@ impl<'a> SzurubooruRequest<'a> {
     /// will be thrown. The full list of post IDs must be provided if they are being updated, and
     /// the previous list of posts will be replaced with the new one. All fields except
     /// [version](crate::models::CreateUpdatePool::version) are optional - update concerns only provided
-    /// fields.
+    /// fields. The posts are kept in the order they're given in, so reordering a pool means
+    /// sending its full list of posts in the new order.
     pub async fn update_pool(
         &self,
         pool_id: u32,
@@ -1328,8 +3805,42 @@ impl<'a> SzurubooruRequest<'a> {
             .map(|r| self.propagate_urls(r))
     }
 
+    /// Lists the pools the given post belongs to. The post's details already include its
+    /// pools, so this is a single [get_post](Self::get_post) that only selects the `pools`
+    /// field, whatever [fields](Self::with_fields) this request has
+    pub async fn pools_containing_post(&self, post_id: u32) -> SzurubooruResult<Vec<PoolResource>> {
+        let post = self
+            .clone()
+            .with_fields(vec!["pools".to_string()])
+            .get_post(post_id)
+            .await?;
+        Ok(post.pools.unwrap_or_default())
+    }
+
     /// Deletes existing pool. All posts in the pool will only have their relation to the pool
     /// removed.
+    ///
+    /// `version` must be the pool's current version. If the pool was changed since, this fails
+    /// with a [VersionConflict](SzurubooruClientError::VersionConflict), so the pool can be
+    /// fetched again with [get_pool](Self::get_pool) to check the change before deleting it.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # use szurubooru_client::errors::SzurubooruClientError;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let stale_version = 3;
+    /// match client.request().delete_pool(7, stale_version).await {
+    ///     Err(SzurubooruClientError::VersionConflict(_)) => {
+    ///         let pool = client.request().get_pool(7).await.unwrap();
+    ///         println!("Pool changed, now has {} posts", pool.post_count.unwrap_or_default());
+    ///     }
+    ///     result => result.unwrap(),
+    /// }
+    /// # };
+    /// # ()
+    /// ```
     pub async fn delete_pool(&self, pool_id: u32, version: u32) -> SzurubooruResult<()> {
         let path = format!("/api/pool/{pool_id}");
         let version_obj = ResourceVersion { version };
@@ -1346,6 +3857,78 @@ impl<'a> SzurubooruRequest<'a> {
             .map(|r| self.propagate_urls(r))
     }
 
+    /// Merges the pool `remove_id` into the pool `into_id`, like [merge_pools](Self::merge_pools)
+    /// but fetches the current version of both pools first. Returns the surviving pool.
+    pub async fn merge_pools_by_id(
+        &self,
+        remove_id: u32,
+        into_id: u32,
+    ) -> SzurubooruResult<PoolResource> {
+        let version_request = self
+            .client
+            .request()
+            .with_fields(vec!["version".to_string()]);
+        let remove_version = version_request.get_pool(remove_id).await?.version;
+        let into_version = version_request.get_pool(into_id).await?.version;
+        let (Some(remove_version), Some(into_version)) = (remove_version, into_version) else {
+            return Err(SzurubooruClientError::ValidationError(
+                "Server did not return a pool version".to_string(),
+            ));
+        };
+
+        let merge_pool = MergePoolBuilder::default()
+            .remove_pool(remove_id)
+            .remove_pool_version(remove_version)
+            .merge_to_pool(into_id)
+            .merge_to_version(into_version)
+            .build()?;
+        self.merge_pools(&merge_pool).await
+    }
+
+    /// Fetches posts, tags, pools and comments for an overview page such as a landing page, all at
+    /// the same time rather than one after the other. Every section uses this request's
+    /// [fields](Self::with_fields), [limit](Self::with_limit) and [offset](Self::with_offset),
+    /// which apply to the resource types they make sense for.
+    ///
+    /// This doesn't fail as a whole. Each section of the [Dashboard] holds its own result, so a
+    /// section that fails, e.g. because the user lacks the privilege to list comments, still
+    /// leaves the others to be shown.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # use szurubooru_client::models::DashboardQueries;
+    /// # use szurubooru_client::tokens::{QueryToken, TagSortToken};
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let queries = DashboardQueries {
+    ///     tags: Some(vec![QueryToken::sort(TagSortToken::Usages)]),
+    ///     ..Default::default()
+    /// };
+    /// let dashboard = client.request().with_limit(10).dashboard(&queries).await;
+    /// match &dashboard.comments {
+    ///     Ok(comments) => println!("{} comments", comments.total),
+    ///     Err(e) => println!("Comments unavailable: {e}"),
+    /// }
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn dashboard(&self, queries: &DashboardQueries) -> Dashboard {
+        let (posts, tags, pools, comments) = futures_util::future::join4(
+            self.list_posts(queries.posts.as_ref()),
+            self.list_tags(queries.tags.as_ref()),
+            self.list_pools(queries.pools.as_ref()),
+            self.list_comments(queries.comments.as_ref()),
+        )
+        .await;
+        Dashboard {
+            posts,
+            tags,
+            pools,
+            comments,
+        }
+    }
+
     /// Searches for comments.
     /// Anonymous tokens are the same as the [text](crate::tokens::CommentNamedToken::Text) token
     pub async fn list_comments(
@@ -1356,6 +3939,59 @@ impl<'a> SzurubooruRequest<'a> {
             .await
     }
 
+    /// Starts a search for comments configured with chainable methods, as an alternative to
+    /// [list_comments](Self::list_comments). See [ListBuilder]
+    pub fn list_comments_builder(&self) -> ListCommentsBuilder<'a> {
+        ListBuilder::new(self.clone(), |request, query| {
+            Box::pin(async move { request.list_comments(query.as_ref()).await })
+        })
+    }
+
+    /// Streams every result of [list_comments](Self::list_comments), fetching the next page once the
+    /// current one is used up. The page size is this request's [limit](Self::with_limit), or the
+    /// server default if none is set. Errors are returned as stream items and end the stream.
+    pub fn stream_comments(
+        &self,
+        query: Option<&Vec<QueryToken>>,
+    ) -> impl Stream<Item = SzurubooruResult<CommentResource>> + 'a {
+        let query = query.cloned();
+        self.paginate(move |request| {
+            let query = query.clone();
+            async move { request.list_comments(query.as_ref()).await }
+        })
+    }
+
+    /// Returns up to `limit` of the highest scored comments matching `query`. The server can't
+    /// sort comments by score, so every matching comment is fetched and sorted client-side.
+    /// Narrow `query` down, e.g. with a [Post](crate::tokens::CommentNamedToken::Post) token, to
+    /// keep the number of requests down. Comments with the same score keep the order the server
+    /// returned them in. If fields are selected, include `score`, since comments without one
+    /// are sorted last
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # use szurubooru_client::tokens::{CommentNamedToken, QueryToken};
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let on_post = vec![QueryToken::token(CommentNamedToken::Post, "42")];
+    /// let top = client.request()
+    ///     .top_comments(3, Some(&on_post))
+    ///     .await;
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn top_comments(
+        &self,
+        limit: usize,
+        query: Option<&Vec<QueryToken>>,
+    ) -> SzurubooruResult<Vec<CommentResource>> {
+        let mut comments: Vec<CommentResource> = self.stream_comments(query).try_collect().await?;
+        comments.sort_by_key(|comment| std::cmp::Reverse(comment.score.unwrap_or(i32::MIN)));
+        comments.truncate(limit);
+        Ok(comments)
+    }
+
     /// Creates a new comment under given post
     pub async fn create_comment(
         &self,
@@ -1365,7 +4001,33 @@ impl<'a> SzurubooruRequest<'a> {
             .await
     }
 
-    /// Updates an existing comment text
+    /// Updates an existing comment text. `update_comment` must carry the comment's current
+    /// `version`; if someone else changed the comment since, this fails with a
+    /// [VersionConflict](SzurubooruClientError::VersionConflict) and the comment should be
+    /// fetched again before retrying.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # use szurubooru_client::errors::SzurubooruClientError;
+    /// # use szurubooru_client::models::CreateUpdateCommentBuilder;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// loop {
+    ///     let comment = client.request().get_comment(12).await.unwrap();
+    ///     let update = CreateUpdateCommentBuilder::default()
+    ///         .version(comment.version.unwrap())
+    ///         .text("Edited".to_string())
+    ///         .build()
+    ///         .unwrap();
+    ///     match client.request().update_comment(12, &update).await {
+    ///         Err(SzurubooruClientError::VersionConflict(_)) => continue,
+    ///         result => break result,
+    ///     }
+    /// }
+    /// # };
+    /// # ()
+    /// ```
     pub async fn update_comment(
         &self,
         comment_id: u32,
@@ -1383,7 +4045,8 @@ impl<'a> SzurubooruRequest<'a> {
             .await
     }
 
-    /// Deletes existing comment
+    /// Deletes existing comment. Like [update_comment](Self::update_comment), this fails with a
+    /// [VersionConflict](SzurubooruClientError::VersionConflict) if `version` is out of date
     pub async fn delete_comment(&self, comment_id: u32, version: u32) -> SzurubooruResult<()> {
         let path = format!("/api/comment/{comment_id}");
         let version_obj = ResourceVersion { version };
@@ -1421,6 +4084,28 @@ impl<'a> SzurubooruRequest<'a> {
             .map(|r| self.propagate_urls(r))
     }
 
+    /// Starts a search for users configured with chainable methods, as an alternative to
+    /// [list_users](Self::list_users). See [ListBuilder]
+    pub fn list_users_builder(&self) -> ListUsersBuilder<'a> {
+        ListBuilder::new(self.clone(), |request, query| {
+            Box::pin(async move { request.list_users(query.as_ref()).await })
+        })
+    }
+
+    /// Streams every result of [list_users](Self::list_users), fetching the next page once the
+    /// current one is used up. The page size is this request's [limit](Self::with_limit), or the
+    /// server default if none is set. Errors are returned as stream items and end the stream.
+    pub fn stream_users(
+        &self,
+        query: Option<&Vec<QueryToken>>,
+    ) -> impl Stream<Item = SzurubooruResult<UserResource>> + 'a {
+        let query = query.cloned();
+        self.paginate(move |request| {
+            let query = query.clone();
+            async move { request.list_users(query.as_ref()).await }
+        })
+    }
+
     async fn create_update_user(
         &self,
         method: Method,
@@ -1459,7 +4144,34 @@ impl<'a> SzurubooruRequest<'a> {
     /// If the rank is empty and the user happens to be the first user ever created,
     /// become an administrator, whereas subsequent users will be given the rank indicated by
     /// `default_rank` in the server's configuration.
+    ///
+    /// Since no avatar file is sent, asking for a `manual` avatar style fails with a
+    /// [ValidationError](SzurubooruClientError::ValidationError) without contacting the server.
+    /// Use [create_user_with_avatar_path](Self::create_user_with_avatar_path) for that instead.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # use szurubooru_client::models::{CreateUpdateUserBuilder, UserAvatarStyle, UserRank};
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let new_user = CreateUpdateUserBuilder::default()
+    ///     .name("alice".to_string())
+    ///     .password("correct horse".to_string())
+    ///     .rank(UserRank::Regular)
+    ///     .avatar_style(UserAvatarStyle::Gravatar)
+    ///     .build()
+    ///     .unwrap();
+    /// let user = client.request().create_user(&new_user).await.unwrap();
+    /// # };
+    /// # ()
+    /// ```
     pub async fn create_user(&self, new_user: &CreateUpdateUser) -> SzurubooruResult<UserResource> {
+        if new_user.avatar_style == Some(UserAvatarStyle::Manual) {
+            return Err(SzurubooruClientError::ValidationError(
+                "A manual avatar style requires an avatar file".to_string(),
+            ));
+        }
         self.do_request(Method::POST, "/api/users", None, Some(new_user))
             .await
             .map(|r| self.propagate_urls(r))
@@ -1493,7 +4205,7 @@ impl<'a> SzurubooruRequest<'a> {
         avatar_path: impl AsRef<Path>,
         new_user: &CreateUpdateUser,
     ) -> SzurubooruResult<UserResource> {
-        let mut file = File::open(&avatar_path).map_err(SzurubooruClientError::IOError)?;
+        let mut file = open_file(&avatar_path)?;
         let filename = avatar_path.as_ref().file_name().unwrap().to_str().unwrap();
         self.create_update_user(
             Method::POST,
@@ -1513,15 +4225,44 @@ impl<'a> SzurubooruRequest<'a> {
     /// `manual` avatar style requires client to pass also the `avatar` file.
     /// All fields except the [version](crate::models::CreateUpdateUser::version) are optional
     /// - update concerns only provided fields.
-    pub async fn update_user<T>(
-        &self,
-        name: T,
-        update_user: &CreateUpdateUser,
-    ) -> SzurubooruResult<UserResource>
-    where
-        T: AsRef<str> + Display,
-    {
-        let path = format!("/api/user/{name}");
+    ///
+    /// Leaving out the version fails with a
+    /// [ValidationError](SzurubooruClientError::ValidationError) without contacting the server.
+    /// If the user was changed since that version was fetched, this fails with a
+    /// [VersionConflict](SzurubooruClientError::VersionConflict). Setting the `manual` avatar
+    /// style without a file only works if the user already has a manual avatar; use
+    /// [update_user_with_avatar_path](Self::update_user_with_avatar_path) to upload a new one.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # use szurubooru_client::models::{CreateUpdateUserBuilder, UserRank};
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let user = client.request().get_user("alice").await.unwrap();
+    /// let promotion = CreateUpdateUserBuilder::default()
+    ///     .version(user.version.unwrap())
+    ///     .rank(UserRank::Power)
+    ///     .build()
+    ///     .unwrap();
+    /// let user = client.request().update_user("alice", &promotion).await.unwrap();
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn update_user<T>(
+        &self,
+        name: T,
+        update_user: &CreateUpdateUser,
+    ) -> SzurubooruResult<UserResource>
+    where
+        T: AsRef<str> + Display,
+    {
+        if update_user.version.is_none() {
+            return Err(SzurubooruClientError::ValidationError(
+                "Version must be set".to_string(),
+            ));
+        }
+        let path = format!("/api/user/{name}");
         self.do_request(Method::PUT, path, None, Some(update_user))
             .await
             .map(|r| self.propagate_urls(r))
@@ -1565,7 +4306,7 @@ impl<'a> SzurubooruRequest<'a> {
         T: AsRef<str> + Display,
     {
         let path = format!("/api/user/{name}");
-        let mut file = File::open(&avatar_path).map_err(SzurubooruClientError::IOError)?;
+        let mut file = open_file(&avatar_path)?;
         let filename = avatar_path.as_ref().file_name().unwrap().to_str().unwrap();
         self.create_update_user(
             Method::PUT,
@@ -1589,7 +4330,12 @@ impl<'a> SzurubooruRequest<'a> {
             .map(|r| self.propagate_urls(r))
     }
 
-    /// Deletes existing user
+    /// Deletes existing user.
+    ///
+    /// `version` must be the user's current version, otherwise this fails with a
+    /// [VersionConflict](SzurubooruClientError::VersionConflict). A user that doesn't exist fails
+    /// with a [SzurubooruServerError](SzurubooruClientError::SzurubooruServerError) named
+    /// [UserNotFoundError](SzurubooruServerErrorType::UserNotFoundError).
     pub async fn delete_user<T>(&self, name: T, version: u32) -> SzurubooruResult<()>
     where
         T: AsRef<str> + Display,
@@ -1714,6 +4460,14 @@ impl<'a> SzurubooruRequest<'a> {
             .map(|r| self.propagate_urls(r))
     }
 
+    /// Starts a search for snapshots configured with chainable methods, as an alternative to
+    /// [list_snapshots](Self::list_snapshots). See [ListBuilder]
+    pub fn list_snapshots_builder(&self) -> ListSnapshotsBuilder<'a> {
+        ListBuilder::new(self.clone(), |request, query| {
+            Box::pin(async move { request.list_snapshots(query.as_ref()).await })
+        })
+    }
+
     /// Retrieves simple statistics. [featured_post](crate::models::GlobalInfo::featured_post) is
     /// [None] if there is no featured post yet.
     /// [server_time](crate::models::GlobalInfo::server_time) is pretty much the same as the Date HTTP
@@ -1723,6 +4477,30 @@ impl<'a> SzurubooruRequest<'a> {
     pub async fn get_global_info(&self) -> SzurubooruResult<GlobalInfo> {
         self.do_request(Method::GET, "/api/info", None, None::<&String>)
             .await
+            .map(|info| self.propagate_urls(info))
+    }
+
+    /// Retrieves the server's statistics and configuration, the same as
+    /// [get_global_info](Self::get_global_info). Useful for dashboards, and for checking what
+    /// the instance supports before making other calls, e.g. whether
+    /// [safety](crate::models::GlobalInfoConfig::enable_safety) ratings are enabled or which
+    /// rank a [privilege](crate::models::GlobalInfoConfig::privileges) needs.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_anonymous("http://localhost:5001", true).unwrap();
+    /// let info = client.request().get_info().await.unwrap();
+    /// println!("{} posts using {} bytes", info.post_count, info.disk_usage);
+    /// if let Some(post) = info.featured_post {
+    ///     println!("Featured post: {:?}", post.id);
+    /// }
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn get_info(&self) -> SzurubooruResult<ServerInfo> {
+        self.get_global_info().await
     }
 
     /// Puts a file in temporary storage and assigns it a token that can be used in other requests.
@@ -1733,6 +4511,7 @@ impl<'a> SzurubooruRequest<'a> {
         file: &mut File,
         file_name: impl AsRef<str>,
     ) -> SzurubooruResult<TemporaryFileUpload> {
+        self.check_upload_size(remaining_length(file)?).await?;
         let request = self.prep_request(Method::POST, "/api/uploads", None);
 
         let content_part = self
@@ -1752,25 +4531,4452 @@ impl<'a> SzurubooruRequest<'a> {
         &self,
         file_path: impl AsRef<Path>,
     ) -> SzurubooruResult<TemporaryFileUpload> {
-        let mut file = File::open(&file_path).map_err(SzurubooruClientError::IOError)?;
+        let mut file = open_file(&file_path)?;
         let filename = file_path.as_ref().file_name().unwrap().to_str().unwrap();
 
         self.upload_temporary_file(&mut file, filename).await
     }
+
+    /// Calls any API endpoint and deserializes the response into `T`, for endpoints or fields
+    /// this crate doesn't model yet. Authentication, the base URL and this request's
+    /// [fields](Self::with_fields), [limit](Self::with_limit) and [offset](Self::with_offset)
+    /// are applied as usual, and `body` is sent as JSON. `path` should be just the path, e.g.
+    /// `/api/posts`. Error responses are returned as
+    /// [SzurubooruServerError](SzurubooruClientError::SzurubooruServerError)s
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # #[allow(unused)]
+    /// # async {
+    /// use reqwest::Method;
+    /// #[derive(serde::Deserialize)]
+    /// struct Stats {
+    ///     #[serde(rename = "postCount")]
+    ///     post_count: u64,
+    /// }
+    ///
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let stats: Stats = client.request()
+    ///     .raw_json(Method::GET, "/api/info", None)
+    ///     .await
+    ///     .unwrap();
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn raw_json<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Value>,
+    ) -> SzurubooruResult<T> {
+        self.do_request(method, path, None, body.as_ref()).await
+    }
 }
 
 /// Which kind of authentication is used. Automatically hides any sensitive information when printed
 /// using [Debug](std::fmt::Debug)
-enum SzurubooruAuth {
-    // The encoded token
+pub enum SzurubooruAuth {
+    /// The full `Authorization` header value for token authentication. Use
+    /// [token](SzurubooruAuth::token) to build it from a username and token
     TokenAuth(String),
+    /// Username and password for basic authentication
     BasicAuth(String, String),
-    #[allow(dead_code)]
+    /// Anonymous access
     None,
 }
 
+impl SzurubooruAuth {
+    /// Token authentication as `username`. See the
+    /// [API docs](https://github.com/rr-/szurubooru/blob/master/doc/API.md#authentication)
+    pub fn token(username: &str, token: &str) -> Self {
+        let encoded_auth = STANDARD.encode(format!("{username}:{token}").as_bytes());
+        SzurubooruAuth::TokenAuth(format!("Token {encoded_auth}"))
+    }
+
+    /// Basic authentication as `username`. Token authentication should be preferred
+    pub fn basic(username: &str, password: &str) -> Self {
+        SzurubooruAuth::BasicAuth(username.to_string(), password.to_string())
+    }
+}
+
 impl std::fmt::Debug for SzurubooruAuth {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "SzurubooruAuth ()")
     }
 }
+
+/// Fetches one page of a search with the request and query a [ListBuilder] was configured with
+type ListFetch<'a, T> = fn(
+    SzurubooruRequest<'a>,
+    Option<Vec<QueryToken>>,
+) -> BoxFuture<'a, SzurubooruResult<PagedSearchResult<T>>>;
+
+/// A search configured with chainable methods, so options can be added without changing the
+/// signature of the `list_*` methods. Start one with a `list_*_builder` method such as
+/// [list_posts_builder](SzurubooruRequest::list_posts_builder), then either
+/// [send](Self::send) it or `.await` it directly.
+///
+/// ```no_run
+/// # use szurubooru_client::SzurubooruClient;
+/// # #[allow(unused)]
+/// # async {
+/// use szurubooru_client::tokens::QueryToken;
+/// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+/// let page = client.request()
+///     .list_posts_builder()
+///     .tokens([QueryToken::anonymous("cat")])
+///     .offset(20)
+///     .limit(10)
+///     .fields(&["id", "tags"])
+///     .await;
+/// # };
+/// # ()
+/// ```
+pub struct ListBuilder<'a, T> {
+    request: SzurubooruRequest<'a>,
+    tokens: Vec<QueryToken>,
+    fetch: ListFetch<'a, T>,
+}
+
+/// A [ListBuilder] for [list_posts](SzurubooruRequest::list_posts)
+pub type ListPostsBuilder<'a> = ListBuilder<'a, PostResource>;
+/// A [ListBuilder] for [list_tags](SzurubooruRequest::list_tags)
+pub type ListTagsBuilder<'a> = ListBuilder<'a, TagResource>;
+/// A [ListBuilder] for [list_pools](SzurubooruRequest::list_pools)
+pub type ListPoolsBuilder<'a> = ListBuilder<'a, PoolResource>;
+/// A [ListBuilder] for [list_comments](SzurubooruRequest::list_comments)
+pub type ListCommentsBuilder<'a> = ListBuilder<'a, CommentResource>;
+/// A [ListBuilder] for [list_users](SzurubooruRequest::list_users)
+pub type ListUsersBuilder<'a> = ListBuilder<'a, UserResource>;
+/// A [ListBuilder] for [list_snapshots](SzurubooruRequest::list_snapshots)
+pub type ListSnapshotsBuilder<'a> = ListBuilder<'a, SnapshotResource>;
+
+impl<'a, T> ListBuilder<'a, T> {
+    fn new(request: SzurubooruRequest<'a>, fetch: ListFetch<'a, T>) -> Self {
+        Self {
+            request,
+            tokens: Vec::new(),
+            fetch,
+        }
+    }
+
+    /// Adds `tokens` to the search query, after any added before
+    pub fn tokens(mut self, tokens: impl IntoIterator<Item = QueryToken>) -> Self {
+        self.tokens.extend(tokens);
+        self
+    }
+
+    /// Adds a single token to the search query
+    pub fn token(mut self, token: QueryToken) -> Self {
+        self.tokens.push(token);
+        self
+    }
+
+    /// Skips the first `offset` results. See [with_offset](SzurubooruRequest::with_offset)
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.request = self.request.with_offset(offset);
+        self
+    }
+
+    /// Returns at most `limit` results. See [with_limit](SzurubooruRequest::with_limit)
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.request = self.request.with_limit(limit);
+        self
+    }
+
+    /// Selects which fields of each result to return. See
+    /// [with_fields](SzurubooruRequest::with_fields)
+    pub fn fields(mut self, fields: &[&str]) -> Self {
+        let fields = fields.iter().map(|f| f.to_string()).collect();
+        self.request = self.request.with_fields(fields);
+        self
+    }
+
+    /// Runs the search, returning one page of results
+    pub async fn send(self) -> SzurubooruResult<PagedSearchResult<T>> {
+        let query = (!self.tokens.is_empty()).then_some(self.tokens);
+        (self.fetch)(self.request, query).await
+    }
+}
+
+impl<'a, T: Send + 'a> IntoFuture for ListBuilder<'a, T> {
+    type Output = SzurubooruResult<PagedSearchResult<T>>;
+    type IntoFuture = BoxFuture<'a, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.send())
+    }
+}
+
+/// A registry of named post searches, for applications that let users save their searches.
+///
+/// This lives entirely on the client side; Szurubooru has no notion of saved searches. The
+/// registry only maps names to [QueryToken]s, so it can be stored and restored however the
+/// application stores the rest of its settings.
+///
+/// ```no_run
+/// # use szurubooru_client::SzurubooruClient;
+/// use szurubooru_client::client::SavedSearches;
+/// use szurubooru_client::tokens::{PostSortToken, QueryToken};
+/// # #[allow(unused)]
+/// # async {
+/// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+/// let mut searches = SavedSearches::default();
+/// searches.register("best cats", vec![
+///     QueryToken::anonymous("cat"),
+///     QueryToken::sort(PostSortToken::Score),
+/// ]);
+/// let posts = searches.run(&client.request().with_limit(10), "best cats").await;
+/// # };
+/// # ()
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SavedSearches {
+    searches: HashMap<String, Vec<QueryToken>>,
+}
+
+impl SavedSearches {
+    /// Saves `query` under `name`, returning the query previously saved under that name, if any
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        query: Vec<QueryToken>,
+    ) -> Option<Vec<QueryToken>> {
+        self.searches.insert(name.into(), query)
+    }
+
+    /// The query saved under `name`
+    pub fn get(&self, name: &str) -> Option<&Vec<QueryToken>> {
+        self.searches.get(name)
+    }
+
+    /// Removes the query saved under `name`, returning it if there was one
+    pub fn remove(&mut self, name: &str) -> Option<Vec<QueryToken>> {
+        self.searches.remove(name)
+    }
+
+    /// The names of all saved searches, in no particular order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.searches.keys().map(String::as_str)
+    }
+
+    /// Lists the posts matching the search saved under `name` with
+    /// [list_posts](SzurubooruRequest::list_posts), so any paging or field selection set on
+    /// `request` applies. Returns a [SzurubooruClientError::ValidationError] if nothing is saved
+    /// under `name`
+    pub async fn run(
+        &self,
+        request: &SzurubooruRequest<'_>,
+        name: &str,
+    ) -> SzurubooruResult<PagedSearchResult<PostResource>> {
+        let query = self.get(name).ok_or_else(|| {
+            SzurubooruClientError::ValidationError(format!("No saved search named {name}"))
+        })?;
+        request.list_posts(Some(query)).await
+    }
+}
+
+/// The most tags [get_tags](SzurubooruRequest::get_tags) asks for at once. Szurubooru caps the
+/// page size at 100
+const MAX_TAG_BATCH_SIZE: usize = 100;
+
+/// The most posts [get_posts_by_ids](SzurubooruRequest::get_posts_by_ids) asks for at once
+const MAX_POST_BATCH_SIZE: usize = 100;
+
+/// The most posts with the same checksum [find_duplicates](SzurubooruRequest::find_duplicates)
+/// looks up for each post
+const MAX_DUPLICATE_LOOKUP_SIZE: u32 = 100;
+
+/// How many posts [get_posts_by_ids](SzurubooruRequest::get_posts_by_ids) fetches at once when
+/// the server doesn't support searching for several IDs
+const FALLBACK_CONCURRENCY: usize = 4;
+
+/// Whether `error` means the server didn't understand a search, rather than the request failing
+/// for some other reason
+fn is_rejected_search(error: &SzurubooruClientError) -> bool {
+    match error {
+        SzurubooruClientError::SzurubooruServerError(e) => matches!(
+            e.name,
+            SzurubooruServerErrorType::SearchError
+                | SzurubooruServerErrorType::InvalidParameterError
+        ),
+        SzurubooruClientError::ResponseError(status, _) => *status == StatusCode::BAD_REQUEST,
+        _ => false,
+    }
+}
+
+/// How long the `,` separating OR values is once URL-encoded
+const ENCODED_COMMA_LENGTH: usize = 3;
+
+/// Escapes the values for use in an OR search and groups them so that each group, once joined
+/// with `,` and URL-encoded, is at most `max_length` long and has at most `max_count` values.
+/// A value that's too long on its own still gets a group of its own.
+fn chunk_or_values(values: &[&str], max_length: usize, max_count: usize) -> Vec<Vec<String>> {
+    let mut chunks: Vec<Vec<String>> = Vec::new();
+    let mut chunk_length = 0;
+    for value in values {
+        let escaped = QueryToken::token_literal("", value).value;
+        let encoded_length: usize = url::form_urlencoded::byte_serialize(escaped.as_bytes())
+            .map(str::len)
+            .sum();
+        let fits = chunks.last().is_some_and(|chunk| {
+            chunk.len() < max_count
+                && chunk_length + ENCODED_COMMA_LENGTH + encoded_length <= max_length
+        });
+        if fits {
+            chunk_length += ENCODED_COMMA_LENGTH + encoded_length;
+            chunks.last_mut().unwrap().push(escaped);
+        } else {
+            chunk_length = encoded_length;
+            chunks.push(vec![escaped]);
+        }
+    }
+    chunks
+}
+
+/// How many times helpers that refetch a resource retry after a version conflict
+const MAX_CONFLICT_RETRIES: u32 = 3;
+
+/// Parses a response like [SzuruEither] does, but fails if the response has any field that `T`
+/// doesn't know about. [SzuruEither] can't be used directly since untagged enums buffer the
+/// whole response and silently drop whatever the chosen variant ignores
+fn parse_strict<T: DeserializeOwned>(response_text: String) -> SzurubooruResult<T> {
+    let parse_error = |e, text: String| SzurubooruClientError::ResponseParsingError(e, text);
+    let value: Value =
+        serde_json::from_str(&response_text).map_err(|e| parse_error(e, response_text.clone()))?;
+
+    let mut unknown_fields = Vec::new();
+    match serde_ignored::deserialize(value.clone(), |path| unknown_fields.push(path.to_string())) {
+        Ok(parsed) if unknown_fields.is_empty() => Ok(parsed),
+        Ok(_) => Err(parse_error(
+            serde::de::Error::custom(format!("unknown fields {}", unknown_fields.join(", "))),
+            response_text,
+        )),
+        Err(e) => match serde_json::from_value::<SzurubooruServerError>(value) {
+            Ok(server_error) => Err(SzurubooruClientError::SzurubooruServerError(server_error)),
+            Err(_) => Err(parse_error(e, response_text)),
+        },
+    }
+}
+
+/// How much of a file [SzurubooruRequest::create_posts_from_paths] reads at a time
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+type UploadProgressCallback = Arc<dyn Fn(&UploadProgress) + Send + Sync>;
+
+/// Wraps a reader, computing the SHA1 checksum of everything read through it.
+///
+/// Once the wrapped reader is exhausted, [checksum](Self::checksum) returns the hex-encoded
+/// checksum of its content, in the same format as [PostResource::checksum]. This is what
+/// [create_post_from_reader](SzurubooruRequest::create_post_from_reader) uses to verify uploads
+/// without reading them into memory first, but it can also be used on its own to look up
+/// existing posts with the `content-checksum` token.
+///
+/// ```rust
+/// # use szurubooru_client::client::StreamingUpload;
+/// use tokio::io::AsyncReadExt;
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let mut upload = StreamingUpload::new(&b"hello world"[..]);
+/// let mut content = Vec::new();
+/// upload.read_to_end(&mut content).await.unwrap();
+/// assert_eq!(upload.bytes_read(), 11);
+/// assert_eq!(
+///     upload.checksum().as_deref(),
+///     Some("2aae6c35c94fcfb415dbe95f408b9ce91ee846ed")
+/// );
+/// # }
+/// ```
+pub struct StreamingUpload<R> {
+    reader: R,
+    hasher: Sha1,
+    bytes_read: u64,
+    checksum: Arc<OnceLock<String>>,
+}
+
+impl<R> StreamingUpload<R> {
+    /// Wraps `reader`, hashing its content as it's read
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            hasher: Sha1::new(),
+            bytes_read: 0,
+            checksum: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// The hex-encoded SHA1 checksum of the content, or `None` if the wrapped reader hasn't
+    /// been read to the end yet
+    pub fn checksum(&self) -> Option<String> {
+        self.checksum.get().cloned()
+    }
+
+    /// How many bytes have been read so far
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for StreamingUpload<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = &mut *self;
+        let before = buf.filled().len();
+        let requested = buf.remaining();
+        ready!(Pin::new(&mut this.reader).poll_read(cx, buf))?;
+
+        let read = &buf.filled()[before..];
+        if read.is_empty() && requested > 0 {
+            let hasher = this.hasher.clone();
+            this.checksum.get_or_init(|| hex::encode(hasher.finalize()));
+        } else {
+            this.hasher.update(read);
+            this.bytes_read += read.len() as u64;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Opens `path` for reading, reporting a missing file as [SzurubooruClientError::FileNotFound]
+fn open_file(path: impl AsRef<Path>) -> SzurubooruResult<File> {
+    File::open(&path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => {
+            SzurubooruClientError::FileNotFound(path.as_ref().to_path_buf())
+        }
+        _ => SzurubooruClientError::IOError(e),
+    })
+}
+
+/// File extensions and MIME types of the file types Szurubooru accepts. The first extension
+/// listed for a MIME type is the one used when naming uploads
+const UPLOAD_TYPES: &[(&str, &str)] = &[
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("png", "image/png"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("bmp", "image/bmp"),
+    ("avif", "image/avif"),
+    ("heif", "image/heif"),
+    ("heic", "image/heic"),
+    ("swf", "application/x-shockwave-flash"),
+    ("mp4", "video/mp4"),
+    ("webm", "video/webm"),
+    ("mov", "video/quicktime"),
+];
+
+/// Guesses the MIME type of an upload from its extension
+fn mime_for_file_name(file_name: &str) -> Option<&'static str> {
+    let extension = Path::new(file_name).extension()?.to_str()?;
+    UPLOAD_TYPES
+        .iter()
+        .find(|(ext, _)| ext.eq_ignore_ascii_case(extension))
+        .map(|(_, mime)| *mime)
+}
+
+/// The file extension to name an upload of the given MIME type with
+fn extension_for_mime(mime: &str) -> Option<&'static str> {
+    let essence = mime.split(';').next()?.trim();
+    UPLOAD_TYPES
+        .iter()
+        .find(|(_, m)| m.eq_ignore_ascii_case(essence))
+        .map(|(ext, _)| *ext)
+}
+
+/// The number of bytes left to read in `file` from its current position
+fn remaining_length(file: &mut File) -> SzurubooruResult<u64> {
+    let position = file
+        .stream_position()
+        .map_err(SzurubooruClientError::IOError)?;
+    let length = file
+        .metadata()
+        .map_err(SzurubooruClientError::IOError)?
+        .len();
+    Ok(length.saturating_sub(position))
+}
+
+/// The hex-encoded SHA1 checksum of the rest of `file`, as used by the `content-checksum` token
+fn file_checksum(mut file: &mut File) -> SzurubooruResult<String> {
+    let mut hasher = Sha1::new();
+    std::io::copy(&mut file, &mut hasher).map_err(SzurubooruClientError::IOError)?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// The path of the tag endpoint `endpoint` for the tag `name`. Tag names can contain any
+/// non-whitespace character, so the name is percent-encoded to keep characters like `/` and `?`
+/// from changing the URL's meaning
+fn tag_path(endpoint: &str, name: &str) -> String {
+    format!("/api/{endpoint}/{}", urlencoding::encode(name))
+}
+
+/// The names to set on a post with the `current` tags to add `add` and remove `remove`, or
+/// `None` if that wouldn't change anything. Each remaining tag is kept under its first name, and
+/// names that match any name of an existing tag are neither added nor removed twice
+fn edited_tag_names(
+    current: &[MicroTagResource],
+    add: &[&str],
+    remove: &[&str],
+) -> Option<Vec<String>> {
+    let mut tags: Vec<String> = current
+        .iter()
+        .filter(|tag| !tag.names.iter().any(|name| remove.contains(&name.as_str())))
+        .filter_map(|tag| tag.names.first().cloned())
+        .collect();
+    let removed_any = tags.len() != current.len();
+    let missing: Vec<String> = add
+        .iter()
+        .filter(|name| {
+            !current
+                .iter()
+                .any(|tag| tag.names.iter().any(|tn| tn == *name))
+        })
+        .map(|name| name.to_string())
+        .collect();
+    if !removed_any && missing.is_empty() {
+        return None;
+    }
+    tags.extend(missing);
+    Some(tags)
+}
+
+/// Rewrites a post query so it can't match unsafe posts, for
+/// [with_block_unsafe](SzurubooruClient::with_block_unsafe). Returns [None] if a safety token
+/// only allows unsafe posts, as nothing can match the search then
+fn without_unsafe(query: Option<&Vec<QueryToken>>) -> Option<Vec<QueryToken>> {
+    let unsafe_value = PostSafety::Unsafe.as_ref();
+    let is_safety = |key: &str| {
+        key == PostNamedToken::Safety.as_ref() || key == PostNamedToken::Rating.as_ref()
+    };
+    let mut query: Vec<QueryToken> = query
+        .into_iter()
+        .flatten()
+        .map(|qt| {
+            if !is_safety(&qt.key) {
+                return Some(qt.clone());
+            }
+            let values = qt
+                .value
+                .split(',')
+                .filter(|v| !v.eq_ignore_ascii_case(unsafe_value))
+                .collect::<Vec<_>>();
+            (!values.is_empty()).then(|| QueryToken {
+                key: qt.key.clone(),
+                value: values.join(","),
+            })
+        })
+        .collect::<Option<_>>()?;
+    query.push(QueryToken::token(PostNamedToken::Safety, unsafe_value).negate());
+    Some(query)
+}
+
+/// Whether the server rejected an update because the resource version was outdated
+fn is_version_conflict(e: &SzurubooruClientError) -> bool {
+    matches!(e, SzurubooruClientError::VersionConflict(_))
+}
+
+struct CursorState<'a> {
+    request: SzurubooruRequest<'a>,
+    query: Vec<QueryToken>,
+    cursor: Option<u32>,
+    page: VecDeque<PostResource>,
+    done: bool,
+}
+
+struct PageState<'a, T, F> {
+    request: SzurubooruRequest<'a>,
+    fetch: F,
+    offset: u32,
+    page: VecDeque<T>,
+    done: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server, ServerGuard};
+
+    fn client_for(server: &ServerGuard) -> SzurubooruClient {
+        SzurubooruClient::new_with_token(&server.url(), "user", "token", false)
+            .expect("Unable to create client")
+    }
+
+    /// The body Szurubooru responds with when a request's version is out of date
+    const VERSION_CONFLICT_BODY: &str = r#"{"name": "IntegrityError", "title": "Integrity violation",
+        "description": "Someone else modified this in the meantime. Please try again."}"#;
+
+    fn assert_version_conflict<T: std::fmt::Debug>(result: SzurubooruResult<T>) {
+        match result {
+            Err(e @ SzurubooruClientError::VersionConflict(_)) => {
+                assert_eq!(e.status(), Some(StatusCode::CONFLICT));
+            }
+            other => panic!("Expected a version conflict, got {other:?}"),
+        }
+    }
+
+    fn temp_file_with(name: &str, contents: &[u8]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("szurubooru-{}-{name}", std::process::id()));
+        std::fs::write(&path, contents).expect("Unable to write temporary file");
+        path
+    }
+
+    #[tokio::test]
+    async fn test_create_post_multipart_body() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/posts")
+            .match_header(
+                "content-type",
+                Matcher::Regex("^multipart/form-data; boundary=".to_string()),
+            )
+            .match_body(Matcher::AllOf(vec![
+                Matcher::Regex(
+                    r#"name="metadata"\r\n\r\n\{"tags":\["cat"\],"safety":"safe"\}"#.to_string(),
+                ),
+                Matcher::Regex(r#"name="content"; filename="cat.jpg""#.to_string()),
+                Matcher::Regex("not really a jpeg".to_string()),
+            ]))
+            .with_body(r#"{"id": 1, "version": 1}"#)
+            .create_async()
+            .await;
+
+        let path = temp_file_with("cat.jpg", b"not really a jpeg");
+        let mut file = File::open(&path).unwrap();
+        let new_post = CreateUpdatePostBuilder::default()
+            .tags(vec!["cat".to_string()])
+            .safety(PostSafety::Safe)
+            .build()
+            .unwrap();
+        let client = client_for(&server);
+        let post = client
+            .request()
+            .create_post_from_file(&mut file, None, "cat.jpg", &new_post)
+            .await
+            .expect("Unable to create post");
+        std::fs::remove_file(path).unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(post.id, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_create_or_get_post() {
+        let mut server = Server::new_async().await;
+        let created = server
+            .mock("POST", "/api/posts")
+            .with_body(r#"{"id": 4, "version": 1, "checksum": "d952426f1f6730f9370e6d476e03ddac5f7e7469"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let path = temp_file_with("imported.jpg", b"imported twice");
+        let new_post = CreateUpdatePostBuilder::default()
+            .tags(vec!["cat".to_string()])
+            .safety(PostSafety::Safe)
+            .build()
+            .unwrap();
+        let client = client_for(&server);
+        let first = client
+            .request()
+            .create_or_get_post_from_file_path(&path, None::<&Path>, &new_post)
+            .await
+            .expect("Unable to create post");
+        created.assert_async().await;
+        assert_eq!(first.post.id, Some(4));
+        assert!(!first.already_existed);
+        created.remove_async().await;
+
+        let duplicate = server
+            .mock("POST", "/api/posts")
+            .with_status(409)
+            .with_body(
+                r#"{"name": "PostAlreadyUploadedError", "title": "Post already uploaded",
+                    "description": "Post already uploaded (4)"}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let lookup = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::UrlEncoded(
+                "query".into(),
+                // SHA1 of "imported twice"
+                "content-checksum:d952426f1f6730f9370e6d476e03ddac5f7e7469".into(),
+            ))
+            .with_body(
+                r#"{"query": "", "offset": 0, "limit": 100, "total": 1, "results": [
+                    {"id": 4, "version": 1, "checksum": "d952426f1f6730f9370e6d476e03ddac5f7e7469"}
+                ]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let second = client
+            .request()
+            .create_or_get_post_from_file_path(&path, None::<&Path>, &new_post)
+            .await
+            .expect("Duplicate upload should return the existing post");
+        duplicate.assert_async().await;
+        lookup.assert_async().await;
+        assert_eq!(second.post, first.post);
+        assert!(second.already_existed);
+        lookup.remove_async().await;
+
+        // Without a post to fall back on, the duplicate error is returned as-is
+        server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::Any)
+            .with_body(r#"{"query": "", "offset": 0, "limit": 100, "total": 0, "results": []}"#)
+            .create_async()
+            .await;
+        let result = client
+            .request()
+            .create_or_get_post_from_file_path(&path, None::<&Path>, &new_post)
+            .await;
+        std::fs::remove_file(&path).unwrap();
+        match result {
+            Err(SzurubooruClientError::SzurubooruServerError(e)) => {
+                assert_eq!(e.name, SzurubooruServerErrorType::PostAlreadyUploadedError)
+            }
+            other => panic!("Expected a duplicate post error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_post_retry_finds_existing_post() {
+        let mut server = Server::new_async().await;
+        // The proxy gives up on the upload, but the server still creates the post
+        let upload = server
+            .mock("POST", "/api/posts")
+            .with_status(504)
+            .with_body("Gateway Timeout")
+            .expect(1)
+            .create_async()
+            .await;
+        let lookup = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::UrlEncoded(
+                "query".into(),
+                // SHA1 of "retried upload"
+                "content-checksum:c163ff5de23d625780f30755732d554251d79e5c".into(),
+            ))
+            .with_body(
+                r#"{"query": "", "offset": 0, "limit": 100, "total": 1,
+                    "results": [{"id": 12, "version": 1}]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let path = temp_file_with("retried.jpg", b"retried upload");
+        let new_post = CreateUpdatePostBuilder::default()
+            .safety(PostSafety::Safe)
+            .build()
+            .unwrap();
+        let client = client_for(&server).with_retry(RetryPolicy {
+            base_delay: Duration::from_millis(1),
+            ..Default::default()
+        });
+        let post = client
+            .request()
+            .create_post_from_file_path_with_retry(&path, None::<&Path>, &new_post)
+            .await
+            .expect("Retry should find the existing post");
+        std::fs::remove_file(path).unwrap();
+
+        upload.assert_async().await;
+        lookup.assert_async().await;
+        assert_eq!(post.id, Some(12));
+    }
+
+    #[tokio::test]
+    async fn test_create_post_from_path() {
+        let mut server = Server::new_async().await;
+        let created = server
+            .mock("POST", "/api/posts")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::Regex(
+                    r#"name="metadata"\r\n\r\n\{"tags":\["cat","sleeping"\],"safety":"sketchy"\}"#
+                        .to_string(),
+                ),
+                Matcher::Regex(
+                    r#"name="content"; filename="[^"]*upload.png"\r\nContent-Type: image/png"#
+                        .to_string(),
+                ),
+                Matcher::Regex("png bytes".to_string()),
+            ]))
+            .with_body(r#"{"id": 5, "version": 1}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let path = temp_file_with("upload.png", b"png bytes");
+        let client = client_for(&server);
+        let post = client
+            .request()
+            .create_post_from_path(&path, &["cat", "sleeping"], PostSafety::Sketchy)
+            .await
+            .expect("Unable to create post");
+        created.assert_async().await;
+        assert_eq!(post.id, Some(5));
+        created.remove_async().await;
+
+        let duplicate = server
+            .mock("POST", "/api/posts")
+            .with_status(409)
+            .with_body(
+                r#"{"name": "PostAlreadyUploadedError", "title": "Post already uploaded",
+                    "description": "Post already uploaded (4)"}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let result = client
+            .request()
+            .create_post_from_path(&path, &["cat"], PostSafety::Safe)
+            .await;
+        std::fs::remove_file(&path).unwrap();
+        duplicate.assert_async().await;
+        assert_eq!(
+            result.as_ref().err().and_then(|e| e.status()),
+            Some(StatusCode::CONFLICT)
+        );
+        match result {
+            Err(SzurubooruClientError::SzurubooruServerError(e)) => {
+                assert_eq!(e.name, SzurubooruServerErrorType::PostAlreadyUploadedError);
+                assert_eq!(e.status, Some(StatusCode::CONFLICT));
+            }
+            other => panic!("Expected a duplicate post error, got {other:?}"),
+        }
+
+        let result = client
+            .request()
+            .create_post_from_path(&path, &["cat"], PostSafety::Safe)
+            .await;
+        match result {
+            Err(SzurubooruClientError::FileNotFound(missing)) => assert_eq!(missing, path),
+            other => panic!("Expected a missing file error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_find_duplicates() {
+        let mut server = Server::new_async().await;
+        let listing = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::UrlEncoded("query".into(), "sort:id".into()))
+            .with_body(
+                r#"{"query": "", "offset": 0, "limit": 100, "total": 3, "results": [
+                    {"id": 3, "checksum": "aaa", "type": "image"},
+                    {"id": 2, "checksum": "bbb", "type": "image"},
+                    {"id": 1, "checksum": "aaa", "type": "image"}]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let copies = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::UrlEncoded(
+                "query".into(),
+                "content-checksum:aaa".into(),
+            ))
+            .with_body(
+                r#"{"query": "", "offset": 0, "limit": 100, "total": 2, "results": [
+                    {"id": 3, "checksum": "aaa"}, {"id": 1, "checksum": "aaa"}]}"#,
+            )
+            // Only checked for post 3, as post 1 is already in its cluster by then
+            .expect(1)
+            .create_async()
+            .await;
+        let unique = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::UrlEncoded(
+                "query".into(),
+                "content-checksum:bbb".into(),
+            ))
+            .with_body(
+                r#"{"query": "", "offset": 0, "limit": 100, "total": 1, "results": [
+                    {"id": 2, "checksum": "bbb"}]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let request = client.request();
+        let clusters = request
+            .find_duplicates(None, None)
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Unable to find duplicates");
+        listing.assert_async().await;
+        copies.assert_async().await;
+        unique.assert_async().await;
+
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].post.id, Some(3));
+        let exact = clusters[0].exact.iter().map(|p| p.id).collect::<Vec<_>>();
+        assert_eq!(exact, vec![Some(1)]);
+        assert!(clusters[0].similar.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_find_duplicates_newer_copy_outside_query() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::UrlEncoded(
+                "query".into(),
+                "safety:safe sort:id".into(),
+            ))
+            .with_body(
+                r#"{"query": "", "offset": 0, "limit": 100, "total": 1, "results": [
+                    {"id": 5, "checksum": "aaa", "type": "image"}]}"#,
+            )
+            .create_async()
+            .await;
+        // Post 10 is sketchy, so only the lookup for post 5 finds it
+        let copies = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("query".into(), "content-checksum:aaa".into()),
+                Matcher::Regex("limit=100$".into()),
+            ]))
+            .with_body(
+                r#"{"query": "", "offset": 0, "limit": 100, "total": 2, "results": [
+                    {"id": 10, "checksum": "aaa"}, {"id": 5, "checksum": "aaa"}]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let query = vec![QueryToken::token(PostNamedToken::Safety, "safe")];
+        // The caller's paging only applies to the listing, not to the checksum lookups
+        let request = client.request().with_offset(5).with_limit(1);
+        let clusters = request
+            .find_duplicates(None, Some(&query))
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Unable to find duplicates");
+        copies.assert_async().await;
+
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].post.id, Some(5));
+        let exact = clusters[0].exact.iter().map(|p| p.id).collect::<Vec<_>>();
+        assert_eq!(exact, vec![Some(10)]);
+    }
+
+    #[tokio::test]
+    async fn test_find_duplicates_similar() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::UrlEncoded("query".into(), "sort:id".into()))
+            .with_body(
+                r#"{"query": "", "offset": 0, "limit": 100, "total": 3, "results": [
+                    {"id": 3, "checksum": "ccc", "type": "image", "mimeType": "image/png"},
+                    {"id": 2, "checksum": "bbb", "type": "image", "mimeType": "image/png"},
+                    {"id": 1, "checksum": "aaa", "type": "image", "mimeType": "image/png"}]}"#,
+            )
+            .create_async()
+            .await;
+        let mut checksums = Vec::new();
+        for (id, checksum) in [(3, "ccc"), (2, "bbb"), (1, "aaa")] {
+            let mock = server
+                .mock("GET", "/api/posts")
+                .match_query(Matcher::UrlEncoded(
+                    "query".into(),
+                    format!("content-checksum:{checksum}"),
+                ))
+                .with_body(format!(
+                    r#"{{"query": "", "offset": 0, "limit": 100, "total": 1, "results": [
+                        {{"id": {id}, "checksum": "{checksum}"}}]}}"#
+                ))
+                .expect(if id == 3 { 1 } else { 0 })
+                .create_async()
+                .await;
+            checksums.push(mock);
+        }
+        server
+            .mock("GET", "/api/post/3")
+            .with_body(r#"{"id": 3, "contentUrl": "data/posts/3.png"}"#)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/data/posts/3.png")
+            .with_body("png bytes")
+            .create_async()
+            .await;
+        // All three posts look alike, so every one of them would match both of the others
+        let search = server
+            .mock("POST", "/api/posts/reverse-search")
+            .with_body(
+                r#"{"exactPost": null, "similarPosts": [
+                    {"distance": 0.02, "post": {"id": 3}},
+                    {"distance": 0.05, "post": {"id": 2}},
+                    {"distance": 0.08, "post": {"id": 1}}
+                ]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let request = client.request();
+        let clusters = request
+            .find_duplicates(Some(0.9), None)
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Unable to find duplicates");
+        search.assert_async().await;
+        for mock in checksums {
+            mock.assert_async().await;
+        }
+
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].post.id, Some(3));
+        assert!(clusters[0].exact.is_empty());
+        let similar = clusters[0]
+            .similar
+            .iter()
+            .map(|s| s.post.id)
+            .collect::<Vec<_>>();
+        assert_eq!(similar, vec![Some(2), Some(1)]);
+    }
+
+    #[tokio::test]
+    async fn test_create_posts_from_paths_progress() {
+        let mut server = Server::new_async().await;
+        let created = server
+            .mock("POST", "/api/posts")
+            .with_body(r#"{"id": 5, "version": 1}"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let first = temp_file_with("batch-1.png", b"first file");
+        let second = temp_file_with("batch-2.png", b"second, longer file");
+        let missing = std::env::temp_dir().join("szurubooru-batch-missing.png");
+        let paths = [first.clone(), missing.clone(), second.clone()];
+
+        let reports = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorder = reports.clone();
+        let results = client_for(&server)
+            .request()
+            .create_posts_from_paths(&paths, &["cat"], PostSafety::Safe, move |progress| {
+                recorder.lock().unwrap().push(progress.clone());
+            })
+            .await;
+        std::fs::remove_file(&first).unwrap();
+        std::fs::remove_file(&second).unwrap();
+        created.assert_async().await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].1.as_ref().unwrap().id, Some(5));
+        assert!(matches!(
+            results[1].1,
+            Err(SzurubooruClientError::FileNotFound(_))
+        ));
+        assert_eq!(results[2].0, second);
+
+        let reports = reports.lock().unwrap();
+        let items_done = reports.iter().map(|p| p.items_done).collect::<Vec<_>>();
+        assert!(items_done.windows(2).all(|w| w[0] <= w[1]));
+        let mut finished = items_done.clone();
+        finished.dedup();
+        assert_eq!(finished, vec![0, 1, 2, 3]);
+        assert!(reports
+            .windows(2)
+            .all(|w| w[0].bytes_sent <= w[1].bytes_sent));
+
+        let last = reports.last().unwrap();
+        assert_eq!(last.items_total, 3);
+        assert_eq!(last.bytes_total, 29);
+        assert_eq!(last.bytes_sent, 29);
+        assert_eq!(last.current_item.as_ref(), Some(&second));
+    }
+
+    #[tokio::test]
+    async fn test_streaming_upload_checksum() {
+        // 200KiB of a repeating pattern, so it's read in several chunks
+        let content = (0..200 * 1024).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        let expected = hex::encode(Sha1::digest(&content));
+
+        let mut upload = StreamingUpload::new(content.as_slice());
+        let mut buf = [0u8; 4096];
+        upload.read_exact(&mut buf).await.unwrap();
+        assert_eq!(upload.checksum(), None);
+        let mut rest = Vec::new();
+        upload.read_to_end(&mut rest).await.unwrap();
+        assert_eq!(upload.bytes_read(), content.len() as u64);
+        assert_eq!(upload.checksum(), Some(expected.clone()));
+
+        let mut server = Server::new_async().await;
+        let matching = server
+            .mock("POST", "/api/posts")
+            .match_body(Matcher::Regex(
+                "name=\"content\"; filename=\"pattern.png\"".to_string(),
+            ))
+            .with_body(format!(
+                r#"{{"id": 6, "version": 1, "checksum": "{expected}"}}"#
+            ))
+            .create_async()
+            .await;
+        let new_post = CreateUpdatePostBuilder::default()
+            .safety(PostSafety::Safe)
+            .build()
+            .unwrap();
+        let post = client_for(&server)
+            .request()
+            .create_post_from_reader(
+                std::io::Cursor::new(content.clone()),
+                "pattern.png",
+                &new_post,
+            )
+            .await
+            .expect("Unable to upload from reader");
+        matching.assert_async().await;
+        assert_eq!(post.id, Some(6));
+
+        matching.remove_async().await;
+        server
+            .mock("POST", "/api/posts")
+            .with_body(r#"{"id": 7, "version": 1, "checksum": "0000000000000000000000000000000000000000"}"#)
+            .create_async()
+            .await;
+        let result = client_for(&server)
+            .request()
+            .create_post_from_reader(std::io::Cursor::new(content), "pattern.png", &new_post)
+            .await;
+        assert!(matches!(
+            result,
+            Err(SzurubooruClientError::ChecksumMismatch { actual, .. }) if actual.starts_with("0000")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_post_from_bytes() {
+        // A 1x1 transparent PNG
+        const PNG: &[u8] = &[
+            0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48,
+            0x44, 0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00,
+            0x00, 0x1f, 0x15, 0xc4, 0x89, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x44, 0x41, 0x54, 0x78,
+            0x9c, 0x63, 0x00, 0x01, 0x00, 0x00, 0x05, 0x00, 0x01, 0x0d, 0x0a, 0x2d, 0xb4, 0x00,
+            0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+        ];
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/posts")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::Regex(
+                    r#"name="metadata"\r\n\r\n\{"tags":\["generated"\],"safety":"safe"\}"#
+                        .to_string(),
+                ),
+                Matcher::Regex(
+                    r#"name="content"; filename="upload.png"\r\nContent-Type: image/png"#
+                        .to_string(),
+                ),
+            ]))
+            .with_body(r#"{"id": 8, "version": 1}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let post = client
+            .request()
+            .create_post_from_bytes(PNG.to_vec(), "image/png", &["generated"], PostSafety::Safe)
+            .await
+            .expect("Unable to create post");
+        mock.assert_async().await;
+        assert_eq!(post.id, Some(8));
+        assert_eq!(extension_for_mime("image/jpeg"), Some("jpg"));
+        assert_eq!(extension_for_mime("application/octet-stream"), None);
+    }
+
+    #[tokio::test]
+    async fn test_create_post_required_tags() {
+        let mut server = Server::new_async().await;
+        let tags = server
+            .mock("GET", "/api/tags")
+            .match_query(Matcher::UrlEncoded("query".into(), "name:cat,tabby".into()))
+            .with_body(
+                r#"{"query": "name:cat,tabby", "offset": 0, "limit": 2, "total": 1, "results": [
+                    {"names": ["cat", "feline"], "version": 1}
+                ]}"#,
+            )
+            .expect(2)
+            .create_async()
+            .await;
+        let created = server
+            .mock("POST", "/api/posts")
+            .expect(0)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let request = client
+            .request()
+            .with_required_tags(vec!["cat".to_string(), "tabby".to_string()]);
+        let result = request
+            .create_post_from_bytes(
+                b"not checked".to_vec(),
+                "image/png",
+                &["cat"],
+                PostSafety::Safe,
+            )
+            .await;
+        match result {
+            Err(SzurubooruClientError::MissingRequiredTags(missing)) => {
+                assert_eq!(missing, vec!["tabby".to_string()])
+            }
+            other => panic!("Expected missing required tags, got {other:?}"),
+        }
+
+        let new_post = CreateUpdatePostBuilder::default()
+            .content_url("https://example.com/cat.jpg".to_string())
+            .tags(vec!["cat".to_string()])
+            .safety(PostSafety::Safe)
+            .build()
+            .unwrap();
+        let result = request.create_post_from_url(&new_post).await;
+        assert!(matches!(
+            result,
+            Err(SzurubooruClientError::MissingRequiredTags(_))
+        ));
+        tags.assert_async().await;
+        created.assert_async().await;
+        created.remove_async().await;
+
+        server
+            .mock("GET", "/api/tags")
+            .match_query(Matcher::UrlEncoded("query".into(), "name:feline".into()))
+            .with_body(
+                r#"{"query": "name:feline", "offset": 0, "limit": 1, "total": 1, "results": [
+                    {"names": ["cat", "feline"], "version": 1}
+                ]}"#,
+            )
+            .create_async()
+            .await;
+        let created = server
+            .mock("POST", "/api/posts")
+            .with_body(r#"{"id": 3, "version": 1}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let post = client
+            .request()
+            .with_required_tags(vec!["feline".to_string()])
+            .create_post_from_url(&new_post)
+            .await
+            .expect("Unable to create post");
+        created.assert_async().await;
+        assert_eq!(post.id, Some(3));
+
+        // A wildcard is looked up literally, and only an exact name counts as present
+        let wildcard = server
+            .mock("GET", "/api/tags")
+            .match_query(Matcher::UrlEncoded("query".into(), r"name:foo\*".into()))
+            .with_body(
+                r#"{"query": "name:foo\\*", "offset": 0, "limit": 1, "total": 1, "results": [
+                    {"names": ["foobar"], "version": 1}
+                ]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let result = client
+            .request()
+            .with_required_tags(vec!["foo*".to_string()])
+            .create_post_from_url(&new_post)
+            .await;
+        wildcard.assert_async().await;
+        match result {
+            Err(SzurubooruClientError::MissingRequiredTags(missing)) => {
+                assert_eq!(missing, vec!["foo*".to_string()])
+            }
+            other => panic!("Expected missing required tags, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_size_check() {
+        let mut server = Server::new_async().await;
+        let info = server
+            .mock("GET", "/api/info")
+            .with_body(
+                r#"{
+                    "postCount": 0,
+                    "diskUsage": 0,
+                    "serverTime": "2024-08-09T21:41:24.123623Z",
+                    "featuredPost": null,
+                    "featuringTime": null,
+                    "featuringUser": null,
+                    "config": {
+                        "userNameRegex": "^[a-zA-Z0-9_-]{1,32}$",
+                        "passwordRegex": "^.{5,}$",
+                        "tagNameRegex": "^\\S+$",
+                        "tagCategoryNameRegex": "^[^\\s%+#/]+$",
+                        "defaultUserRank": "regular",
+                        "enableSafety": true,
+                        "contactEmail": null,
+                        "canSendMails": false,
+                        "privileges": {},
+                        "maxUploadSize": 8
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let rejected = server
+            .mock("POST", "/api/posts")
+            .expect(0)
+            .create_async()
+            .await;
+
+        let client = client_for(&server).with_upload_size_check(true);
+        let large = temp_file_with("too-large.jpg", b"sixteen bytes!!!");
+        let result = client
+            .request()
+            .create_post_from_path(&large, &["cat"], PostSafety::Safe)
+            .await;
+        std::fs::remove_file(&large).unwrap();
+        match result {
+            Err(SzurubooruClientError::FileTooLarge { size, max }) => {
+                assert_eq!((size, max), (16, 8))
+            }
+            other => panic!("Expected a file too large error, got {other:?}"),
+        }
+        let result = client
+            .request()
+            .create_post_from_bytes(vec![0u8; 9], "image/png", &["cat"], PostSafety::Safe)
+            .await;
+        assert!(matches!(
+            result,
+            Err(SzurubooruClientError::FileTooLarge { size: 9, max: 8 })
+        ));
+        rejected.assert_async().await;
+        rejected.remove_async().await;
+
+        let created = server
+            .mock("POST", "/api/posts")
+            .with_body(r#"{"id": 2, "version": 1}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let small = temp_file_with("small-enough.jpg", b"8 bytes!");
+        let post = client
+            .request()
+            .create_post_from_path(&small, &["cat"], PostSafety::Safe)
+            .await;
+        std::fs::remove_file(&small).unwrap();
+        assert_eq!(post.expect("Unable to create post").id, Some(2));
+        created.assert_async().await;
+        info.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_create_post_from_url() {
+        let mut server = Server::new_async().await;
+        let created = server
+            .mock("POST", "/api/posts")
+            .match_header("content-type", "application/json")
+            .match_body(Matcher::Json(serde_json::json!({
+                "tags": ["cat"],
+                "safety": "safe",
+                "contentUrl": "https://example.com/cat.jpg"
+            })))
+            .with_body(r#"{"id": 3, "version": 1}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let new_post = CreateUpdatePostBuilder::default()
+            .content_url("https://example.com/cat.jpg".to_string())
+            .tags(vec!["cat".to_string()])
+            .safety(PostSafety::Safe)
+            .build()
+            .unwrap();
+        let client = client_for(&server);
+        let post = client
+            .request()
+            .create_post_from_url(&new_post)
+            .await
+            .expect("Unable to create post");
+        created.assert_async().await;
+        assert_eq!(post.id, Some(3));
+        created.remove_async().await;
+
+        server
+            .mock("POST", "/api/posts")
+            .with_status(400)
+            .with_body(
+                r#"{"name": "DownloadError", "title": "Download Error",
+                    "description": "Error downloading https://example.com/cat.jpg."}"#,
+            )
+            .create_async()
+            .await;
+        match client.request().create_post_from_url(&new_post).await {
+            Err(SzurubooruClientError::SzurubooruServerError(e)) => {
+                assert_eq!(e.name, SzurubooruServerErrorType::DownloadError)
+            }
+            other => panic!("Expected a download error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reverse_search() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/posts/reverse-search")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::Regex(r#"name="content"; filename="[^"]*.png""#.to_string()),
+                Matcher::Regex("Content-Type: image/png".to_string()),
+            ]))
+            .with_body(
+                r#"{"exactPost": {"id": 1, "contentUrl": "data/posts/1.png"},
+                    "similarPosts": [
+                        {"distance": 0.12, "post": {"id": 2, "contentUrl": "data/posts/2.png"}},
+                        {"distance": 0.3, "post": {"id": 3}},
+                        {"distance": 0.45, "post": {"id": 4}}
+                    ]}"#,
+            )
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let by_bytes = client
+            .request()
+            .reverse_search_bytes(b"png bytes".to_vec(), "image/png")
+            .await
+            .expect("Unable to reverse search bytes");
+        let path = temp_file_with("search.png", b"png bytes");
+        let by_path = client
+            .request()
+            .reverse_search_file_path(&path)
+            .await
+            .expect("Unable to reverse search file");
+        std::fs::remove_file(path).unwrap();
+        mock.assert_async().await;
+
+        for result in [by_bytes, by_path] {
+            let exact = result.exact_post.expect("Expected an exact match");
+            assert_eq!(exact.id, Some(1));
+            assert_eq!(
+                exact.content_url,
+                Some(format!("{}/data/posts/1.png", server.url()))
+            );
+            let similar: Vec<(Option<u32>, f32)> = result
+                .similar_posts
+                .iter()
+                .map(|p| (p.post.id, p.distance))
+                .collect();
+            assert_eq!(
+                similar,
+                vec![(Some(2), 0.12), (Some(3), 0.3), (Some(4), 0.45)]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_post_anonymously() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/posts")
+            .match_body(Matcher::Json(serde_json::json!({
+                "safety": "safe",
+                "contentUrl": "http://example.com/cat.jpg",
+                "anonymous": true
+            })))
+            .with_body(r#"{"id": 1, "version": 1, "user": null}"#)
+            .create_async()
+            .await;
+
+        let new_post = CreateUpdatePostBuilder::default()
+            .safety(PostSafety::Safe)
+            .content_url("http://example.com/cat.jpg".to_string())
+            .anonymous(true)
+            .build()
+            .unwrap();
+        let client = client_for(&server);
+        let post = client
+            .request()
+            .create_post_from_url(&new_post)
+            .await
+            .expect("Unable to create post");
+
+        mock.assert_async().await;
+        assert!(post.user.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_retag_search() {
+        let mut server = Server::new_async().await;
+        for (offset, id) in [("0", 1), ("1", 2)] {
+            server
+                .mock("GET", "/api/posts")
+                .match_query(Matcher::AllOf(vec![
+                    Matcher::UrlEncoded("query".into(), "cat sort:id".into()),
+                    Matcher::UrlEncoded("fields".into(), "id,version".into()),
+                    Matcher::UrlEncoded("offset".into(), offset.into()),
+                ]))
+                .with_body(format!(
+                    r#"{{"query": "cat", "offset": {offset}, "limit": 1, "total": 2,
+                        "results": [{{"id": {id}}}]}}"#
+                ))
+                .create_async()
+                .await;
+        }
+        server
+            .mock("GET", "/api/post/1")
+            .match_query(Matcher::UrlEncoded("fields".into(), "version,tags".into()))
+            .with_body(
+                r#"{"version": 4, "tags": [
+                    {"names": ["cat"], "category": "default", "usages": 2}
+                ]}"#,
+            )
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/api/post/2")
+            .match_query(Matcher::UrlEncoded("fields".into(), "version,tags".into()))
+            .with_body(
+                r#"{"version": 9, "tags": [
+                    {"names": ["cat"], "category": "default", "usages": 2},
+                    {"names": ["tabby", "striped"], "category": "default", "usages": 1}
+                ]}"#,
+            )
+            .create_async()
+            .await;
+        let update = server
+            .mock("PUT", "/api/post/1")
+            .match_query(Matcher::Any)
+            .match_body(Matcher::Json(serde_json::json!({
+                "version": 4,
+                "tags": ["cat", "striped"]
+            })))
+            .with_body(r#"{"id": 1}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let unchanged = server
+            .mock("PUT", "/api/post/2")
+            .match_query(Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let query = vec![QueryToken::anonymous("cat")];
+        let summary = client
+            .with_limit(1)
+            .retag_search(Some(&query), &["striped"], &[], 2, None, None)
+            .await
+            .expect("Unable to retag posts");
+        update.assert_async().await;
+        unchanged.assert_async().await;
+        assert_eq!(summary.matched, 2);
+        assert_eq!(summary.updated, vec![1]);
+        assert_eq!(summary.unchanged, 1);
+        assert!(summary.failed.is_empty());
+        assert_eq!(summary.cursor, Some(BulkCursor { last_post_id: 2 }));
+    }
+
+    #[tokio::test]
+    async fn test_retag_search_resume() {
+        let mut server = Server::new_async().await;
+        let search = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("query".into(), "cat id:..6 sort:id".into()),
+                Matcher::UrlEncoded("fields".into(), "id,version".into()),
+            ]))
+            .with_body(
+                r#"{"query": "cat id:..6 sort:id", "offset": 0, "limit": 100, "total": 2,
+                    "results": [{"id": 6}, {"id": 3}]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        for post_id in [6, 3] {
+            server
+                .mock("GET", format!("/api/post/{post_id}").as_str())
+                .match_query(Matcher::Any)
+                .with_body(r#"{"version": 1, "tags": []}"#)
+                .expect(1)
+                .create_async()
+                .await;
+            server
+                .mock("PUT", format!("/api/post/{post_id}").as_str())
+                .match_query(Matcher::Any)
+                .with_body(format!(r#"{{"id": {post_id}}}"#))
+                .expect(1)
+                .create_async()
+                .await;
+        }
+        let already_done = server
+            .mock("GET", Matcher::Regex(r"^/api/post/(7|8|9)$".into()))
+            .match_query(Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let query = vec![
+            QueryToken::anonymous("cat"),
+            QueryToken::sort(PostSortToken::Random),
+        ];
+        let mut progress = Vec::new();
+        let mut record = |cursor: BulkCursor| progress.push(cursor.last_post_id);
+        let summary = client
+            .request()
+            .retag_search(
+                Some(&query),
+                &["striped"],
+                &[],
+                1,
+                Some(BulkCursor { last_post_id: 7 }),
+                Some(&mut record),
+            )
+            .await
+            .expect("Unable to resume retagging");
+        search.assert_async().await;
+        already_done.assert_async().await;
+        assert_eq!(summary.updated, vec![6, 3]);
+        assert_eq!(progress, vec![6, 3]);
+        assert_eq!(summary.cursor, Some(BulkCursor { last_post_id: 3 }));
+    }
+
+    #[tokio::test]
+    async fn test_tag_category_stats() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/api/tag-categories")
+            .with_body(
+                r#"{"results": [
+                    {"name": "default", "version": 1, "usages": 3},
+                    {"version": 4, "usages": 2},
+                    {"name": "character", "version": 2, "usages": 1}
+                ]}"#,
+            )
+            .create_async()
+            .await;
+        for (category, total) in [("default", 3), ("character", 1)] {
+            server
+                .mock("GET", "/api/tags")
+                .match_query(Matcher::UrlEncoded(
+                    "query".to_string(),
+                    format!("category:{category}"),
+                ))
+                .with_body(format!(
+                    r#"{{"query": "category:{category}", "offset": 0, "limit": 1, "total": {total},
+                        "results": [{{"version": 1, "names": ["some_tag"]}}]}}"#
+                ))
+                .expect(1)
+                .create_async()
+                .await;
+        }
+
+        let client = client_for(&server);
+        let stats = client
+            .request()
+            .tag_category_stats()
+            .await
+            .expect("Unable to fetch tag category stats");
+        let stats: Vec<(Option<String>, u32)> = stats
+            .into_iter()
+            .map(|(cat, count)| (cat.name, count))
+            .collect();
+        assert_eq!(
+            stats,
+            vec![
+                (Some("default".to_string()), 3),
+                (Some("character".to_string()), 1)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_json_accept_header() {
+        use futures_util::StreamExt;
+
+        let mut server = Server::new_async().await;
+        let api = server
+            .mock("GET", "/api/post/1")
+            .match_header("accept", "application/json")
+            .with_body(r#"{"id": 1, "contentUrl": "data/posts/1.jpg"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let download = server
+            .mock("GET", "/data/posts/1.jpg")
+            .match_header("accept", "*/*")
+            .with_body("not really a jpeg")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let bytes: Vec<u8> = client
+            .request()
+            .get_image_bytestream(1)
+            .await
+            .expect("Unable to download post")
+            .map(|chunk| chunk.unwrap().to_vec())
+            .concat()
+            .await;
+        api.assert_async().await;
+        download.assert_async().await;
+        assert_eq!(bytes, b"not really a jpeg");
+
+        let info = server
+            .mock("GET", "/api/info")
+            .match_header("accept", "*/*")
+            .with_body("{}")
+            .expect(1)
+            .create_async()
+            .await;
+        let client = client_for(&server).with_json_accept_header(false);
+        client.ping().await.expect("Unable to ping server");
+        info.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_hydrate_relations_fetches_once() {
+        let mut server = Server::new_async().await;
+        let fetch = server
+            .mock("GET", "/api/post/5")
+            .match_query(Matcher::UrlEncoded(
+                "fields".into(),
+                "relations,pools".into(),
+            ))
+            .with_body(
+                r#"{"relations": [{"id": 6, "thumbnailUrl": "data/generated-thumbnails/6.jpg"}],
+                    "pools": []}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let request = client.request();
+        let mut post: PostResource = serde_json::from_str(r#"{"id": 5}"#).unwrap();
+        post.hydrate_relations(&request).await.unwrap();
+        post.hydrate_relations(&request).await.unwrap();
+        fetch.assert_async().await;
+        assert_eq!(post.relations.unwrap()[0].id, 6);
+        assert_eq!(post.pools.map(|pools| pools.len()), Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_pools_containing_post() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/post/7")
+            .match_query(Matcher::UrlEncoded("fields".into(), "pools".into()))
+            .with_body(
+                r#"{"pools": [
+                    {"id": 1, "version": 2, "names": ["Morning walks"], "postCount": 3},
+                    {"id": 4, "version": 1, "names": ["Best of 2024"], "postCount": 12}
+                ]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let pools = client
+            .with_fields(vec!["id".to_string()])
+            .pools_containing_post(7)
+            .await
+            .expect("Unable to fetch pools");
+        mock.assert_async().await;
+        let ids: Vec<Option<u32>> = pools.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![Some(1), Some(4)]);
+        assert_eq!(pools[1].names, Some(vec!["Best of 2024".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_list_posts_cursor() {
+        use futures_util::StreamExt;
+
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("query".into(), "cat sort:id".into()),
+                Matcher::UrlEncoded("limit".into(), "2".into()),
+            ]))
+            .with_body(
+                r#"{"query": "", "offset": 0, "limit": 2, "total": 4,
+                    "results": [{"id": 5}, {"id": 4}]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        // Post 6 is uploaded before the second page is fetched. Offset paging would return
+        // post 4 a second time here
+        let second_page = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("query".into(), "cat id:..3 sort:id".into()),
+                Matcher::UrlEncoded("limit".into(), "2".into()),
+            ]))
+            .with_body(
+                r#"{"query": "", "offset": 0, "limit": 2, "total": 2,
+                    "results": [{"id": 3}, {"id": 2}]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let query = vec![
+            QueryToken::anonymous("cat"),
+            QueryToken::sort(PostSortToken::Random),
+        ];
+        let ids: Vec<Option<u32>> = client
+            .with_limit(2)
+            .with_offset(10)
+            .list_posts_cursor(Some(&query))
+            .map(|post| post.expect("Unable to list posts").id)
+            .collect()
+            .await;
+        second_page.assert_async().await;
+        assert_eq!(ids, vec![Some(5), Some(4), Some(3), Some(2)]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_tags() {
+        use futures_util::StreamExt;
+
+        let mut server = Server::new_async().await;
+        let pages = [
+            (
+                "0",
+                r#"[{"version": 1, "names": ["a"]}, {"version": 1, "names": ["b"]}]"#,
+            ),
+            ("2", r#"[{"version": 1, "names": ["c"]}]"#),
+        ];
+        for (offset, results) in pages {
+            server
+                .mock("GET", "/api/tags")
+                .match_query(Matcher::AllOf(vec![
+                    Matcher::UrlEncoded("query".into(), "usages:1..".into()),
+                    Matcher::UrlEncoded("limit".into(), "2".into()),
+                    Matcher::UrlEncoded("offset".into(), offset.into()),
+                ]))
+                .with_body(format!(
+                    r#"{{"query": "", "offset": {offset}, "limit": 2, "total": 3,
+                        "results": {results}}}"#
+                ))
+                .expect(1)
+                .create_async()
+                .await;
+        }
+
+        let client = client_for(&server);
+        let query = vec![QueryToken::token(
+            TagNamedToken::Usages,
+            RangeToken::at_least(1),
+        )];
+        let names: Vec<String> = client
+            .with_limit(2)
+            .stream_tags(Some(&query))
+            .map(|tag| tag.expect("Unable to list tags").names.unwrap().remove(0))
+            .collect()
+            .await;
+        assert_eq!(names, vec!["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_users_error() {
+        use futures_util::StreamExt;
+
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/api/users")
+            .match_query(Matcher::UrlEncoded("offset".into(), "0".into()))
+            .with_body(
+                r#"{"query": "", "offset": 0, "limit": 1, "total": 5, "results": [{"name": "a"}]}"#,
+            )
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/api/users")
+            .match_query(Matcher::UrlEncoded("offset".into(), "1".into()))
+            .with_status(500)
+            .with_body("Internal error")
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let request = client.with_limit(1);
+        let results: Vec<SzurubooruResult<UserResource>> =
+            request.stream_users(None).collect().await;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap().name, Some("a".to_string()));
+        assert!(matches!(
+            results[1],
+            Err(SzurubooruClientError::ResponseError(
+                reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+                _
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_ping() {
+        let mut server = Server::new_async().await;
+        let info = server
+            .mock("GET", "/api/info")
+            .with_body(r#"{"postCount": 0}"#)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let latency = client.ping().await.expect("Unable to ping server");
+        info.assert_async().await;
+        assert!(latency > Duration::ZERO);
+
+        // Nothing listens on port 1
+        let unreachable = SzurubooruClient::new_anonymous("http://127.0.0.1:1", false).unwrap();
+        assert!(matches!(
+            unreachable.ping().await,
+            Err(SzurubooruClientError::RequestError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_merge_posts() {
+        let mut server = Server::new_async().await;
+        let merge = server
+            .mock("POST", "/api/post-merge/")
+            .match_body(Matcher::Json(serde_json::json!({
+                "remove": 12,
+                "removeVersion": 2,
+                "mergeTo": 3,
+                "mergeToVersion": 5,
+                "replaceContent": true
+            })))
+            .with_body(
+                r#"{"id": 3, "version": 6, "favoriteCount": 4,
+                    "contentUrl": "data/posts/3_def.png"}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let keep_content = server
+            .mock("POST", "/api/post-merge/")
+            .match_body(Matcher::PartialJson(
+                serde_json::json!({"replaceContent": false}),
+            ))
+            .with_body(r#"{"id": 3, "version": 7}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let post = client
+            .request()
+            .merge_posts(12, 2, 3, 5, true)
+            .await
+            .expect("Unable to merge posts");
+        merge.assert_async().await;
+        assert_eq!(post.id, Some(3));
+        assert_eq!(post.version, Some(6));
+        assert_eq!(post.favorite_count, Some(4));
+        assert_eq!(
+            post.content_url,
+            Some(format!("{}/data/posts/3_def.png", server.url()))
+        );
+
+        let post = client
+            .request()
+            .merge_posts(13, 1, 3, 6, false)
+            .await
+            .expect("Unable to merge posts");
+        keep_content.assert_async().await;
+        assert_eq!(post.version, Some(7));
+    }
+
+    #[tokio::test]
+    async fn test_merge_pools_by_id() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/api/pool/1")
+            .match_query(Matcher::UrlEncoded("fields".into(), "version".into()))
+            .with_body(r#"{"version": 3}"#)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/api/pool/2")
+            .match_query(Matcher::UrlEncoded("fields".into(), "version".into()))
+            .with_body(r#"{"version": 7}"#)
+            .create_async()
+            .await;
+        let merge = server
+            .mock("POST", "/api/pool-merge")
+            .match_body(Matcher::Json(serde_json::json!({
+                "remove": 1,
+                "removeVersion": 3,
+                "mergeTo": 2,
+                "mergeToVersion": 7
+            })))
+            .with_body(r#"{"id": 2, "version": 8, "names": ["cats"], "postCount": 4}"#)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let pool = client
+            .request()
+            .merge_pools_by_id(1, 2)
+            .await
+            .expect("Unable to merge pools");
+        merge.assert_async().await;
+        assert_eq!(pool.id, Some(2));
+        assert_eq!(pool.version, Some(8));
+        assert_eq!(pool.names, Some(vec!["cats".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_user_uploads() {
+        use futures_util::StreamExt;
+
+        let mut server = Server::new_async().await;
+        let query_for = |offset: &str| {
+            Matcher::AllOf(vec![
+                Matcher::UrlEncoded(
+                    "query".into(),
+                    "uploader:some-user sort:creation-date".into(),
+                ),
+                Matcher::UrlEncoded("limit".into(), "2".into()),
+                Matcher::UrlEncoded("offset".into(), offset.into()),
+            ])
+        };
+        server
+            .mock("GET", "/api/posts")
+            .match_query(query_for("0"))
+            .with_body(
+                r#"{"query": "", "offset": 0, "limit": 2, "total": 3,
+                    "results": [{"id": 3}, {"id": 2}]}"#,
+            )
+            .create_async()
+            .await;
+        let last_page = server
+            .mock("GET", "/api/posts")
+            .match_query(query_for("2"))
+            .with_body(
+                r#"{"query": "", "offset": 2, "limit": 2, "total": 3,
+                    "results": [{"id": 1}]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let request = client.with_limit(2);
+        let username = "some-user".to_string();
+        let ids: Vec<Option<u32>> = request
+            .user_uploads(&username)
+            .map(|post| post.expect("Unable to fetch uploads").id)
+            .collect()
+            .await;
+        last_page.assert_async().await;
+        assert_eq!(ids, vec![Some(3), Some(2), Some(1)]);
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        // Accepts connections into the backlog but never answers them
+        let listener =
+            std::net::TcpListener::bind("127.0.0.1:0").expect("Unable to bind test listener");
+        let host = format!("http://{}", listener.local_addr().unwrap());
+        let client = SzurubooruClient::new_anonymous(&host, false)
+            .expect("Unable to create client")
+            .with_timeout(Duration::from_millis(200));
+
+        let start = Instant::now();
+        let result = client.request().get_global_info().await;
+        let elapsed = start.elapsed();
+        match result {
+            Err(SzurubooruClientError::RequestError(e)) => assert!(e.is_timeout(), "{e:?}"),
+            other => panic!("Expected a timeout error, got {other:?}"),
+        }
+        assert!(elapsed >= Duration::from_millis(200), "{elapsed:?}");
+        assert!(elapsed < Duration::from_secs(5), "{elapsed:?}");
+        drop(listener);
+    }
+
+    #[tokio::test]
+    async fn test_dashboard() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Holds every response back until all four sections have sent their request, so the
+        // dashboard only completes if they are in flight at the same time
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Unable to bind test listener");
+        let host = format!("http://{}", listener.local_addr().unwrap());
+        let barrier = Arc::new(tokio::sync::Barrier::new(4));
+        let paths = Arc::new(Mutex::new(Vec::new()));
+        let server = {
+            let paths = paths.clone();
+            tokio::spawn(async move {
+                for _ in 0..4 {
+                    let (mut socket, _) = listener.accept().await.unwrap();
+                    let barrier = barrier.clone();
+                    let paths = paths.clone();
+                    tokio::spawn(async move {
+                        let mut head = Vec::new();
+                        let mut buf = [0u8; 1024];
+                        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                            let read = socket.read(&mut buf).await.unwrap();
+                            head.extend_from_slice(&buf[..read]);
+                        }
+                        let head = String::from_utf8_lossy(&head).to_string();
+                        let path = head.split(' ').nth(1).unwrap().to_string();
+                        paths.lock().unwrap().push(path.clone());
+                        barrier.wait().await;
+
+                        let (status, body) = if path.starts_with("/api/comments") {
+                            (
+                                "403 Forbidden",
+                                r#"{"name": "AuthError", "title": "Not authorized",
+                                    "description": "Insufficient privileges to do this."}"#
+                                    .to_string(),
+                            )
+                        } else {
+                            let result = match path.split('?').next().unwrap() {
+                                "/api/posts" => r#"{"id": 3, "version": 1}"#,
+                                "/api/tags" => r#"{"names": ["cat"], "version": 1, "usages": 9}"#,
+                                _ => r#"{"id": 2, "version": 1, "names": ["best"]}"#,
+                            };
+                            (
+                                "200 OK",
+                                format!(
+                                    r#"{{"query": "", "offset": 0, "limit": 5, "total": 1,
+                                        "results": [{result}]}}"#
+                                ),
+                            )
+                        };
+                        let response = format!(
+                            "HTTP/1.1 {status}\r\nContent-Type: application/json\r\n\
+                             Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                            body.len()
+                        );
+                        socket.write_all(response.as_bytes()).await.unwrap();
+                    });
+                }
+            })
+        };
+
+        let client = SzurubooruClient::new_anonymous(&host, false)
+            .expect("Unable to create client")
+            .with_timeout(Duration::from_secs(5));
+        let queries = DashboardQueries {
+            tags: Some(vec![QueryToken::sort(TagSortToken::Usages)]),
+            ..Default::default()
+        };
+        let dashboard = client.request().with_limit(5).dashboard(&queries).await;
+        server.await.unwrap();
+
+        let mut paths = paths.lock().unwrap().clone();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                "/api/comments?limit=5",
+                "/api/pools?limit=5",
+                "/api/posts?limit=5",
+                "/api/tags?query=sort%3Ausages&limit=5",
+            ]
+        );
+        assert!(!dashboard.is_complete());
+        let posts = dashboard.posts.expect("Unable to list posts");
+        assert_eq!(posts.results[0].id, Some(3));
+        let tags = dashboard.tags.expect("Unable to list tags");
+        assert_eq!(tags.results[0].usages, Some(9));
+        let pools = dashboard.pools.expect("Unable to list pools");
+        assert_eq!(pools.results[0].id, Some(2));
+        match dashboard.comments {
+            Err(SzurubooruClientError::SzurubooruServerError(e)) => {
+                assert_eq!(e.name, SzurubooruServerErrorType::AuthError);
+                assert_eq!(e.status, Some(StatusCode::FORBIDDEN));
+            }
+            other => panic!("Expected an auth error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_posts_of_type() {
+        let mut server = Server::new_async().await;
+        let client = client_for(&server);
+        let variants = [
+            (PostType::Image, "type:image"),
+            (PostType::Animation, "type:animation"),
+            (PostType::Animated, "type:animated"),
+            (PostType::Anim, "type:anim"),
+            (PostType::Flash, "type:flash"),
+            (PostType::Swf, "type:swf"),
+            (PostType::Video, "type:video"),
+            (PostType::Webm, "type:webm"),
+        ];
+        for (post_type, expected) in variants {
+            let mock = server
+                .mock("GET", "/api/posts")
+                .match_query(Matcher::UrlEncoded("query".into(), expected.into()))
+                .with_body(r#"{"query": "", "offset": 0, "limit": 100, "total": 0, "results": []}"#)
+                .expect(1)
+                .create_async()
+                .await;
+            client
+                .request()
+                .list_posts_of_type(post_type, None)
+                .await
+                .expect("Unable to list posts by type");
+            mock.assert_async().await;
+            mock.remove_async().await;
+        }
+
+        let extra = vec![QueryToken::token(PostNamedToken::Safety, PostSafety::Safe)];
+        let mock = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::UrlEncoded(
+                "query".into(),
+                "type:video safety:safe".into(),
+            ))
+            .with_body(r#"{"query": "", "offset": 0, "limit": 100, "total": 0, "results": []}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        client
+            .request()
+            .list_posts_of_type(PostType::Video, Some(&extra))
+            .await
+            .expect("Unable to list posts by type");
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_get_tag_exact() {
+        let mut server = Server::new_async().await;
+        let exact = server
+            .mock("GET", "/api/tags")
+            .match_query(Matcher::UrlEncoded("query".into(), "name:foo".into()))
+            .with_body(
+                r#"{"query": "name:foo", "offset": 0, "limit": 100, "total": 2, "results": [
+                    {"version": 1, "names": ["foobar"], "category": "default", "usages": 1},
+                    {"version": 1, "names": ["Foo", "foo_alias"], "category": "default", "usages": 3}
+                ]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let alias = server
+            .mock("GET", "/api/tags")
+            .match_query(Matcher::UrlEncoded("query".into(), "name:foo_alias".into()))
+            .with_body(
+                r#"{"query": "name:foo_alias", "offset": 0, "limit": 100, "total": 1, "results": [
+                    {"version": 1, "names": ["Foo", "foo_alias"], "category": "default", "usages": 3}
+                ]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let literal = server
+            .mock("GET", "/api/tags")
+            .match_query(Matcher::UrlEncoded("query".into(), "name:foo\\*".into()))
+            .with_body(
+                r#"{"query": "name:foo\\*", "offset": 0, "limit": 100, "total": 0, "results": []}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let tag = client
+            .request()
+            .get_tag_exact("foo")
+            .await
+            .expect("Unable to look up tag")
+            .expect("Tag foo should exist");
+        assert_eq!(tag.names.unwrap(), vec!["Foo", "foo_alias"]);
+
+        let by_alias = client
+            .request()
+            .get_tag_exact("foo_alias")
+            .await
+            .expect("Unable to look up tag")
+            .expect("Tag foo_alias should exist");
+        assert_eq!(by_alias.names.unwrap(), vec!["Foo", "foo_alias"]);
+
+        let wildcard = client
+            .request()
+            .get_tag_exact("foo*")
+            .await
+            .expect("Unable to look up tag");
+        assert!(wildcard.is_none());
+        exact.assert_async().await;
+        alias.assert_async().await;
+        literal.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_favorite_post() {
+        let mut server = Server::new_async().await;
+        let favorite = server
+            .mock("POST", "/api/post/5/favorite")
+            .match_header("authorization", "Token dXNlcjp0b2tlbg==")
+            .with_body(r#"{"id": 5, "favoriteCount": 3, "ownFavorite": true}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let unfavorite = server
+            .mock("DELETE", "/api/post/5/favorite")
+            .match_header("authorization", "Token dXNlcjp0b2tlbg==")
+            .with_body(r#"{"id": 5, "favoriteCount": 2, "ownFavorite": false}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let post = client
+            .request()
+            .favorite_post(5)
+            .await
+            .expect("Unable to favorite post");
+        favorite.assert_async().await;
+        assert_eq!(post.favorite_count, Some(3));
+        assert_eq!(post.own_favorite, Some(true));
+
+        let post = client
+            .request()
+            .unfavorite_post(5)
+            .await
+            .expect("Unable to unfavorite post");
+        unfavorite.assert_async().await;
+        assert_eq!(post.favorite_count, Some(2));
+        assert_eq!(post.own_favorite, Some(false));
+    }
+
+    #[tokio::test]
+    async fn test_create_returns_id_and_version() {
+        let mut server = Server::new_async().await;
+        let post = server
+            .mock("POST", "/api/posts")
+            .with_body(r#"{"id": 41, "version": 1, "safety": "safe"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let tag = server
+            .mock("POST", "/api/tags")
+            .with_body(r#"{"names": ["cat"], "category": "default", "version": 1}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let pool = server
+            .mock("POST", "/api/pool")
+            .with_body(r#"{"id": 7, "version": 1, "names": ["cats"], "posts": []}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let new_post = CreateUpdatePostBuilder::default()
+            .content_url("https://example.com/cat.jpg".to_string())
+            .safety(PostSafety::Safe)
+            .build()
+            .unwrap();
+        let created = client
+            .request()
+            .create_post_from_url(&new_post)
+            .await
+            .expect("Unable to create post");
+        post.assert_async().await;
+        assert_eq!((created.id, created.version), (Some(41), Some(1)));
+
+        let new_tag = CreateUpdateTagBuilder::default()
+            .names(vec!["cat".to_string()])
+            .build()
+            .unwrap();
+        let created = client
+            .request()
+            .create_tag(&new_tag)
+            .await
+            .expect("Unable to create tag");
+        tag.assert_async().await;
+        assert_eq!(created.version, 1);
+
+        let new_pool = CreateUpdatePoolBuilder::default()
+            .names(vec!["cats".to_string()])
+            .build()
+            .unwrap();
+        let created = client
+            .request()
+            .create_pool(&new_pool)
+            .await
+            .expect("Unable to create pool");
+        pool.assert_async().await;
+        assert_eq!((created.id, created.version), (Some(7), Some(1)));
+    }
+
+    #[tokio::test]
+    async fn test_create_and_reorder_pool() {
+        let mut server = Server::new_async().await;
+        let pool_body = |posts: [u32; 3], version: u32| {
+            let posts = posts
+                .iter()
+                .map(|id| {
+                    format!(
+                        r#"{{"id": {id}, "thumbnailUrl": "data/generated-thumbnails/{id}.jpg"}}"#
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(r#"{{"id": 7, "version": {version}, "names": ["cats"], "posts": [{posts}]}}"#)
+        };
+        let created = server
+            .mock("POST", "/api/pool")
+            .match_body(Matcher::Json(serde_json::json!({
+                "names": ["cats"],
+                "category": "default",
+                "posts": [3, 1, 2],
+            })))
+            .with_body(pool_body([3, 1, 2], 1))
+            .expect(1)
+            .create_async()
+            .await;
+        let reordered = server
+            .mock("PUT", "/api/pool/7")
+            .match_body(Matcher::Json(serde_json::json!({
+                "version": 1,
+                "posts": [1, 2, 3],
+            })))
+            .with_body(pool_body([1, 2, 3], 2))
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let new_pool = CreateUpdatePoolBuilder::default()
+            .names(vec!["cats".to_string()])
+            .category("default".to_string())
+            .posts(vec![3, 1, 2])
+            .build()
+            .unwrap();
+        let pool = client
+            .request()
+            .create_pool(&new_pool)
+            .await
+            .expect("Unable to create pool");
+        created.assert_async().await;
+        let post_ids = |pool: &PoolResource| {
+            pool.posts
+                .iter()
+                .flatten()
+                .map(|p| p.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(post_ids(&pool), vec![3, 1, 2]);
+
+        let reorder = CreateUpdatePoolBuilder::default()
+            .version(pool.version.unwrap())
+            .posts(vec![1, 2, 3])
+            .build()
+            .unwrap();
+        let pool = client
+            .request()
+            .update_pool(7, &reorder)
+            .await
+            .expect("Unable to reorder pool");
+        reordered.assert_async().await;
+        assert_eq!(post_ids(&pool), vec![1, 2, 3]);
+        assert_eq!(pool.version, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_list_posts_raw() {
+        let mut server = Server::new_async().await;
+        let first = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("query".into(), "cat".into()),
+                Matcher::UrlEncoded("offset".into(), "0".into()),
+            ]))
+            .with_body(
+                r#"{"query": "cat", "offset": 0, "limit": 2, "total": 3, "results": [
+                    {"id": 3, "version": 1, "customField": {"nested": true}},
+                    {"id": 2, "version": 5, "contentUrl": "data/posts/2.png"}
+                ]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let second = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("query".into(), "cat".into()),
+                Matcher::UrlEncoded("offset".into(), "2".into()),
+            ]))
+            .with_body(
+                r#"{"query": "cat", "offset": 2, "limit": 2, "total": 3, "results": [
+                    {"id": 1, "version": 2}
+                ]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server).with_strict_deserialization(true);
+        let query = vec![QueryToken::anonymous("cat")];
+        let posts: Vec<Value> = client
+            .request()
+            .with_limit(2)
+            .list_posts_raw(Some(&query))
+            .try_collect()
+            .await
+            .expect("Unable to list raw posts");
+        first.assert_async().await;
+        second.assert_async().await;
+
+        let ids = posts
+            .iter()
+            .map(|post| post["id"].as_u64())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![Some(3), Some(2), Some(1)]);
+        assert_eq!(posts[0]["customField"]["nested"], Value::Bool(true));
+        assert_eq!(posts[1]["contentUrl"], "data/posts/2.png");
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit() {
+        let mut server = Server::new_async().await;
+        let tag = server
+            .mock("GET", "/api/tag/cat")
+            .with_body(r#"{"names": ["cat"], "version": 1}"#)
+            .expect(10)
+            .create_async()
+            .await;
+
+        // 5 requests at 20 per second need at least 4 gaps of 50ms between them
+        let client = client_for(&server)
+            .with_rate_limit(20.0)
+            .expect("Unable to set rate limit");
+        let start = Instant::now();
+        for _ in 0..5 {
+            client
+                .request()
+                .get_tag("cat")
+                .await
+                .expect("Unable to get tag");
+        }
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        // Concurrent requests are spaced out as well
+        let start = Instant::now();
+        let results = futures_util::future::join_all((0..5).map(|_| {
+            let request = client.request();
+            async move { request.get_tag("cat").await }
+        }))
+        .await;
+        assert!(results.iter().all(Result::is_ok));
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        tag.assert_async().await;
+
+        for invalid in [0.0, -1.0, f64::NAN, f64::INFINITY, 1e-300] {
+            let result = client_for(&server).with_rate_limit(invalid);
+            assert!(
+                matches!(result, Err(SzurubooruClientError::ValidationError(_))),
+                "{invalid} was accepted"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_info() {
+        let mut server = Server::new_async().await;
+        let info_mock = server
+            .mock("GET", "/api/info")
+            .with_body(
+                r#"{
+                    "postCount": 1234,
+                    "diskUsage": 8589934592,
+                    "serverTime": "2024-08-09T21:41:24.123623Z",
+                    "featuredPost": {
+                        "id": 42,
+                        "version": 3,
+                        "safety": "safe",
+                        "contentUrl": "data/posts/42_abc.jpg",
+                        "thumbnailUrl": "data/generated-thumbnails/42_abc.jpg"
+                    },
+                    "featuringTime": "2024-08-01T12:00:00Z",
+                    "featuringUser": {"name": "admin", "avatarUrl": "data/avatars/admin.png"},
+                    "config": {
+                        "name": "booru",
+                        "userNameRegex": "^[a-zA-Z0-9_-]{1,32}$",
+                        "passwordRegex": "^.{5,}$",
+                        "tagNameRegex": "^\\S+$",
+                        "tagCategoryNameRegex": "^[^\\s%+#/]+$",
+                        "defaultUserRank": "regular",
+                        "enableSafety": true,
+                        "contactEmail": "admin@example.com",
+                        "canSendMails": false,
+                        "privileges": {"posts:create:identified": "regular"}
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let info = client_for(&server)
+            .request()
+            .get_info()
+            .await
+            .expect("Unable to get info");
+        info_mock.assert_async().await;
+        assert_eq!(info.post_count, 1234);
+        assert_eq!(info.disk_usage, 8 * 1024 * 1024 * 1024);
+        let featured = info.featured_post.expect("No featured post");
+        assert_eq!(featured.id, Some(42));
+        assert_eq!(
+            featured.content_url,
+            Some(format!("{}/data/posts/42_abc.jpg", server.url()))
+        );
+        let featuring_user = info.featuring_user.expect("No featuring user");
+        assert_eq!(featuring_user.name.as_deref(), Some("admin"));
+        assert!(info.featuring_time.is_some());
+        assert!(info.config.enable_safety);
+        assert_eq!(info.config.default_user_rank, "regular");
+        assert_eq!(
+            info.config.privileges.get("posts:create:identified"),
+            Some(&"regular".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_pool() {
+        let mut server = Server::new_async().await;
+        let deleted = server
+            .mock("DELETE", "/api/pool/7")
+            .match_body(Matcher::Json(serde_json::json!({"version": 2})))
+            .with_status(204)
+            .expect(1)
+            .create_async()
+            .await;
+        let deleted_with_body = server
+            .mock("DELETE", "/api/pool/8")
+            .match_body(Matcher::Json(serde_json::json!({"version": 1})))
+            .with_body("{}")
+            .expect(1)
+            .create_async()
+            .await;
+        let stale = server
+            .mock("DELETE", "/api/pool/9")
+            .with_status(409)
+            .with_body(VERSION_CONFLICT_BODY)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        client
+            .request()
+            .delete_pool(7, 2)
+            .await
+            .expect("Unable to delete pool");
+        client
+            .request()
+            .delete_pool(8, 1)
+            .await
+            .expect("Unable to delete pool");
+        let result = client.request().delete_pool(9, 1).await;
+        deleted.assert_async().await;
+        deleted_with_body.assert_async().await;
+        stale.assert_async().await;
+        assert_version_conflict(result);
+    }
+
+    #[tokio::test]
+    async fn test_delete_post() {
+        let mut server = Server::new_async().await;
+        let deleted = server
+            .mock("DELETE", "/api/post/12")
+            .match_header("authorization", "Token dXNlcjp0b2tlbg==")
+            .match_body(Matcher::Json(serde_json::json!({"version": 4})))
+            .with_body("{}")
+            .expect(1)
+            .create_async()
+            .await;
+        let stale = server
+            .mock("DELETE", "/api/post/13")
+            .match_body(Matcher::Json(serde_json::json!({"version": 1})))
+            .with_status(409)
+            .with_body(VERSION_CONFLICT_BODY)
+            .expect(1)
+            .create_async()
+            .await;
+        let missing = server
+            .mock("DELETE", "/api/post/14")
+            .with_status(404)
+            .with_body(
+                r#"{"name": "PostNotFoundError", "title": "Not found",
+                    "description": "Post 14 not found."}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        client
+            .request()
+            .delete_post(12, 4)
+            .await
+            .expect("Unable to delete post");
+        deleted.assert_async().await;
+
+        let result = client.request().delete_post(13, 1).await;
+        stale.assert_async().await;
+        assert_version_conflict(result);
+
+        let result = client.request().delete_post(14, 1).await;
+        missing.assert_async().await;
+        match result {
+            Err(SzurubooruClientError::SzurubooruServerError(e)) => {
+                assert_eq!(e.name, SzurubooruServerErrorType::PostNotFoundError);
+                assert_eq!(e.status, Some(StatusCode::NOT_FOUND));
+            }
+            other => panic!("Expected a post not found error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_post_relations() {
+        let mut server = Server::new_async().await;
+        let set = server
+            .mock("PUT", "/api/post/5")
+            .match_query(Matcher::UrlEncoded(
+                "fields".into(),
+                "id,version,relations".into(),
+            ))
+            .match_body(Matcher::Json(
+                serde_json::json!({"version": 3, "relations": [6, 9]}),
+            ))
+            .with_body(
+                r#"{"id": 5, "version": 4, "relations": [
+                    {"id": 6, "thumbnailUrl": "data/generated-thumbnails/6.jpg"},
+                    {"id": 9, "thumbnailUrl": "data/generated-thumbnails/9.jpg"}
+                ]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let cleared = server
+            .mock("PUT", "/api/post/5")
+            .match_query(Matcher::Missing)
+            .match_body(Matcher::Json(
+                serde_json::json!({"version": 4, "relations": []}),
+            ))
+            .with_body(r#"{"id": 5, "version": 5, "relations": []}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let post = client
+            .request()
+            .with_fields(vec!["id".to_string(), "version".to_string()])
+            .set_post_relations(5, 3, &[6, 9])
+            .await
+            .expect("Unable to set relations");
+        set.assert_async().await;
+        assert_eq!(post.version, Some(4));
+        let relations = post.relations.expect("No relations");
+        assert_eq!(
+            relations.iter().map(|rel| rel.id).collect::<Vec<_>>(),
+            vec![6, 9]
+        );
+        assert_eq!(
+            relations[0].thumbnail_url,
+            format!("{}/data/generated-thumbnails/6.jpg", server.url())
+        );
+
+        let post = client
+            .request()
+            .set_post_relations(5, 4, &[])
+            .await
+            .expect("Unable to clear relations");
+        cleared.assert_async().await;
+        assert_eq!(post.version, Some(5));
+        assert_eq!(post.relations, Some(vec![]));
+    }
+
+    #[tokio::test]
+    async fn test_create_update_and_delete_user() {
+        let mut server = Server::new_async().await;
+        let created = server
+            .mock("POST", "/api/users")
+            .match_body(Matcher::Json(serde_json::json!({
+                "name": "alice",
+                "password": "correct horse",
+                "email": "alice@example.com",
+                "rank": "regular",
+                "avatarStyle": "gravatar"
+            })))
+            .with_body(
+                r#"{"name": "alice", "version": 1, "rank": "regular", "avatarStyle": "gravatar",
+                    "avatarUrl": "https://gravatar.com/avatar/abc?d=retro&s=300"}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let promoted = server
+            .mock("PUT", "/api/user/alice")
+            .match_body(Matcher::Json(
+                serde_json::json!({"version": 1, "rank": "power"}),
+            ))
+            .with_body(r#"{"name": "alice", "version": 2, "rank": "power"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let stale = server
+            .mock("DELETE", "/api/user/alice")
+            .match_body(Matcher::Json(serde_json::json!({"version": 1})))
+            .with_status(409)
+            .with_body(VERSION_CONFLICT_BODY)
+            .expect(1)
+            .create_async()
+            .await;
+        let deleted = server
+            .mock("DELETE", "/api/user/alice")
+            .match_body(Matcher::Json(serde_json::json!({"version": 2})))
+            .with_body("{}")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let new_user = CreateUpdateUserBuilder::default()
+            .name("alice".to_string())
+            .password("correct horse".to_string())
+            .email("alice@example.com".to_string())
+            .rank(UserRank::Regular)
+            .avatar_style(UserAvatarStyle::Gravatar)
+            .build()
+            .unwrap();
+        let user = client
+            .request()
+            .create_user(&new_user)
+            .await
+            .expect("Unable to create user");
+        created.assert_async().await;
+        assert_eq!(user.name.as_deref(), Some("alice"));
+        assert_eq!(user.rank, Some(UserRank::Regular));
+
+        let promotion = CreateUpdateUserBuilder::default()
+            .version(user.version.unwrap())
+            .rank(UserRank::Power)
+            .build()
+            .unwrap();
+        let user = client
+            .request()
+            .update_user("alice", &promotion)
+            .await
+            .expect("Unable to update user");
+        promoted.assert_async().await;
+        assert_eq!(user.version, Some(2));
+        assert_eq!(user.rank, Some(UserRank::Power));
+
+        let result = client.request().delete_user("alice", 1).await;
+        stale.assert_async().await;
+        assert_version_conflict(result);
+        client
+            .request()
+            .delete_user("alice", 2)
+            .await
+            .expect("Unable to delete user");
+        deleted.assert_async().await;
+
+        // Caught before anything is sent
+        let manual = CreateUpdateUserBuilder::default()
+            .name("bob".to_string())
+            .password("hunter22".to_string())
+            .avatar_style(UserAvatarStyle::Manual)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            client.request().create_user(&manual).await,
+            Err(SzurubooruClientError::ValidationError(_))
+        ));
+        let unversioned = CreateUpdateUserBuilder::default()
+            .rank(UserRank::Restricted)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            client.request().update_user("alice", &unversioned).await,
+            Err(SzurubooruClientError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_and_rename_tag() {
+        let mut server = Server::new_async().await;
+        let created = server
+            .mock("POST", "/api/tags")
+            .match_body(Matcher::Json(serde_json::json!({
+                "names": ["kitty", "kitten"],
+                "category": "species",
+                "implications": ["cat"],
+            })))
+            .with_body(
+                r#"{"names": ["kitty", "kitten"], "category": "species", "version": 1,
+                    "implications": [{"names": ["cat"], "category": "species", "usages": 0}]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let renamed = server
+            .mock("PUT", "/api/tag/kitty")
+            .match_body(Matcher::Json(serde_json::json!({
+                "version": 1,
+                "names": ["kitten"],
+            })))
+            .with_body(r#"{"names": ["kitten"], "category": "species", "version": 2}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let new_tag = CreateUpdateTagBuilder::default()
+            .names(vec!["kitty".to_string(), "kitten".to_string()])
+            .category("species".to_string())
+            .implications(vec!["cat".to_string()])
+            .build()
+            .unwrap();
+        let tag = client
+            .request()
+            .create_tag(&new_tag)
+            .await
+            .expect("Unable to create tag");
+        created.assert_async().await;
+        assert_eq!(
+            tag.names,
+            Some(vec!["kitty".to_string(), "kitten".to_string()])
+        );
+        let implied = tag.implications.as_ref().unwrap();
+        assert_eq!(implied[0].names, vec!["cat".to_string()]);
+
+        let rename = CreateUpdateTagBuilder::default()
+            .version(tag.version)
+            .names(vec!["kitten".to_string()])
+            .build()
+            .unwrap();
+        let tag = client
+            .request()
+            .update_tag("kitty", &rename)
+            .await
+            .expect("Unable to rename tag");
+        renamed.assert_async().await;
+        assert_eq!(tag.names, Some(vec!["kitten".to_string()]));
+        assert_eq!(tag.version, 2);
+    }
+
+    #[tokio::test]
+    async fn test_featured_post() {
+        let mut server = Server::new_async().await;
+        let none_featured = server
+            .mock("GET", "/api/featured-post")
+            .with_body("null")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let featured = client
+            .request()
+            .get_featured_post()
+            .await
+            .expect("Unable to get featured post");
+        none_featured.assert_async().await;
+        assert!(featured.is_none());
+        none_featured.remove_async().await;
+
+        let empty = server
+            .mock("GET", "/api/featured-post")
+            .with_status(204)
+            .expect(1)
+            .create_async()
+            .await;
+        let featured = client
+            .request()
+            .get_featured_post()
+            .await
+            .expect("Unable to get featured post");
+        empty.assert_async().await;
+        assert!(featured.is_none());
+        empty.remove_async().await;
+
+        let feature = server
+            .mock("POST", "/api/featured-post")
+            .match_header("authorization", "Token dXNlcjp0b2tlbg==")
+            .match_body(Matcher::Json(serde_json::json!({"id": 42})))
+            .with_body(r#"{"id": 42, "version": 3, "contentUrl": "data/posts/42_abc.jpg"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let featured = server
+            .mock("GET", "/api/featured-post")
+            .with_body(r#"{"id": 42, "version": 3, "contentUrl": "data/posts/42_abc.jpg"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let post = client
+            .request()
+            .set_featured_post(42)
+            .await
+            .expect("Unable to feature post");
+        feature.assert_async().await;
+        assert_eq!(post.id, Some(42));
+        assert_eq!(
+            post.content_url,
+            Some(format!("{}/data/posts/42_abc.jpg", server.url()))
+        );
+
+        let current = client
+            .request()
+            .get_featured_post()
+            .await
+            .expect("Unable to get featured post")
+            .expect("No featured post");
+        featured.assert_async().await;
+        assert_eq!(current, post);
+    }
+
+    #[tokio::test]
+    async fn test_delete_tag() {
+        let mut server = Server::new_async().await;
+        let deleted = server
+            .mock("DELETE", "/api/tag/AC%2FDC%3F%20live%25")
+            .match_body(Matcher::Json(serde_json::json!({"version": 2})))
+            .with_body("{}")
+            .expect(1)
+            .create_async()
+            .await;
+        let missing = server
+            .mock("DELETE", "/api/tag/unknown")
+            .with_status(404)
+            .with_body(
+                r#"{"name": "TagNotFoundError", "title": "Not found",
+                    "description": "Tag unknown not found."}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let stale = server
+            .mock("DELETE", "/api/tag/cat")
+            .with_status(409)
+            .with_body(VERSION_CONFLICT_BODY)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        client
+            .request()
+            .delete_tag("AC/DC? live%", 2)
+            .await
+            .expect("Unable to delete tag");
+        deleted.assert_async().await;
+
+        let result = client.request().delete_tag("unknown", 1).await;
+        missing.assert_async().await;
+        match result {
+            Err(SzurubooruClientError::SzurubooruServerError(e)) => {
+                assert_eq!(e.name, SzurubooruServerErrorType::TagNotFoundError);
+                assert_eq!(e.status, Some(StatusCode::NOT_FOUND));
+            }
+            other => panic!("Expected a tag not found error, got {other:?}"),
+        }
+
+        let result = client.request().delete_tag("cat", 1).await;
+        stale.assert_async().await;
+        assert_version_conflict(result);
+    }
+
+    #[tokio::test]
+    async fn test_merge_tags() {
+        let mut server = Server::new_async().await;
+        let merged = server
+            .mock("POST", "/api/tag-merge")
+            .match_body(Matcher::Json(serde_json::json!({
+                "removeVersion": 1,
+                "remove": "kitty",
+                "mergeToVersion": 4,
+                "mergeTo": "cat",
+            })))
+            .with_body(r#"{"names": ["cat"], "version": 5, "usages": 12}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let missing = server
+            .mock("POST", "/api/tag-merge")
+            .match_body(Matcher::PartialJson(serde_json::json!({"remove": "nope"})))
+            .with_status(404)
+            .with_body(
+                r#"{"name": "TagNotFoundError", "title": "Not found",
+                    "description": "Tag 'nope' not found."}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let merge = MergeTagsBuilder::default()
+            .remove_tag("kitty".to_string())
+            .remove_tag_version(1)
+            .merge_to_tag("cat".to_string())
+            .merge_to_version(4)
+            .build()
+            .unwrap();
+        // The 5 usages of "kitty" are added to the 7 of "cat"
+        let tag = client
+            .request()
+            .merge_tags(&merge)
+            .await
+            .expect("Unable to merge tags");
+        merged.assert_async().await;
+        assert_eq!(tag.names, Some(vec!["cat".to_string()]));
+        assert_eq!(tag.usages, Some(12));
+
+        let merge = MergeTagsBuilder::default()
+            .remove_tag("nope".to_string())
+            .remove_tag_version(1)
+            .merge_to_tag("cat".to_string())
+            .merge_to_version(5)
+            .build()
+            .unwrap();
+        let result = client.request().merge_tags(&merge).await;
+        missing.assert_async().await;
+        match result {
+            Err(SzurubooruClientError::SzurubooruServerError(e)) => {
+                assert_eq!(e.name, SzurubooruServerErrorType::TagNotFoundError);
+            }
+            other => panic!("Expected a missing tag error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_comment() {
+        let mut server = Server::new_async().await;
+        let updated = server
+            .mock("PUT", "/api/comment/12")
+            .match_body(Matcher::Json(
+                serde_json::json!({"version": 2, "text": "Edited"}),
+            ))
+            .with_body(r#"{"id": 12, "version": 3, "text": "Edited"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let conflict = server
+            .mock("PUT", "/api/comment/12")
+            .match_body(Matcher::Json(
+                serde_json::json!({"version": 1, "text": "Stale"}),
+            ))
+            .with_status(409)
+            .with_body(VERSION_CONFLICT_BODY)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let update = CreateUpdateCommentBuilder::default()
+            .version(2)
+            .text("Edited".to_string())
+            .build()
+            .unwrap();
+        let comment = client
+            .request()
+            .update_comment(12, &update)
+            .await
+            .expect("Unable to update comment");
+        updated.assert_async().await;
+        assert_eq!(comment.version, Some(3));
+        assert_eq!(comment.text.as_deref(), Some("Edited"));
+
+        let stale = CreateUpdateCommentBuilder::default()
+            .version(1)
+            .text("Stale".to_string())
+            .build()
+            .unwrap();
+        let result = client.request().update_comment(12, &stale).await;
+        conflict.assert_async().await;
+        assert_version_conflict(result);
+    }
+
+    #[tokio::test]
+    async fn test_rate_post() {
+        let mut server = Server::new_async().await;
+        let client = client_for(&server);
+        for (rating, score) in [
+            (PostRating::Up, 1),
+            (PostRating::Neutral, 0),
+            (PostRating::Down, -1),
+        ] {
+            let rated = server
+                .mock("PUT", "/api/post/5/score")
+                .match_body(Matcher::Json(serde_json::json!({ "score": score })))
+                .with_body(format!(r#"{{"id": 5, "ownScore": {score}}}"#))
+                .expect(1)
+                .create_async()
+                .await;
+            let post = client
+                .request()
+                .rate_post(5, rating)
+                .await
+                .expect("Unable to rate post");
+            rated.assert_async().await;
+            assert_eq!(post.own_score, Some(score));
+            assert_eq!(PostRating::try_from(score as i8).unwrap(), rating);
+            rated.remove_async().await;
+        }
+        assert!(matches!(
+            PostRating::try_from(2),
+            Err(SzurubooruClientError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_favorite_post_requires_auth() {
+        let mut server = Server::new_async().await;
+        let never_favorited = server
+            .mock("POST", Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+        let never_unfavorited = server
+            .mock("DELETE", Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+
+        let client = SzurubooruClient::new_anonymous(&server.url(), false).unwrap();
+        for result in [
+            client.request().favorite_post(5).await,
+            client.request().unfavorite_post(5).await,
+        ] {
+            match result {
+                Err(SzurubooruClientError::ValidationError(message)) => {
+                    assert!(message.ends_with("requires an authenticated client"))
+                }
+                other => panic!("Expected a validation error, got {other:?}"),
+            }
+        }
+        never_favorited.assert_async().await;
+        never_unfavorited.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_get_post() {
+        let mut server = Server::new_async().await;
+        let found = server
+            .mock("GET", "/api/post/7")
+            .with_body(r#"{"id": 7, "version": 2, "safety": "safe"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let missing = server
+            .mock("GET", "/api/post/8")
+            .with_status(404)
+            .with_body(
+                r#"{"name": "PostNotFoundError", "title": "Not Found",
+                    "description": "Post 8 not found."}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let post = client
+            .request()
+            .get_post(7)
+            .await
+            .expect("Unable to get post");
+        found.assert_async().await;
+        assert_eq!(post.id, Some(7));
+        assert_eq!(post.version, Some(2));
+
+        let result = client.request().get_post(8).await;
+        missing.assert_async().await;
+        match result {
+            Err(SzurubooruClientError::SzurubooruServerError(e)) => {
+                assert_eq!(e.name, SzurubooruServerErrorType::PostNotFoundError);
+                assert_eq!(e.status, Some(StatusCode::NOT_FOUND));
+            }
+            other => panic!("Expected a post not found error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_top_scored_posts() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("query".into(), "sort:score safety:safe".into()),
+                Matcher::UrlEncoded("limit".into(), "5".into()),
+            ]))
+            .with_body(
+                r#"{"query": "sort:score safety:safe", "offset": 0, "limit": 5, "total": 2,
+                    "results": [{"id": 9, "score": 12}, {"id": 3, "score": 7}]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let safe = vec![QueryToken::token(PostNamedToken::Safety, PostSafety::Safe)];
+        let posts = client
+            .request()
+            .top_scored_posts(5, Some(&safe))
+            .await
+            .expect("Unable to list top scored posts");
+        mock.assert_async().await;
+        assert_eq!(
+            posts.iter().map(|p| p.id).collect::<Vec<_>>(),
+            vec![Some(9), Some(3)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_top_comments() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/comments")
+            .match_query(Matcher::UrlEncoded("query".into(), "post:42".into()))
+            .with_body(
+                r#"{"query": "post:42", "offset": 0, "limit": 100, "total": 4, "results": [
+                    {"id": 1, "score": 2}, {"id": 2, "score": -1},
+                    {"id": 3, "score": 5}, {"id": 4, "score": 2}]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let query = vec![QueryToken::token(CommentNamedToken::Post, "42")];
+        let comments = client
+            .request()
+            .top_comments(3, Some(&query))
+            .await
+            .expect("Unable to list top comments");
+        mock.assert_async().await;
+        assert_eq!(
+            comments.iter().map(|c| c.id).collect::<Vec<_>>(),
+            vec![Some(3), Some(1), Some(4)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_errors() {
+        let mut server = Server::new_async().await;
+        let unavailable = server
+            .mock("GET", "/api/tag-categories")
+            .with_status(503)
+            .with_body("Service Unavailable")
+            .expect(1)
+            .create_async()
+            .await;
+        let rate_limited = server
+            .mock("GET", "/api/tag-categories")
+            .with_status(429)
+            .with_header("Retry-After", "1")
+            .with_body("Too Many Requests")
+            .expect(1)
+            .create_async()
+            .await;
+        let success = server
+            .mock("GET", "/api/tag-categories")
+            .with_body(r#"{"results": []}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server).with_retry(RetryPolicy {
+            base_delay: Duration::from_millis(1),
+            ..Default::default()
+        });
+        let start = Instant::now();
+        client
+            .request()
+            .list_tag_categories()
+            .await
+            .expect("Request should succeed after retrying");
+        unavailable.assert_async().await;
+        rate_limited.assert_async().await;
+        success.assert_async().await;
+        assert!(
+            start.elapsed() >= Duration::from_secs(1),
+            "Retry-After was ignored"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up() {
+        let mut server = Server::new_async().await;
+        let get_mock = server
+            .mock("GET", "/api/info")
+            .with_status(502)
+            .with_body("Bad Gateway")
+            .expect(2)
+            .create_async()
+            .await;
+        let post_mock = server
+            .mock("POST", "/api/tag-categories")
+            .with_status(503)
+            .with_body("Service Unavailable")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server).with_retry(RetryPolicy {
+            max_attempts: 2,
+            base_delay: Duration::from_millis(1),
+            ..Default::default()
+        });
+        let result = client.request().get_global_info().await;
+        assert!(
+            matches!(
+                result,
+                Err(SzurubooruClientError::ResponseError(
+                    StatusCode::BAD_GATEWAY,
+                    _
+                ))
+            ),
+            "{result:?}"
+        );
+        let new_category = CreateUpdateTagCategoryBuilder::default()
+            .name("new-category".to_string())
+            .color("blue".to_string())
+            .build()
+            .unwrap();
+        let result = client.request().create_tag_category(&new_category).await;
+        assert!(result.is_err());
+        get_mock.assert_async().await;
+        post_mock.assert_async().await;
+    }
+
+    #[test]
+    fn test_base_url() {
+        let cases = [
+            ("http://localhost:5001", "http://localhost:5001/"),
+            ("http://localhost:5001/", "http://localhost:5001/"),
+            ("https://example.com/booru/", "https://example.com/booru"),
+            ("https://example.com/booru", "https://example.com/booru"),
+            ("https://example.com/booru//", "https://example.com/booru"),
+            ("https://example.com/#top", "https://example.com/"),
+        ];
+        for (host, expected) in cases {
+            let client = SzurubooruClient::new_with_token(host, "user", "token", false)
+                .expect("Unable to create client");
+            assert_eq!(client.base_url().as_str(), expected);
+        }
+
+        let client = SzurubooruClient::with_reqwest_client(
+            Client::new(),
+            "http://localhost:5001/",
+            SzurubooruAuth::None,
+        )
+        .expect("Unable to create client");
+        assert_eq!(
+            client.base_url(),
+            &Url::parse("http://localhost:5001").unwrap()
+        );
+
+        for host in [
+            "not a url",
+            "localhost:5001",
+            "ftp://example.com",
+            "http://",
+            "",
+        ] {
+            let result = SzurubooruClient::new_anonymous(host, false);
+            assert!(
+                matches!(result, Err(SzurubooruClientError::InvalidBaseUrl { ref url, .. }) if url == host),
+                "{host} should be rejected"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_with_reqwest_client() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/tag-categories")
+            .match_header("x-app-name", "my-app")
+            .match_header("authorization", "Token dXNlcjp0b2tlbg==")
+            .match_header("content-type", "application/json")
+            .with_body(r#"{"version": 1, "name": "new-category", "color": "blue", "usages": 0, "order": 1, "default": false}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-app-name", "my-app".parse().unwrap());
+        let http = ClientBuilder::new()
+            .default_headers(headers)
+            .build()
+            .unwrap();
+        let client = SzurubooruClient::with_reqwest_client(
+            http,
+            &server.url(),
+            SzurubooruAuth::token("user", "token"),
+        )
+        .expect("Unable to create client");
+        let new_category = CreateUpdateTagCategoryBuilder::default()
+            .name("new-category".to_string())
+            .color("blue".to_string())
+            .build()
+            .unwrap();
+        client
+            .request()
+            .create_tag_category(&new_category)
+            .await
+            .expect("Unable to create tag category");
+        mock.assert_async().await;
+
+        // The crate's own timeout and retries are left to the supplied client
+        let slow = server
+            .mock("GET", "/api/info")
+            .with_status(503)
+            .with_chunked_body(|w| {
+                std::thread::sleep(Duration::from_millis(300));
+                w.write_all(
+                    br#"{"name": "ServiceUnavailable", "title": "Unavailable",
+                    "description": "Try again later"}"#,
+                )
+            })
+            .expect(1)
+            .create_async()
+            .await;
+        let client = SzurubooruClient::with_reqwest_client(
+            Client::new(),
+            &server.url(),
+            SzurubooruAuth::None,
+        )
+        .expect("Unable to create client")
+        .with_timeout(Duration::from_millis(50))
+        .with_retry(RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            ..Default::default()
+        });
+        match client.request().get_global_info().await {
+            Err(SzurubooruClientError::ResponseError(status, _)) => {
+                assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE)
+            }
+            other => panic!("Expected the server's error, got {other:?}"),
+        }
+        slow.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_list_posts_with_fields() {
+        let mut server = Server::new_async().await;
+        let listed = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::UrlEncoded(
+                "fields".into(),
+                "id,tags,version".into(),
+            ))
+            .with_body(
+                r#"{"query": "", "offset": 0, "limit": 100, "total": 1, "results": [
+                    {"id": 4, "version": 1,
+                     "tags": [{"names": ["cat"], "category": "default", "usages": 3}]}]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let page = client
+            .with_fields(vec!["id".to_string(), "tags".to_string()])
+            .list_posts(None)
+            .await
+            .expect("Unable to list posts");
+        listed.assert_async().await;
+        let post = &page.results[0];
+        assert_eq!(post.id, Some(4));
+        assert_eq!(
+            post.tags.as_ref().unwrap()[0].names,
+            vec!["cat".to_string()]
+        );
+        assert_eq!(post.safety, None);
+        assert_eq!(post.content_url, None);
+        assert!(post.user.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_listed_posts_are_updatable() {
+        let mut server = Server::new_async().await;
+        let listed = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::UrlEncoded(
+                "fields".into(),
+                "id,tags,version".into(),
+            ))
+            .with_body(
+                r#"{"query": "", "offset": 0, "limit": 100, "total": 1,
+                    "results": [{"id": 4, "version": 3, "tags": []}]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let updated = server
+            .mock("PUT", "/api/post/4")
+            .match_body(Matcher::Json(
+                serde_json::json!({"version": 3, "tags": ["cat"]}),
+            ))
+            .with_body(r#"{"id": 4, "version": 4}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let request = client.with_fields(vec!["id".to_string(), "tags".to_string()]);
+        let page = request
+            .list_posts(None)
+            .await
+            .expect("Unable to list posts");
+        listed.assert_async().await;
+        let post = &page.results[0];
+        assert_eq!(post.version, Some(3));
+
+        let update = CreateUpdatePostBuilder::default()
+            .version(post.version.unwrap())
+            .tags(vec!["cat".to_string()])
+            .build()
+            .unwrap();
+        let post = client
+            .request()
+            .update_post(post.id.unwrap(), &update)
+            .await
+            .expect("Unable to update post");
+        updated.assert_async().await;
+        assert_eq!(post.version, Some(4));
+    }
+
+    #[tokio::test]
+    async fn test_list_builders() {
+        let mut server = Server::new_async().await;
+        let posts = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("query".into(), "cat sort:score".into()),
+                Matcher::UrlEncoded("offset".into(), "20".into()),
+                Matcher::UrlEncoded("limit".into(), "10".into()),
+                Matcher::UrlEncoded("fields".into(), "id,tags,version".into()),
+            ]))
+            .with_body(
+                r#"{"query": "cat sort:score", "offset": 20, "limit": 10, "total": 21,
+                    "results": [{"id": 1}]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let tags = server
+            .mock("GET", "/api/tags")
+            .match_query(Matcher::Exact("limit=5&offset=5".into()))
+            .with_body(r#"{"query": "", "offset": 5, "limit": 5, "total": 5, "results": []}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let page = client
+            .request()
+            .list_posts_builder()
+            .tokens([QueryToken::anonymous("cat")])
+            .token(QueryToken::sort_typed(PostSortToken::Score))
+            .offset(20)
+            .limit(10)
+            .fields(&["id", "tags"])
+            .await
+            .expect("Unable to list posts");
+        posts.assert_async().await;
+        assert_eq!((page.offset, page.limit, page.total), (20, 10, 21));
+        assert_eq!(page.results[0].id, Some(1));
+
+        let page = client
+            .request()
+            .list_tags_builder()
+            .offset(5)
+            .limit(5)
+            .send()
+            .await
+            .expect("Unable to list tags");
+        tags.assert_async().await;
+        assert!(page.results.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_add_and_remove_post_tags() {
+        let mut server = Server::new_async().await;
+        let tags = r#"{"id": 5, "version": 3, "tags": [
+            {"names": ["cat", "neko"], "category": "default", "usages": 4},
+            {"names": ["sky"], "category": "default", "usages": 1}
+        ]}"#;
+        let fetch = server
+            .mock("GET", "/api/post/5")
+            .match_query(Matcher::Missing)
+            .with_body(tags)
+            .expect(3)
+            .create_async()
+            .await;
+        let fetch_fields = server
+            .mock("GET", "/api/post/5")
+            .match_query(Matcher::UrlEncoded(
+                "fields".into(),
+                "id,tags,version".into(),
+            ))
+            .with_body(tags)
+            .expect(1)
+            .create_async()
+            .await;
+        let added = server
+            .mock("PUT", "/api/post/5")
+            .match_body(Matcher::Json(serde_json::json!({
+                "version": 3,
+                "tags": ["cat", "sky", "tabby", "kitten"]
+            })))
+            .with_body(r#"{"id": 5, "version": 4}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let removed = server
+            .mock("PUT", "/api/post/5")
+            .match_body(Matcher::Json(
+                serde_json::json!({"version": 4, "tags": ["sky"]}),
+            ))
+            .with_body(r#"{"id": 5, "version": 5}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let conflict = server
+            .mock("PUT", "/api/post/5")
+            .match_body(Matcher::PartialJson(serde_json::json!({"version": 3})))
+            .with_status(409)
+            .with_body(VERSION_CONFLICT_BODY)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let post = client
+            .request()
+            .add_post_tags(5, 3, &["tabby", "neko", "kitten"])
+            .await
+            .expect("Unable to add tags");
+        added.assert_async().await;
+        assert_eq!(post.version, Some(4));
+        added.remove_async().await;
+
+        let post = client
+            .request()
+            .remove_post_tags(5, 4, &["neko"])
+            .await
+            .expect("Unable to remove tags");
+        removed.assert_async().await;
+        assert_eq!(post.version, Some(5));
+
+        let result = client.request().remove_post_tags(5, 3, &["sky"]).await;
+        fetch.assert_async().await;
+        conflict.assert_async().await;
+        assert_version_conflict(result);
+
+        // Nothing to add, so the fetched post is returned without an update
+        let post = client
+            .with_fields(vec!["id".to_string()])
+            .add_post_tags(5, 3, &["neko"])
+            .await
+            .expect("Unable to add tags");
+        fetch_fields.assert_async().await;
+        conflict.assert_async().await;
+        assert_eq!(post.version, Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_get_posts_by_ids() {
+        let mut server = Server::new_async().await;
+        let combined = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::UrlEncoded("query".into(), "id:1,2,3".into()))
+            .with_body(
+                r#"{"query": "id:1,2,3", "offset": 0, "limit": 3, "total": 2,
+                    "results": [{"id": 3, "version": 1}, {"id": 1, "version": 4}]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let posts = client
+            .request()
+            .get_posts_by_ids(&[3, 1, 2, 3])
+            .await
+            .expect("Unable to get posts");
+        combined.assert_async().await;
+        assert_eq!(posts.len(), 2);
+        assert_eq!(posts[&1].version, Some(4));
+        assert_eq!(posts[&3].version, Some(1));
+        combined.remove_async().await;
+
+        let rejected = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::Any)
+            .with_status(400)
+            .with_body(
+                r#"{"name": "SearchError", "title": "Search error",
+                    "description": "Unknown named token: id"}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let first = server
+            .mock("GET", "/api/post/1")
+            .with_body(r#"{"id": 1, "version": 4}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let missing = server
+            .mock("GET", "/api/post/2")
+            .with_status(404)
+            .with_body(
+                r#"{"name": "PostNotFoundError", "title": "Not Found",
+                    "description": "Post 2 not found."}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let posts = client
+            .request()
+            .get_posts_by_ids(&[1, 2])
+            .await
+            .expect("Unable to get posts individually");
+        rejected.assert_async().await;
+        first.assert_async().await;
+        missing.assert_async().await;
+        assert_eq!(posts.keys().collect::<Vec<_>>(), vec![&1]);
+    }
+
+    #[tokio::test]
+    async fn test_max_search_terms() {
+        let mut server = Server::new_async().await;
+        let search = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::Any)
+            .with_body(r#"{"query": "", "offset": 0, "limit": 100, "total": 0, "results": []}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let query = (0..4)
+            .map(|i| QueryToken::anonymous(format!("tag{i}")))
+            .chain([QueryToken::anonymous("")])
+            .collect::<Vec<_>>();
+        let client = client_for(&server).with_max_search_terms(Some(3));
+        let result = client.request().list_posts(Some(&query)).await;
+        assert!(matches!(
+            result,
+            Err(SzurubooruClientError::TooManySearchTerms { terms: 4, max: 3 })
+        ));
+
+        let client = client.with_max_search_terms(None);
+        client
+            .request()
+            .list_posts(Some(&query))
+            .await
+            .expect("Unable to search without a term limit");
+        search.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_base_url_subpath() {
+        for host in ["https://example.com/booru/", "https://example.com/booru"] {
+            let client = SzurubooruClient::new_anonymous(host, false).unwrap();
+            let request = client.request();
+            assert_eq!(
+                request.build_url("/api/posts", None).as_str(),
+                "https://example.com/booru/api/posts"
+            );
+            assert_eq!(
+                request.build_url("/api/tag/re:zero", None).as_str(),
+                "https://example.com/booru/api/tag/re:zero"
+            );
+        }
+        let client = SzurubooruClient::new_anonymous("https://example.com", false).unwrap();
+        assert_eq!(
+            client.request().build_url("/api/posts", None).as_str(),
+            "https://example.com/api/posts"
+        );
+        let query = vec![
+            QueryToken::anonymous("blue sky"),
+            QueryToken::token("source", "http://example.com"),
+        ];
+        assert_eq!(
+            client
+                .request()
+                .build_url("/api/posts", Some(&query))
+                .query(),
+            Some(format!("query={}", query.to_encoded_query_string()).as_str())
+        );
+
+        let mut server = Server::new_async().await;
+        let posts = server
+            .mock("GET", "/booru/api/posts")
+            .with_body(r#"{"query": "", "offset": 0, "limit": 100, "total": 0, "results": []}"#)
+            .create_async()
+            .await;
+        let post = server
+            .mock("GET", "/booru/api/post/4")
+            .with_body(r#"{"id": 4, "contentUrl": "data/posts/4_abc.png"}"#)
+            .create_async()
+            .await;
+        let tag = server
+            .mock("GET", "/booru/api/tag/cat")
+            .with_body(r#"{"names": ["cat"], "version": 1}"#)
+            .create_async()
+            .await;
+        let client = SzurubooruClient::new_anonymous(&format!("{}/booru/", server.url()), false)
+            .expect("Unable to create client");
+        client
+            .request()
+            .list_posts(None)
+            .await
+            .expect("Unable to list posts");
+        client
+            .request()
+            .get_tag("cat")
+            .await
+            .expect("Unable to get tag");
+        let content_url = client
+            .request()
+            .get_post(4)
+            .await
+            .expect("Unable to get post")
+            .content_url;
+        posts.assert_async().await;
+        tag.assert_async().await;
+        post.assert_async().await;
+        assert_eq!(
+            content_url,
+            Some(format!("{}/booru/data/posts/4_abc.png", server.url()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_saved_searches() {
+        let mut server = Server::new_async().await;
+        let search = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("query".into(), "cat sort:score".into()),
+                Matcher::UrlEncoded("limit".into(), "5".into()),
+            ]))
+            .with_body(r#"{"query": "cat sort:score", "offset": 0, "limit": 5, "total": 1, "results": [{"id": 3, "version": 1}]}"#)
+            .create_async()
+            .await;
+
+        let mut searches = SavedSearches::default();
+        let query = vec![
+            QueryToken::anonymous("cat"),
+            QueryToken::sort(PostSortToken::Score),
+        ];
+        assert_eq!(searches.register("best cats", query.clone()), None);
+        assert_eq!(searches.get("best cats"), Some(&query));
+        assert_eq!(searches.names().collect::<Vec<_>>(), vec!["best cats"]);
+
+        let client = client_for(&server);
+        let request = client.request().with_limit(5);
+        let posts = searches
+            .run(&request, "best cats")
+            .await
+            .expect("Unable to run saved search");
+        search.assert_async().await;
+        assert_eq!(posts.total, 1);
+        assert_eq!(posts.results[0].id, Some(3));
+
+        let missing = searches.run(&request, "best dogs").await;
+        assert!(matches!(
+            missing,
+            Err(SzurubooruClientError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_download_post_content() {
+        let mut server = Server::new_async().await;
+        let content = server
+            .mock("GET", "/data/posts/4_abc.png")
+            .match_header("authorization", "Token dXNlcjp0b2tlbg==")
+            .with_body("tiny png")
+            .expect(3)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let dest = std::env::temp_dir().join(format!("szurubooru-{}-4.png", std::process::id()));
+        let post_with_url = |url: String| -> PostResource {
+            serde_json::from_value(serde_json::json!({ "contentUrl": url })).unwrap()
+        };
+        let relative = post_with_url("data/posts/4_abc.png".to_string());
+        let absolute = post_with_url(format!("{}/data/posts/4_abc.png", server.url()));
+        for post in [&relative, &absolute] {
+            let written = client
+                .request()
+                .download_post_content(post, &dest)
+                .await
+                .expect("Unable to download post content");
+            assert_eq!(written, 8);
+            assert_eq!(std::fs::read(&dest).unwrap(), b"tiny png");
+        }
+        std::fs::remove_file(&dest).unwrap();
+
+        let bytes = client
+            .request()
+            .download_post_content_bytes(&relative)
+            .await
+            .expect("Unable to download post content");
+        content.assert_async().await;
+        assert_eq!(bytes.as_ref(), b"tiny png");
+
+        let no_url: PostResource = serde_json::from_str(r#"{"id": 4}"#).unwrap();
+        let result = client.request().download_post_content_bytes(&no_url).await;
+        assert!(matches!(
+            result,
+            Err(SzurubooruClientError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_download_thumbnail_to_path() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/api/post/4")
+            .with_body(r#"{"id": 4, "thumbnailUrl": "data/generated-thumbnails/4_abc.jpg"}"#)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/data/generated-thumbnails/4_abc.jpg")
+            .with_body("thumb")
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let dest =
+            std::env::temp_dir().join(format!("szurubooru-{}-4-thumb.jpg", std::process::id()));
+        std::fs::write(&dest, b"an older, longer thumbnail").unwrap();
+        // Once over an existing file, once creating it
+        for _ in 0..2 {
+            client
+                .request()
+                .download_thumbnail_to_path(4, &dest)
+                .await
+                .expect("Unable to download thumbnail");
+            assert_eq!(std::fs::read(&dest).unwrap(), b"thumb");
+            std::fs::remove_file(&dest).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_response_cache_invalidation() {
+        let mut server = Server::new_async().await;
+        let fetched = server
+            .mock("GET", "/api/post/4")
+            .with_body(r#"{"id": 4, "version": 1}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        let updated = server
+            .mock("PUT", "/api/post/4")
+            .with_body(r#"{"id": 4, "version": 2}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server).with_response_cache(Duration::from_secs(60));
+        for _ in 0..2 {
+            let post = client.request().get_post(4).await.unwrap();
+            assert_eq!(post.version, Some(1));
+        }
+
+        let update = CreateUpdatePostBuilder::default()
+            .version(1)
+            .tags(vec!["cat".to_string()])
+            .build()
+            .unwrap();
+        client.request().update_post(4, &update).await.unwrap();
+        updated.assert_async().await;
+
+        let post = client.request().get_post(4).await.unwrap();
+        // Only the first and last fetches reach the server
+        fetched.assert_async().await;
+        assert_eq!(post.id, Some(4));
+    }
+
+    #[tokio::test]
+    async fn test_block_unsafe() {
+        let mut server = Server::new_async().await;
+        let search = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::UrlEncoded(
+                "query".into(),
+                "cat safety:safe -safety:unsafe".into(),
+            ))
+            .with_body(
+                r#"{"query": "", "offset": 0, "limit": 100, "total": 2, "results": [
+                    {"id": 2, "safety": "unsafe"}, {"id": 1, "safety": "safe"}]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server).with_block_unsafe(true);
+        let query = vec![
+            QueryToken::anonymous("cat"),
+            QueryToken::token(PostNamedToken::Safety, "safe,unsafe"),
+        ];
+        let page = client
+            .request()
+            .list_posts(Some(&query))
+            .await
+            .expect("Unable to list posts");
+        search.assert_async().await;
+        let ids = page.results.iter().map(|p| p.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![Some(1)]);
+        assert_eq!(
+            without_unsafe(None),
+            Some(vec![
+                QueryToken::token(PostNamedToken::Safety, "unsafe").negate()
+            ])
+        );
+
+        // Asking only for unsafe posts can't match anything, so no request is sent
+        let query = vec![
+            QueryToken::anonymous("cat"),
+            QueryToken::token(PostNamedToken::Rating, "unsafe"),
+        ];
+        assert_eq!(without_unsafe(Some(&query)), None);
+        let page = client
+            .request()
+            .with_limit(20)
+            .list_posts(Some(&query))
+            .await
+            .expect("Unable to list posts");
+        assert_eq!((page.total, page.limit), (0, 20));
+        assert!(page.results.is_empty());
+        let raw = client
+            .request()
+            .list_posts_raw(Some(&query))
+            .collect::<Vec<_>>()
+            .await;
+        assert!(raw.is_empty());
+        search.assert_async().await;
+
+        // Streams page by what the server sent, so a page with only unsafe posts doesn't end
+        // the stream or shift the next page's offset
+        let mut pages = Vec::new();
+        for (offset, results) in [
+            (
+                "0",
+                r#"{"id": 5, "safety": "unsafe"}, {"id": 4, "safety": "unsafe"}"#,
+            ),
+            ("2", r#"{"id": 3, "safety": "safe"}"#),
+        ] {
+            let page = server
+                .mock("GET", "/api/posts")
+                .match_query(Matcher::AllOf(vec![
+                    Matcher::UrlEncoded("query".into(), "-safety:unsafe".into()),
+                    Matcher::UrlEncoded("offset".into(), offset.into()),
+                ]))
+                .with_body(format!(
+                    r#"{{"query": "", "offset": {offset}, "limit": 2, "total": 3,
+                        "results": [{results}]}}"#
+                ))
+                .expect(2)
+                .create_async()
+                .await;
+            pages.push(page);
+        }
+        let request = client.request().with_limit(2);
+        let posts = request
+            .stream_posts(None)
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Unable to stream posts");
+        assert_eq!(
+            posts.iter().map(|p| p.id).collect::<Vec<_>>(),
+            vec![Some(3)]
+        );
+        let raw = request
+            .list_posts_raw(None)
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Unable to stream raw posts");
+        assert_eq!(
+            raw.iter().map(|p| p["id"].as_u64()).collect::<Vec<_>>(),
+            vec![Some(3)]
+        );
+        for page in pages {
+            page.assert_async().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_strict_deserialization() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/api/tag-category/meta")
+            .with_body(
+                r#"{"version": 1, "name": "meta", "color": "red", "usages": 2, "order": 1,
+                    "default": false, "description": "added in a newer server"}"#,
+            )
+            .expect(2)
+            .create_async()
+            .await;
+
+        let lenient = client_for(&server);
+        let category = lenient
+            .request()
+            .get_tag_category("meta")
+            .await
+            .expect("Lenient mode should ignore unknown fields");
+        assert_eq!(category.name.as_deref(), Some("meta"));
+
+        let strict = client_for(&server).with_strict_deserialization(true);
+        match strict.request().get_tag_category("meta").await {
+            Err(SzurubooruClientError::ResponseParsingError(e, _)) => {
+                assert!(e.to_string().contains("description"), "{e}")
+            }
+            other => panic!("Expected an unknown field error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_raw_json() {
+        #[derive(Debug, serde::Deserialize, PartialEq)]
+        struct Custom {
+            answer: u32,
+            #[serde(rename = "echoedName")]
+            echoed_name: String,
+        }
+
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/custom-plugin/thing")
+            .match_query(Matcher::UrlEncoded("fields".into(), "answer".into()))
+            .match_header("authorization", "Token dXNlcjp0b2tlbg==")
+            .match_body(Matcher::Json(serde_json::json!({"name": "thing"})))
+            .with_body(r#"{"answer": 42, "echoedName": "thing"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let custom: Custom = client
+            .with_fields(vec!["answer".to_string()])
+            .raw_json(
+                Method::POST,
+                "/api/custom-plugin/thing",
+                Some(serde_json::json!({"name": "thing"})),
+            )
+            .await
+            .expect("Unable to call custom endpoint");
+        mock.assert_async().await;
+        assert_eq!(
+            custom,
+            Custom {
+                answer: 42,
+                echoed_name: "thing".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_untagged_posts() {
+        use futures_util::StreamExt;
+
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/posts")
+            .match_query(Matcher::UrlEncoded(
+                "query".into(),
+                "tag-count:0 sort:creation-date".into(),
+            ))
+            .with_body(
+                r#"{"query": "", "offset": 0, "limit": 100, "total": 2,
+                    "results": [{"id": 9, "tagCount": 0}, {"id": 4, "tagCount": 0}]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let request = client.request();
+        let ids: Vec<Option<u32>> = request
+            .untagged_posts()
+            .map(|post| post.expect("Unable to fetch untagged posts").id)
+            .collect()
+            .await;
+        mock.assert_async().await;
+        assert_eq!(ids, vec![Some(9), Some(4)]);
+    }
+
+    #[tokio::test]
+    async fn test_get_tags() {
+        let mut server = Server::new_async().await;
+        let search = server
+            .mock("GET", "/api/tags")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("query".into(), r"name:cat,maine-coon,b\,w,a\*".into()),
+                Matcher::UrlEncoded("limit".into(), "4".into()),
+            ]))
+            .with_body(
+                r#"{"query": "", "offset": 0, "limit": 4, "total": 4, "results": [
+                    {"version": 1, "names": ["cat"], "category": "species"},
+                    {"version": 1, "names": ["maine_coon", "maine-coon"], "category": "species"},
+                    {"version": 1, "names": ["b,w"], "category": "meta"},
+                    {"version": 1, "names": ["a*"], "category": "meta"}
+                ]}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let tags = client
+            .request()
+            .get_tags(&["cat", "maine-coon", "b,w", "a*"])
+            .await
+            .expect("Unable to resolve tags");
+        search.assert_async().await;
+        assert_eq!(tags.len(), 4);
+        assert_eq!(tags["cat"].category, Some("species".to_string()));
+        assert_eq!(
+            tags["maine-coon"].names,
+            Some(vec!["maine_coon".to_string(), "maine-coon".to_string()])
+        );
+        assert_eq!(tags["b,w"].category, Some("meta".to_string()));
+        assert_eq!(tags["a*"].names, Some(vec!["a*".to_string()]));
+    }
+
+    #[test]
+    fn test_chunk_or_values() {
+        // "aaa%2Cbbb" is 9 long, "%5C-cc" is 6 long
+        let chunks = chunk_or_values(&["aaa", "bbb", "-cc", "dd"], 11, 100);
+        assert_eq!(
+            chunks,
+            vec![
+                vec!["aaa".to_string(), "bbb".to_string()],
+                vec![r"\-cc".to_string(), "dd".to_string()],
+            ]
+        );
+
+        let chunks = chunk_or_values(&["a", "b", "c"], 100, 2);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunk_or_values(&["toolongname"], 4, 100).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_tags_splits_long_urls() {
+        let mut server = Server::new_async().await;
+        let names: Vec<String> = (0..40).map(|i| format!("tag_number_{i:02}")).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let mut mocks = Vec::new();
+        for chunk in names.chunks(20) {
+            let results: Vec<String> = chunk
+                .iter()
+                .map(|name| format!(r#"{{"version": 1, "names": ["{name}"]}}"#))
+                .collect();
+            let mock = server
+                .mock("GET", "/api/tags")
+                .match_query(Matcher::UrlEncoded(
+                    "query".into(),
+                    format!("name:{}", chunk.join(",")),
+                ))
+                .with_body(format!(
+                    r#"{{"query": "", "offset": 0, "limit": 20, "total": 20, "results": [{}]}}"#,
+                    results.join(",")
+                ))
+                .expect(1)
+                .create_async()
+                .await;
+            mocks.push(mock);
+        }
+
+        // Room for exactly 20 names: each is 13 long, plus 3 for every comma between them
+        let client = client_for(&server);
+        let placeholder = vec![QueryToken::token("name", "_")];
+        let base_length = client
+            .request()
+            .with_offset(0)
+            .with_limit(100)
+            .build_url("/api/tags", Some(&placeholder))
+            .as_str()
+            .len()
+            - 1;
+        let client = client.with_max_url_length(base_length + 20 * 13 + 19 * 3);
+        let tags = client
+            .request()
+            .get_tags(&names)
+            .await
+            .expect("Unable to resolve tags");
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+        assert_eq!(tags.len(), 40);
+    }
+
+    #[tokio::test]
+    async fn test_create_post_surfaces_server_tags() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("POST", "/api/posts")
+            .match_body(Matcher::PartialJsonString(
+                r#"{"tags": ["maine_coon", "Tabby"]}"#.to_string(),
+            ))
+            .with_body(
+                r#"{"id": 2, "version": 1, "tags": [
+                    {"names": ["maine_coon", "coon"], "category": "default", "usages": 1},
+                    {"names": ["tabby"], "category": "default", "usages": 3},
+                    {"names": ["cat"], "category": "default", "usages": 5}
+                ]}"#,
+            )
+            .create_async()
+            .await;
+
+        let new_post = CreateUpdatePostBuilder::default()
+            .tags(vec!["maine_coon".to_string(), "Tabby".to_string()])
+            .safety(PostSafety::Safe)
+            .content_url("http://example.com/cat.jpg".to_string())
+            .build()
+            .unwrap();
+        let client = client_for(&server);
+        let post = client
+            .request()
+            .create_post_from_url(&new_post)
+            .await
+            .expect("Unable to create post");
+
+        assert_eq!(post.tags.as_ref().map(Vec::len), Some(3));
+        // "Tabby" resolved to the existing "tabby" tag, so only "cat" came from the server
+        let added = post.server_added_tags(&new_post);
+        assert_eq!(added.len(), 1);
+        assert_eq!(added[0].names, vec!["cat".to_string()]);
+    }
+}
@@ -35,6 +35,15 @@ pub enum SzurubooruClientError {
         /// The URL in question
         url: String,
     },
+    /// The host given when constructing a [SzurubooruClient](crate::SzurubooruClient) isn't an
+    /// absolute `http` or `https` URL
+    #[error("Invalid base URL {url}: {reason}")]
+    InvalidBaseUrl {
+        /// The host as given
+        url: String,
+        /// Why it was rejected
+        reason: String,
+    },
     /// Error occurred building the request before it's sent to the server
     #[error("Error building request {0}")]
     RequestBuilderError(#[source] reqwest::Error),
@@ -59,17 +68,83 @@ pub enum SzurubooruClientError {
     /// Error when validation fails for one of the Builder types
     #[error("Validation error: {0}")]
     ValidationError(String),
+    /// A search query has more terms than the client's
+    /// [limit](crate::SzurubooruClient::with_max_search_terms), so it wasn't sent
+    #[error("Search has {terms} terms, more than the maximum of {max}")]
+    TooManySearchTerms {
+        /// How many terms the query has
+        terms: usize,
+        /// The maximum number of terms allowed
+        max: usize,
+    },
     /// Error occurred when reading a file
     #[error("IO Error: {0}")]
     IOError(#[source] std::io::Error),
+    /// The file to read from doesn't exist
+    #[error("File not found: {}", .0.display())]
+    FileNotFound(std::path::PathBuf),
     /// Error returned by the Szurubooru server
     #[error("Error returned from Szurubooru host: {0:?}")]
     SzurubooruServerError(SzurubooruServerError),
+    /// The server's [IntegrityError](SzurubooruServerErrorType::IntegrityError): a write
+    /// conflicted with the resource's current state. Usually an update or delete whose
+    /// `version` is out of date, in which case fetch the resource again and retry with its new
+    /// version. The server uses the same error when two concurrent requests collide on
+    /// something that must be unique, e.g. uploading the same content at the same time, where no
+    /// version was sent
+    #[error("Version conflict: {0:?}")]
+    VersionConflict(SzurubooruServerError),
+    /// The checksum the server reported for uploaded content doesn't match the checksum of the
+    /// content that was sent
+    #[error("Checksum mismatch: sent content with checksum {expected}, server has {actual}")]
+    ChecksumMismatch {
+        /// The checksum of the content that was sent
+        expected: String,
+        /// The checksum the server reported
+        actual: String,
+    },
+    /// A file wasn't uploaded because it's larger than the server's
+    /// [maximum upload size](crate::models::GlobalInfoConfig::max_upload_size)
+    #[error("File is {size} bytes, more than the server's maximum upload size of {max} bytes")]
+    FileTooLarge {
+        /// The size of the file in bytes
+        size: u64,
+        /// The server's maximum upload size in bytes
+        max: u64,
+    },
+    /// A post wasn't created because some of the
+    /// [required tags](crate::SzurubooruRequest::with_required_tags) don't exist yet
+    #[error("Required tags don't exist: {}", .0.join(", "))]
+    MissingRequiredTags(
+        /// The required tags that weren't found
+        Vec<String>,
+    ),
+}
+
+impl SzurubooruClientError {
+    /// The HTTP status of the response that caused this error, if there was one
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            SzurubooruClientError::ResponseError(status, _) => Some(*status),
+            SzurubooruClientError::SzurubooruServerError(e)
+            | SzurubooruClientError::VersionConflict(e) => e.status,
+            SzurubooruClientError::RequestError(e) => e.status(),
+            _ => None,
+        }
+    }
 }
 
 impl From<SzurubooruServerError> for SzurubooruClientError {
+    /// Wraps a server error, as a [VersionConflict](SzurubooruClientError::VersionConflict) if
+    /// it's the server's [IntegrityError](SzurubooruServerErrorType::IntegrityError), whether or
+    /// not the request sent a version
     fn from(value: SzurubooruServerError) -> Self {
-        SzurubooruClientError::SzurubooruServerError(value)
+        match value.name {
+            SzurubooruServerErrorType::IntegrityError => {
+                SzurubooruClientError::VersionConflict(value)
+            }
+            _ => SzurubooruClientError::SzurubooruServerError(value),
+        }
     }
 }
 
@@ -102,7 +177,7 @@ impl<T> IntoClientResult<T> for SzuruEither<T, SzurubooruServerError> {
     fn into_result(self) -> SzurubooruResult<T> {
         match self {
             SzuruEither::Left(v) => Ok(v),
-            SzuruEither::Right(e) => Err(SzurubooruClientError::SzurubooruServerError(e)),
+            SzuruEither::Right(e) => Err(e.into()),
         }
     }
 }
@@ -196,6 +271,10 @@ pub enum SzurubooruServerErrorType {
     InvalidAvatarError,
     /// Processing error
     ProcessingError,
+    /// The server couldn't download the content from a `contentUrl`
+    DownloadError,
+    /// The content at a `contentUrl` is bigger than the server allows
+    DownloadTooLargeError,
     /// Validation error
     ValidationError,
 }
@@ -209,6 +288,24 @@ pub struct SzurubooruServerError {
     pub title: String,
     /// More of a description of the error
     pub description: String,
+    /// HTTP status of the response the error was returned with. This isn't part of the JSON
+    /// body, so it's only set for errors read from an HTTP error response
+    #[serde(skip)]
+    pub status: Option<StatusCode>,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+/// Error returned when a string can't be parsed into a [QueryToken](crate::tokens::QueryToken)
+pub enum TokenParseError {
+    /// The string was empty
+    #[error("Query token is empty")]
+    Empty,
+    /// The string contained whitespace, so it's more than one token
+    #[error("Query token {0:?} contains whitespace")]
+    ContainsWhitespace(String),
+    /// The string had a value but no key, e.g. `:value`
+    #[error("Query token {0:?} has no key")]
+    MissingKey(String),
 }
 
 #[cfg(test)]
@@ -230,5 +327,30 @@ mod test {
         assert_eq!(sse.name, SzurubooruServerErrorType::ValidationError);
         assert_eq!(sse.title, "Validation Error");
         assert_eq!(sse.description, "Some sort of validation error");
+        assert_eq!(sse.status, None);
+    }
+
+    #[test]
+    fn test_parse_duplicate_post_error() {
+        let json_response = r#"{
+        "name": "PostAlreadyUploadedError",
+        "title": "Post already uploaded",
+        "description": "Post already uploaded (http://localhost/post/4)",
+        "otherPostId": 4,
+        "otherPostUrl": "http://localhost/post/4"
+        }"#;
+
+        let sse = serde_json::from_str::<SzurubooruServerError>(json_response)
+            .expect("Failed to parse the JSON response");
+
+        assert_eq!(
+            sse.name,
+            SzurubooruServerErrorType::PostAlreadyUploadedError
+        );
+        assert_eq!(sse.title, "Post already uploaded");
+        assert_eq!(
+            sse.description,
+            "Post already uploaded (http://localhost/post/4)"
+        );
     }
 }
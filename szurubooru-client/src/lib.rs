@@ -35,6 +35,9 @@ pub use errors::SzurubooruResult;
 pub mod models;
 pub mod tokens;
 
+#[cfg(feature = "markdown")]
+pub mod markdown;
+
 #[cfg(feature = "python")]
 #[doc(hidden)]
 pub mod py;
@@ -66,7 +69,8 @@ mod szurubooru_client {
             PostSpecialToken, QueryToken, SnapshotNamedToken, TagNamedToken, TagSortToken,
             UserNamedToken, UserSortToken,
         },*/
-        py::asynchronous::PythonAsyncClient, py::synchronous::PythonSyncClient,
+        py::asynchronous::{PyPostStream, PythonAsyncClient},
+        py::synchronous::PythonSyncClient,
         py::PyPagedSearchResult,
     };
 
@@ -74,10 +78,10 @@ mod szurubooru_client {
     mod tokens {
         #[pymodule_export]
         pub use crate::tokens::{
-            anonymous_token, named_token, sort_token, special_token, CommentNamedToken,
-            CommentSortToken, PoolNamedToken, PoolSortToken, PostNamedToken, PostSortToken,
-            PostSpecialToken, QueryToken, SnapshotNamedToken, TagNamedToken, TagSortToken,
-            UserNamedToken, UserSortToken,
+            anonymous_token, named_token, sort_token, special_token, to_query_string,
+            CommentNamedToken, CommentSortToken, PoolNamedToken, PoolSortToken, PostNamedToken,
+            PostSortToken, PostSpecialToken, QueryToken, SnapshotNamedToken, TagNamedToken,
+            TagSortToken, UserNamedToken, UserSortToken,
         };
     }
 
@@ -0,0 +1,269 @@
+//! Best-effort rendering of Szurubooru-flavoured Markdown, as used in comments and tag
+//! descriptions.
+//!
+//! Szurubooru renders Markdown in the browser with its own set of extensions. This module
+//! approximates that output with [pulldown_cmark]: standard CommonMark plus strikethrough,
+//! `[spoiler]...[/spoiler]` blocks, and `@123` post references. Code blocks are rendered literally,
+//! raw HTML in the input is escaped rather than passed through, links and images are only kept when
+//! they point at an `http`, `https` or relative URL, and unclosed spoilers are closed with the
+//! block they started in. The output will not be byte-for-byte identical to what the web UI
+//! produces, so treat it as a preview rather than a canonical rendering.
+
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag, TagEnd};
+
+const SPOILER_OPEN: &str = "[spoiler]";
+const SPOILER_CLOSE: &str = "[/spoiler]";
+
+/// Renders Szurubooru-flavoured Markdown `text` to HTML.
+///
+/// This is a best-effort approximation of the web UI's renderer; see the
+/// [module documentation](self) for the supported extensions.
+///
+/// ```rust
+/// use szurubooru_client::markdown::render_markdown;
+/// let html = render_markdown("See @42 for *details*");
+/// assert_eq!(html, "<p>See <a href=\"/post/42\">@42</a> for <em>details</em></p>\n");
+/// ```
+pub fn render_markdown(text: &str) -> String {
+    let parser = Parser::new_ext(text, Options::ENABLE_STRIKETHROUGH);
+    let mut renderer = Renderer::default();
+    for event in coalesce_text(parser) {
+        renderer.push(event);
+    }
+    renderer.close_spoilers(0);
+
+    let mut output = String::with_capacity(text.len() * 3 / 2);
+    html::push_html(&mut output, renderer.events.into_iter());
+    output
+}
+
+/// Rewrites the parsed events, keeping track of the open elements so that every spoiler span
+/// is closed inside the element it was opened in
+#[derive(Default)]
+struct Renderer<'a> {
+    events: Vec<Event<'a>>,
+    /// How many elements are currently open
+    depth: usize,
+    /// The depth each open spoiler was opened at, innermost last
+    spoilers: Vec<usize>,
+    /// Whether each open link or image is kept, innermost last
+    links: Vec<bool>,
+    /// Whether a code block is open, so its text is kept as it is
+    in_code_block: bool,
+}
+
+impl<'a> Renderer<'a> {
+    fn push(&mut self, event: Event<'a>) {
+        match event {
+            Event::Start(tag @ (Tag::Link { .. } | Tag::Image { .. })) => {
+                let keep = match &tag {
+                    Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                        is_safe_url(dest_url)
+                    }
+                    _ => unreachable!(),
+                };
+                self.links.push(keep);
+                if keep {
+                    self.start(tag);
+                }
+            }
+            Event::End(end @ (TagEnd::Link | TagEnd::Image)) => {
+                if self.links.pop().unwrap_or(true) {
+                    self.end(end);
+                }
+            }
+            Event::Start(tag @ Tag::CodeBlock(_)) => {
+                self.in_code_block = true;
+                self.start(tag);
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                self.in_code_block = false;
+                self.end(TagEnd::CodeBlock);
+            }
+            Event::Start(tag) => self.start(tag),
+            Event::End(end) => self.end(end),
+            Event::Text(text) if self.in_code_block => self.events.push(Event::Text(text)),
+            Event::Text(text) => self.push_text(text),
+            other => self.events.push(other),
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'a>) {
+        self.depth += 1;
+        self.events.push(Event::Start(tag));
+    }
+
+    fn end(&mut self, end: TagEnd) {
+        self.close_spoilers(self.depth);
+        self.depth = self.depth.saturating_sub(1);
+        self.events.push(Event::End(end));
+    }
+
+    /// Closes the spoilers opened at `depth` or deeper
+    fn close_spoilers(&mut self, depth: usize) {
+        while self.spoilers.last().is_some_and(|&opened| opened >= depth) {
+            self.spoilers.pop();
+            self.events.push(Event::InlineHtml("</span>".into()));
+        }
+    }
+
+    /// Splits a text event into plain text, spoiler markers and post references. A closing
+    /// marker that doesn't match a spoiler opened in the same element is kept as plain text
+    fn push_text(&mut self, text: CowStr<'a>) {
+        if !text.contains(SPOILER_OPEN) && !text.contains(SPOILER_CLOSE) && !text.contains('@') {
+            self.events.push(Event::Text(text));
+            return;
+        }
+
+        let mut plain = String::new();
+        let mut rest: &str = &text;
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix(SPOILER_OPEN) {
+                self.flush_plain(&mut plain);
+                self.events
+                    .push(Event::InlineHtml("<span class=\"spoiler\">".into()));
+                self.spoilers.push(self.depth);
+                rest = after;
+            } else if let Some(after) = rest
+                .strip_prefix(SPOILER_CLOSE)
+                .filter(|_| self.spoilers.last() == Some(&self.depth))
+            {
+                self.flush_plain(&mut plain);
+                self.events.push(Event::InlineHtml("</span>".into()));
+                self.spoilers.pop();
+                rest = after;
+            } else if let Some((post_id, after)) = post_reference(rest, plain.chars().last()) {
+                self.flush_plain(&mut plain);
+                self.events.push(Event::InlineHtml(
+                    format!("<a href=\"/post/{post_id}\">@{post_id}</a>").into(),
+                ));
+                rest = after;
+            } else {
+                let ch = rest.chars().next().expect("rest is not empty");
+                plain.push(ch);
+                rest = &rest[ch.len_utf8()..];
+            }
+        }
+        self.flush_plain(&mut plain);
+    }
+
+    fn flush_plain(&mut self, plain: &mut String) {
+        if !plain.is_empty() {
+            self.events.push(Event::Text(std::mem::take(plain).into()));
+        }
+    }
+}
+
+/// Whether `url` is relative or uses the `http` or `https` scheme. Browsers ignore leading
+/// whitespace and control characters, as well as tabs and newlines inside the scheme, so
+/// those are skipped before comparing
+fn is_safe_url(url: &str) -> bool {
+    let url = url.trim_start_matches(|c: char| c.is_ascii_whitespace() || c.is_ascii_control());
+    match url.find([':', '/', '?', '#']) {
+        Some(end) if url[end..].starts_with(':') => {
+            let scheme: String = url[..end]
+                .chars()
+                .filter(|c| !c.is_ascii_whitespace() && !c.is_ascii_control())
+                .collect();
+            scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https")
+        }
+        _ => true,
+    }
+}
+
+/// Escapes raw HTML and merges adjacent text events, since the parser splits text around
+/// brackets and would otherwise break up `[spoiler]` markers
+fn coalesce_text<'a>(events: impl Iterator<Item = Event<'a>>) -> Vec<Event<'a>> {
+    let mut merged: Vec<Event<'a>> = Vec::new();
+    for event in events {
+        let event = match event {
+            Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+            other => other,
+        };
+        match (merged.last_mut(), event) {
+            (Some(Event::Text(previous)), Event::Text(text)) => {
+                *previous = format!("{previous}{text}").into();
+            }
+            (_, event) => merged.push(event),
+        }
+    }
+    merged
+}
+
+/// Matches `@<digits>` at the start of `text`, as long as it doesn't follow a word character
+fn post_reference(text: &str, previous: Option<char>) -> Option<(&str, &str)> {
+    if previous.is_some_and(|c| c.is_alphanumeric()) {
+        return None;
+    }
+    let digits = text.strip_prefix('@')?;
+    let end = digits
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(digits.len());
+    (end > 0).then(|| digits.split_at(end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_link_and_emphasis() {
+        let html = render_markdown("A [link](https://example.com) with *emphasis* and **more**");
+        assert_eq!(
+            html,
+            "<p>A <a href=\"https://example.com\">link</a> with <em>emphasis</em> and \
+             <strong>more</strong></p>\n"
+        );
+    }
+
+    #[test]
+    fn test_render_extensions() {
+        let html = render_markdown("~~old~~ [spoiler]secret[/spoiler] @7 mail@8 <b>raw</b>");
+        assert_eq!(
+            html,
+            "<p><del>old</del> <span class=\"spoiler\">secret</span> \
+             <a href=\"/post/7\">@7</a> mail@8 &lt;b&gt;raw&lt;/b&gt;</p>\n"
+        );
+    }
+
+    #[test]
+    fn test_render_code_blocks() {
+        assert_eq!(
+            render_markdown("```\nuser@42 [spoiler]x[/spoiler] @7\n```"),
+            "<pre><code>user@42 [spoiler]x[/spoiler] @7\n</code></pre>\n"
+        );
+        assert_eq!(
+            render_markdown("    [spoiler]x @7\n\n[spoiler]y[/spoiler]"),
+            "<pre><code>[spoiler]x @7\n</code></pre>\n\
+             <p><span class=\"spoiler\">y</span></p>\n"
+        );
+    }
+
+    #[test]
+    fn test_render_unsafe_urls() {
+        let html = render_markdown(
+            "[a](javascript:alert(1)) [b](JavaScript:alert(1)) [c](java%09script:x) \
+             ![d](data:image/png;base64,AAAA) <vbscript:x> [e](/post/1) [f](https://example.com)",
+        );
+        assert_eq!(
+            html,
+            "<p>a b c d vbscript:x \
+             <a href=\"/post/1\">e</a> <a href=\"https://example.com\">f</a></p>\n"
+        );
+        let html = render_markdown("[a](<\u{1} java\tscript:alert(1)>) [b](<\u{1}javascript:x>)");
+        assert!(!html.contains("href"), "{html}");
+    }
+
+    #[test]
+    fn test_render_unclosed_spoilers() {
+        assert_eq!(
+            render_markdown("[spoiler]secret"),
+            "<p><span class=\"spoiler\">secret</span></p>\n"
+        );
+        assert_eq!(
+            render_markdown("[spoiler]**bold [spoiler]x**\n\nnext[/spoiler]"),
+            "<p><span class=\"spoiler\"><strong>bold <span class=\"spoiler\">x</span>\
+             </strong></span></p>\n<p>next[/spoiler]</p>\n"
+        );
+    }
+}
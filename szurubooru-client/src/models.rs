@@ -5,11 +5,15 @@
 //! See [here](https://github.com/rr-/szurubooru/blob/master/doc/API.md#field-selecting) for
 //! more information.
 
-use crate::errors::SzurubooruClientError;
+use crate::errors::{SzurubooruClientError, SzurubooruResult};
+use crate::tokens::QueryToken;
+use crate::SzurubooruRequest;
 use chrono::{DateTime, Utc};
 use derive_builder::Builder;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::path::PathBuf;
 use strum_macros::AsRefStr;
 
 #[cfg(feature = "python")]
@@ -258,7 +262,6 @@ impl TagCategoryResource {
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, Builder)]
 #[builder(setter(strip_option), build_fn(error = "SzurubooruClientError"))]
-
 /// Used for creating or updating a Tag Category
 pub struct CreateUpdateTagCategory {
     /// Resource version. See [versioning](ResourceVersion)
@@ -348,6 +351,79 @@ pub enum PostType {
     Webm,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A flag set on a post. Flags the crate doesn't know about are kept as
+/// [Other](PostFlag::Other) rather than failing to deserialize.
+///
+/// In Python, flags are plain ``str`` values
+pub enum PostFlag {
+    /// The content should be played in a loop
+    Loop,
+    /// The content has sound
+    Sound,
+    /// Any other flag
+    Other(String),
+}
+
+impl AsRef<str> for PostFlag {
+    fn as_ref(&self) -> &str {
+        match self {
+            PostFlag::Loop => "loop",
+            PostFlag::Sound => "sound",
+            PostFlag::Other(flag) => flag,
+        }
+    }
+}
+
+impl From<&str> for PostFlag {
+    fn from(value: &str) -> Self {
+        match value {
+            "loop" => PostFlag::Loop,
+            "sound" => PostFlag::Sound,
+            other => PostFlag::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for PostFlag {
+    fn from(value: String) -> Self {
+        PostFlag::from(value.as_str())
+    }
+}
+
+impl Serialize for PostFlag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_ref())
+    }
+}
+
+impl<'de> Deserialize<'de> for PostFlag {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(PostFlag::from)
+    }
+}
+
+#[cfg(feature = "python")]
+impl ToPyObject for PostFlag {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        self.as_ref().to_object(py)
+    }
+}
+
+#[cfg(feature = "python")]
+impl IntoPy<PyObject> for PostFlag {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+#[cfg(feature = "python")]
+impl<'py> FromPyObject<'py> for PostFlag {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        ob.extract::<String>().map(PostFlag::from)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, AsRefStr, Eq, PartialEq)]
 #[cfg_attr(
     all(feature = "python"),
@@ -404,13 +480,62 @@ impl WithBaseURL for MicroPostResource {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// What [PostResource::best_quality_url] should return for videos above the size threshold
+pub enum LargeVideoBehavior {
+    /// Return the thumbnail instead, falling back to streaming if there's no thumbnail
+    Thumbnail,
+    /// Return the content URL, flagged as needing to be streamed
+    Stream,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The URL chosen by [PostResource::best_quality_url]
+pub enum ViewerUrl<'a> {
+    /// The original content, small enough to load in full
+    Content(&'a str),
+    /// The original content, which is large enough that it should be streamed
+    Stream(&'a str),
+    /// The post thumbnail, used in place of a large video
+    Thumbnail(&'a str),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// The changes between two versions of a post, as returned by [PostResource::diff]. Changed
+/// values are stored as `(old, new)` pairs
+pub struct PostDiff {
+    /// Tags on the new version that weren't on the old one
+    pub added_tags: Vec<String>,
+    /// Tags on the old version that aren't on the new one
+    pub removed_tags: Vec<String>,
+    /// The safety, if it changed
+    pub safety: Option<(Option<PostSafety>, Option<PostSafety>)>,
+    /// The source, if it changed
+    pub source: Option<(Option<String>, Option<String>)>,
+    /// The flags, if they changed
+    pub flags: Option<(Vec<PostFlag>, Vec<PostFlag>)>,
+    /// Whether the content was replaced, based on the checksums
+    pub content_changed: bool,
+    /// IDs of posts that became related
+    pub added_relations: Vec<u32>,
+    /// IDs of posts that are no longer related
+    pub removed_relations: Vec<u32>,
+}
+
+impl PostDiff {
+    /// Whether nothing changed between the two versions
+    pub fn is_empty(&self) -> bool {
+        self == &PostDiff::default()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[doc(hidden)]
 pub(crate) struct PostId {
     pub id: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(
     all(feature = "python"),
     pyclass(get_all, module = "szurubooru_client.models")
@@ -449,8 +574,11 @@ pub struct PostResource {
     /// Where the post thumbnail is located
     pub thumbnail_url: Option<String>,
     /// Various flags such as whether the post is looped
-    pub flags: Option<Vec<String>>,
-    /// List of tags the post is tagged with
+    pub flags: Option<Vec<PostFlag>>,
+    /// List of tags the post is tagged with. Servers that send the tags as plain names are
+    /// supported too, each name becomes a tag with an empty category and no usages. Use
+    /// [tag_names](Self::tag_names) to get the names regardless of the shape
+    #[serde(default, deserialize_with = "deserialize_post_tags")]
     pub tags: Option<Vec<MicroTagResource>>,
     /// A list of related posts.
     pub relations: Option<Vec<MicroPostResource>>,
@@ -501,6 +629,181 @@ impl PostResource {
     }
 }
 
+/// Deserializes post tags given either as [MicroTagResource]s or as plain tag names
+fn deserialize_post_tags<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Vec<MicroTagResource>>, D::Error> {
+    let tags = Option::<Vec<SzuruEither<MicroTagResource, String>>>::deserialize(deserializer)?;
+    Ok(tags.map(|tags| {
+        tags.into_iter()
+            .map(|tag| match tag {
+                SzuruEither::Left(tag) => tag,
+                SzuruEither::Right(name) => MicroTagResource {
+                    names: vec![name],
+                    category: String::new(),
+                    usages: 0,
+                },
+            })
+            .collect()
+    }))
+}
+
+impl PostResource {
+    /// A reasonable default size above which [best_quality_url](Self::best_quality_url) treats a
+    /// video as large. 50 MiB
+    pub const DEFAULT_LARGE_VIDEO_THRESHOLD: u64 = 50 * 1024 * 1024;
+
+    /// Picks the URL a viewer should display for this post. Images and animations always use the
+    /// full [content URL](Self::content_url). Videos larger than `large_video_threshold` bytes
+    /// are handled according to `large_video`. Returns `None` if the needed URLs weren't
+    /// included in the response
+    pub fn best_quality_url(
+        &self,
+        large_video_threshold: u64,
+        large_video: LargeVideoBehavior,
+    ) -> Option<ViewerUrl<'_>> {
+        let content_url = self.content_url.as_deref();
+        let is_video = matches!(self.post_type, Some(PostType::Video | PostType::Webm));
+        let is_large = self
+            .file_size
+            .is_some_and(|size| size > large_video_threshold);
+        if !(is_video && is_large) {
+            return content_url.map(ViewerUrl::Content);
+        }
+
+        match large_video {
+            LargeVideoBehavior::Thumbnail => self
+                .thumbnail_url
+                .as_deref()
+                .map(ViewerUrl::Thumbnail)
+                .or(content_url.map(ViewerUrl::Stream)),
+            LargeVideoBehavior::Stream => content_url.map(ViewerUrl::Stream),
+        }
+    }
+
+    /// The annotations on this post, or an empty slice if [notes](Self::notes) weren't
+    /// included in the response
+    pub fn notes(&self) -> &[NoteResource] {
+        self.notes.as_deref().unwrap_or_default()
+    }
+
+    /// The primary name of each tag on this post, in order, whether the server sent the tags as
+    /// resources or as plain names. Empty if [tags](Self::tags) weren't included in the response
+    pub fn tag_names(&self) -> Vec<String> {
+        self.tags
+            .iter()
+            .flatten()
+            .filter_map(|tag| tag.names.first().cloned())
+            .collect()
+    }
+
+    /// Fetches the [relations](Self::relations) and [pools](Self::pools) of this post if either
+    /// of them is missing, e.g. because the post came from a search that selected other
+    /// fields. Once both are present later calls return straight away without a request, so
+    /// this can be called before every access.
+    ///
+    /// The cache is the post itself, so hydrating needs `&mut self`. To share a post between
+    /// tasks, wrap it in a lock such as [tokio::sync::Mutex](https://docs.rs/tokio/latest/tokio/sync/struct.Mutex.html)
+    /// so only one of them fetches.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # #[allow(unused)]
+    /// # async {
+    /// let client = SzurubooruClient::new_with_token("http://localhost:5001", "myuser", "sz-123456", true).unwrap();
+    /// let request = client.request();
+    /// let mut post = client.with_fields(vec!["id".to_string()]).get_post(1).await.unwrap();
+    /// post.hydrate_relations(&request).await.unwrap();
+    /// // No request is made this time
+    /// post.hydrate_relations(&request).await.unwrap();
+    /// println!("{:?} {:?}", post.relations, post.pools);
+    /// # };
+    /// # ()
+    /// ```
+    pub async fn hydrate_relations(
+        &mut self,
+        request: &SzurubooruRequest<'_>,
+    ) -> SzurubooruResult<()> {
+        if self.relations.is_some() && self.pools.is_some() {
+            return Ok(());
+        }
+        let post_id = self.id.ok_or_else(|| {
+            SzurubooruClientError::ValidationError("Post ID is required".to_string())
+        })?;
+        let fetched = request
+            .clone()
+            .with_fields(vec!["relations".to_string(), "pools".to_string()])
+            .get_post(post_id)
+            .await?;
+        self.relations = Some(fetched.relations.unwrap_or_default());
+        self.pools = Some(fetched.pools.unwrap_or_default());
+        Ok(())
+    }
+
+    /// Compares this post to a newer version of it, reporting what changed going from `self` to
+    /// `other`. Tags are compared by their primary name. Fields that weren't fetched on either
+    /// post are treated as empty.
+    pub fn diff(&self, other: &PostResource) -> PostDiff {
+        fn relation_ids(post: &PostResource) -> Vec<u32> {
+            post.relations.iter().flatten().map(|rel| rel.id).collect()
+        }
+        fn changed<T: Clone + PartialEq>(old: &T, new: &T) -> Option<(T, T)> {
+            (old != new).then(|| (old.clone(), new.clone()))
+        }
+
+        let (old_tags, new_tags) = (self.tag_names(), other.tag_names());
+        let (old_relations, new_relations) = (relation_ids(self), relation_ids(other));
+        let old_flags = self.flags.clone().unwrap_or_default();
+        let new_flags = other.flags.clone().unwrap_or_default();
+        PostDiff {
+            added_tags: new_tags
+                .iter()
+                .filter(|t| !old_tags.contains(t))
+                .cloned()
+                .collect(),
+            removed_tags: old_tags
+                .iter()
+                .filter(|t| !new_tags.contains(t))
+                .cloned()
+                .collect(),
+            safety: changed(&self.safety, &other.safety),
+            source: changed(&self.source, &other.source),
+            flags: changed(&old_flags, &new_flags),
+            content_changed: self.checksum != other.checksum,
+            added_relations: new_relations
+                .iter()
+                .filter(|id| !old_relations.contains(id))
+                .copied()
+                .collect(),
+            removed_relations: old_relations
+                .iter()
+                .filter(|id| !new_relations.contains(id))
+                .copied()
+                .collect(),
+        }
+    }
+
+    /// Returns the tags on this post that weren't part of the submitted
+    /// [tags](CreateUpdatePost::tags), such as implied tags or tags added by the server when the
+    /// post was uploaded. A submitted tag matches if it's any of the tag's names or aliases,
+    /// ignoring case as Szurubooru does.
+    pub fn server_added_tags(&self, submitted: &CreateUpdatePost) -> Vec<MicroTagResource> {
+        let submitted_tags = submitted.tags.as_deref().unwrap_or_default();
+        self.tags
+            .iter()
+            .flatten()
+            .filter(|tag| {
+                !tag.names.iter().any(|name| {
+                    submitted_tags
+                        .iter()
+                        .any(|submitted| submitted.eq_ignore_ascii_case(name))
+                })
+            })
+            .cloned()
+            .collect()
+    }
+}
+
 impl WithBaseURL for PostResource {
     fn with_base_url(self, url: &str) -> Self {
         let curl = self.content_url.map(|cu| {
@@ -570,7 +873,7 @@ pub struct CreateUpdatePost {
     /// Flags relevant to the post. If omitted they will be auto-detected
     #[builder(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub flags: Option<Vec<String>>,
+    pub flags: Option<Vec<PostFlag>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     /// The URL to download the content from
     #[builder(default)]
@@ -580,12 +883,113 @@ pub struct CreateUpdatePost {
     /// [upload_temporary_file](crate::SzurubooruRequest::upload_temporary_file)
     #[builder(default)]
     pub content_token: Option<String>,
-    /// Upload the post anonymously
+    /// Upload the post anonymously, so it isn't attributed to the authenticated user. Requires
+    /// the `posts:create:anonymous` privilege
     #[builder(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub anonymous: Option<bool>,
 }
 
+impl CreateUpdatePost {
+    /// Checks each line of [source](Self::source) for what look like mistyped URLs. Szurubooru
+    /// stores sources as free-form text with one source per line, so lines that aren't URLs at
+    /// all are fine; only lines that were probably meant to be URLs but won't link properly are
+    /// reported. This is purely diagnostic, the post can still be sent as-is.
+    ///
+    /// ```
+    /// use szurubooru_client::models::{CreateUpdatePostBuilder, SourceWarning};
+    /// let post = CreateUpdatePostBuilder::default()
+    ///     .source("https://example.com/art/1\nScanned from a magazine\nhtps://example.com/art/2".to_string())
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     post.validate_sources(),
+    ///     vec![SourceWarning::MisspelledScheme(2, "htps".to_string())]
+    /// );
+    /// ```
+    pub fn validate_sources(&self) -> Vec<SourceWarning> {
+        let mut seen = HashSet::new();
+        self.source
+            .iter()
+            .flat_map(|source| source.lines().enumerate())
+            .map(|(index, line)| (index, line.trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .filter_map(|(index, line)| {
+                if !seen.insert(line) {
+                    return Some(SourceWarning::Duplicate(index));
+                }
+                source_warning(index, line)
+            })
+            .collect()
+    }
+}
+
+/// Schemes that aren't `http` or `https` but look like they could be misspellings of them
+const NON_HTTP_SCHEMES: &[&str] = &["ftp", "sftp", "ftps"];
+
+/// Checks a single non-empty source line for URL typos
+fn source_warning(index: usize, line: &str) -> Option<SourceWarning> {
+    let lower = line.to_ascii_lowercase();
+    if lower.starts_with("www.") {
+        return Some(SourceWarning::MissingScheme(index));
+    }
+    match url::Url::parse(line) {
+        Ok(url) => {
+            let scheme = url.scheme();
+            if scheme == "http" || scheme == "https" {
+                // The parser accepts `http:/host` and `http:host`, browsers linkifying the
+                // source won't
+                (!lower.starts_with(&format!("{scheme}://"))).then(|| {
+                    SourceWarning::MalformedUrl(index, "expected :// after the scheme".to_string())
+                })
+            } else {
+                let http_like =
+                    scheme.starts_with("ht") || scheme.ends_with("tp") || scheme.ends_with("tps");
+                (http_like && !NON_HTTP_SCHEMES.contains(&scheme))
+                    .then(|| SourceWarning::MisspelledScheme(index, scheme.to_string()))
+            }
+        }
+        Err(e) => {
+            let first_word = lower.split_whitespace().next().unwrap_or_default();
+            (first_word.starts_with("http") && first_word.contains(['/', ':']))
+                .then(|| SourceWarning::MalformedUrl(index, e.to_string()))
+        }
+    }
+}
+
+/// A possible typo in a post's sources, as returned by
+/// [CreateUpdatePost::validate_sources]. Each variant holds the 0-based index of the offending
+/// line, while the [Display] message numbers lines from 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SourceWarning {
+    /// The line starts with `www.` but has no `http://` or `https://` in front of it
+    MissingScheme(usize),
+    /// The line is a URL whose scheme, given here, looks like a misspelling of `http` or
+    /// `https`, e.g. `htps://`
+    MisspelledScheme(usize, String),
+    /// The line starts like an `http` URL but isn't a valid one, for the given reason
+    MalformedUrl(usize, String),
+    /// The line repeats an earlier source
+    Duplicate(usize),
+}
+
+impl Display for SourceWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SourceWarning::MissingScheme(index) => {
+                write!(f, "Source {} is missing http:// or https://", index + 1)
+            }
+            SourceWarning::MisspelledScheme(index, scheme) => {
+                write!(f, "Source {} has a misspelled scheme {scheme}", index + 1)
+            }
+            SourceWarning::MalformedUrl(index, reason) => {
+                write!(f, "Source {} is not a valid URL: {reason}", index + 1)
+            }
+            SourceWarning::Duplicate(index) => write!(f, "Source {} is a duplicate", index + 1),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 /// A token representing a temporary file upload
@@ -619,13 +1023,152 @@ pub struct MergePost {
     pub replace_post_content: bool,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+/// Where a bulk operation such as [retag_search](crate::SzurubooruRequest::retag_search) got
+/// to. Bulk operations work through posts from the highest ID down, so passing this back as
+/// `resume_from` skips every post that was already processed. It can be serialized to save it
+/// between runs
+pub struct BulkCursor {
+    /// The ID of the last post that was processed, whether or not processing it succeeded
+    pub last_post_id: u32,
+}
+
+#[derive(Debug, Default)]
+/// The outcome of [retag_search](crate::SzurubooruRequest::retag_search)
+pub struct RetagSummary {
+    /// The number of posts that matched the query
+    pub matched: u32,
+    /// The IDs of the posts whose tags were changed
+    pub updated: Vec<u32>,
+    /// The number of posts that already had the requested tags
+    pub unchanged: u32,
+    /// The posts that couldn't be updated, along with the reason why
+    pub failed: Vec<(u32, SzurubooruClientError)>,
+    /// Where to resume from to skip the posts processed so far. `None` if no post was processed
+    pub cursor: Option<BulkCursor>,
+}
+
+#[derive(Debug, Clone)]
+/// The outcome of
+/// [create_or_get_post_from_file_path](crate::SzurubooruRequest::create_or_get_post_from_file_path)
+pub struct CreatedPost {
+    /// The newly created post, or the existing post with the same content
+    pub post: PostResource,
+    /// Whether the content had already been uploaded, in which case [post](Self::post) is the
+    /// existing post and none of the submitted properties were applied to it
+    pub already_existed: bool,
+}
+
+#[derive(Debug, Clone)]
+/// A group of posts that are likely duplicates of each other, as found by
+/// [find_duplicates](crate::SzurubooruRequest::find_duplicates)
+pub struct DuplicateCluster {
+    /// The newest post in the group
+    pub post: PostResource,
+    /// Older posts with exactly the same content checksum as [post](Self::post)
+    pub exact: Vec<PostResource>,
+    /// Older posts that look like [post](Self::post), closest first
+    pub similar: Vec<ImageSearchSimilarPost>,
+}
+
+#[derive(Debug, Clone, Default)]
+/// The searches run by [dashboard](crate::SzurubooruRequest::dashboard), one per section. A
+/// section without a query lists everything in the server's default order
+pub struct DashboardQueries {
+    /// The query for the [posts](Dashboard::posts) section
+    pub posts: Option<Vec<QueryToken>>,
+    /// The query for the [tags](Dashboard::tags) section
+    pub tags: Option<Vec<QueryToken>>,
+    /// The query for the [pools](Dashboard::pools) section
+    pub pools: Option<Vec<QueryToken>>,
+    /// The query for the [comments](Dashboard::comments) section
+    pub comments: Option<Vec<QueryToken>>,
+}
+
+#[derive(Debug)]
+/// The outcome of [dashboard](crate::SzurubooruRequest::dashboard). Each section is fetched
+/// separately, so one failing doesn't affect the others
+pub struct Dashboard {
+    /// The first page of posts matching [DashboardQueries::posts]
+    pub posts: SzurubooruResult<PagedSearchResult<PostResource>>,
+    /// The first page of tags matching [DashboardQueries::tags]
+    pub tags: SzurubooruResult<PagedSearchResult<TagResource>>,
+    /// The first page of pools matching [DashboardQueries::pools]
+    pub pools: SzurubooruResult<PagedSearchResult<PoolResource>>,
+    /// The first page of comments matching [DashboardQueries::comments]
+    pub comments: SzurubooruResult<PagedSearchResult<CommentResource>>,
+}
+
+impl Dashboard {
+    /// Whether every section was fetched successfully
+    pub fn is_complete(&self) -> bool {
+        self.posts.is_ok() && self.tags.is_ok() && self.pools.is_ok() && self.comments.is_ok()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// How far an upload has got, as reported to the progress callback of uploads such as
+/// [create_posts_from_paths](crate::SzurubooruRequest::create_posts_from_paths). A single-file
+/// upload reports itself as a batch of one item
+pub struct UploadProgress {
+    /// The number of items that have finished uploading, whether or not they succeeded
+    pub items_done: usize,
+    /// The number of items in the upload
+    pub items_total: usize,
+    /// The item currently being uploaded, or the last one that finished
+    pub current_item: Option<PathBuf>,
+    /// The number of bytes of file content sent so far, across all items
+    pub bytes_sent: u64,
+    /// The total size of the file content in the upload, across all items
+    pub bytes_total: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[doc(hidden)]
 pub struct RateResource {
     pub score: i8,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// The score a user gives a post with [rate_post](crate::SzurubooruRequest::rate_post)
+pub enum PostRating {
+    /// An upvote, sent as a score of 1
+    Up,
+    /// Clears a previous vote, sent as a score of 0
+    Neutral,
+    /// A downvote, sent as a score of -1
+    Down,
+}
+
+impl From<PostRating> for i8 {
+    fn from(rating: PostRating) -> Self {
+        match rating {
+            PostRating::Up => 1,
+            PostRating::Neutral => 0,
+            PostRating::Down => -1,
+        }
+    }
+}
+
+impl TryFrom<i8> for PostRating {
+    type Error = SzurubooruClientError;
+
+    /// Converts a raw score of -1, 0 or 1, failing with a
+    /// [ValidationError](SzurubooruClientError::ValidationError) for anything else
+    fn try_from(score: i8) -> Result<Self, Self::Error> {
+        match score {
+            1 => Ok(PostRating::Up),
+            0 => Ok(PostRating::Neutral),
+            -1 => Ok(PostRating::Down),
+            _ => Err(SzurubooruClientError::ValidationError(
+                "Score must be -1, 0 or 1".to_string(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(
     all(feature = "python"),
     pyclass(get_all, module = "szurubooru_client.models")
@@ -637,7 +1180,7 @@ pub struct NoteResource {
     /// For example, `[[0,0],[0,1],[1,1],[1,0]]` will draw the annotation on the whole post,
     /// whereas `[[0,0],[0,0.5],[0.5,0.5],[0.5,0]]` will draw it inside the post's upper left
     /// quarter
-    pub polygon: Vec<Vec<u8>>,
+    pub polygon: Vec<[f64; 2]>,
     /// The annotation text, in Markdown format
     pub text: String,
 }
@@ -891,6 +1434,10 @@ pub struct CreateUpdateUser {
     #[builder(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub password: Option<String>,
+    /// The user's email address, also used to look up their Gravatar
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
     /// The user's desired rank, if not given will default to `default_rank` in the server's
     /// configuration
     #[builder(default)]
@@ -1052,6 +1599,8 @@ pub struct GlobalInfoConfig {
     pub can_send_mails: bool,
     /// Available privileges enabled for this server
     pub privileges: HashMap<String, String>,
+    /// The largest file the server accepts for upload, in bytes, if it reports one
+    pub max_upload_size: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1064,20 +1613,36 @@ pub struct GlobalInfoConfig {
 pub struct GlobalInfo {
     /// The total number of posts
     pub post_count: u32,
-    /// Total disk usage
-    pub disk_usage: u32,
-    /// The current featured post
-    pub featured_post: Option<u32>,
-    /// The time the current featured post was featured
+    /// Total disk usage in bytes
+    pub disk_usage: u64,
+    /// The current featured post, or [None] if no post has been featured yet
+    pub featured_post: Option<PostResource>,
+    /// The time the current featured post was featured. [None] along with
+    /// [featured_post](Self::featured_post)
     pub featuring_time: Option<DateTime<Utc>>,
-    /// The user who uploaded the featured post
-    pub featuring_user: Option<u32>,
+    /// The user who featured the current featured post, usually with just the `name` and
+    /// `avatarUrl` fields. [None] along with [featured_post](Self::featured_post), or if the
+    /// featuring user was deleted
+    pub featuring_user: Option<UserResource>,
     /// The current server time
     pub server_time: DateTime<Utc>,
     /// The configuration for this server
     pub config: GlobalInfoConfig,
 }
 
+/// The server information returned by [get_info](crate::SzurubooruRequest::get_info)
+pub type ServerInfo = GlobalInfo;
+
+impl WithBaseURL for GlobalInfo {
+    fn with_base_url(self, url: &str) -> Self {
+        Self {
+            featured_post: self.featured_post.with_base_url(url),
+            featuring_user: self.featuring_user.with_base_url(url),
+            ..self
+        }
+    }
+}
+
 #[cfg(feature = "python")]
 #[cfg_attr(all(feature = "python"), pymethods)]
 #[doc(hidden)]
@@ -1406,7 +1971,7 @@ pub enum SnapshotResourceType {
     PoolCategory,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(
     all(feature = "python"),
     pyclass(eq, module = "szurubooru_client.models")
@@ -1495,7 +2060,7 @@ impl SnapshotModificationData {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(
     all(feature = "python"),
     pyclass(eq, module = "szurubooru_client.models")
@@ -1660,11 +2225,14 @@ impl AroundPostResult {
 #[cfg(test)]
 mod tests {
     use crate::models::{
-        GlobalInfo, GlobalInfoConfig, PostResource, SnapshotResource, TagCategoryResource,
+        CreateUpdatePostBuilder, GlobalInfo, GlobalInfoConfig, LargeVideoBehavior, NoteResource,
+        PostFlag, PostResource, PostSafety, SnapshotResource, SourceWarning, TagCategoryResource,
+        ViewerUrl,
     };
-    use chrono::Datelike;
+    use chrono::{Datelike, TimeZone, Utc};
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_parse_global_info() {
         let cfg_str = r#"{
             "name": "integrationland",
@@ -1716,6 +2284,55 @@ mod tests {
         let global_info =
             serde_json::from_str::<GlobalInfo>(info_str).expect("Unable to parse info_str");
         assert_eq!(global_info.server_time.year(), 2024);
+        assert!(global_info.featured_post.is_none());
+        assert!(global_info.featuring_user.is_none());
+        assert!(global_info.featuring_time.is_none());
+    }
+
+    #[test]
+    fn test_parse_global_info_featuring() {
+        let mut info = serde_json::json!({
+            "postCount": 2,
+            "diskUsage": 2048,
+            "serverTime": "2024-08-09T21:41:24.123623Z",
+            "config": {
+                "userNameRegex": "^[a-zA-Z0-9_-]{1,32}$",
+                "passwordRegex": "^.{5,}$",
+                "tagNameRegex": "^\\S+$",
+                "tagCategoryNameRegex": "^[^\\s%+#/]+$",
+                "defaultUserRank": "regular",
+                "enableSafety": true,
+                "contactEmail": null,
+                "canSendMails": false,
+                "privileges": {}
+            },
+            "featuredPost": {"id": 2, "version": 4},
+            "featuringUser": {"name": "admin", "avatarUrl": "https://gravatar.com/avatar/1"},
+            "featuringTime": "2024-08-01T12:30:00.5Z"
+        });
+        let global_info: GlobalInfo =
+            serde_json::from_value(info.clone()).expect("Unable to parse info with featuring");
+        assert_eq!(global_info.featured_post.map(|post| post.id), Some(Some(2)));
+        let featuring_user = global_info.featuring_user.expect("No featuring user");
+        assert_eq!(featuring_user.name.as_deref(), Some("admin"));
+        assert_eq!(
+            global_info.featuring_time,
+            Some(
+                Utc.with_ymd_and_hms(2024, 8, 1, 12, 30, 0).unwrap()
+                    + chrono::Duration::milliseconds(500)
+            )
+        );
+
+        // The featuring fields may also be left out entirely
+        let fields = info.as_object_mut().unwrap();
+        for key in ["featuredPost", "featuringUser", "featuringTime"] {
+            fields.remove(key);
+        }
+        let global_info: GlobalInfo =
+            serde_json::from_value(info).expect("Unable to parse info without featuring");
+        assert!(global_info.featured_post.is_none());
+        assert!(global_info.featuring_user.is_none());
+        assert!(global_info.featuring_time.is_none());
     }
 
     #[test]
@@ -1884,4 +2501,184 @@ mod tests {
         serde_json::from_str::<SnapshotResource>(input_str)
             .expect("Could not parse created snapshot resource");
     }
+
+    #[test]
+    fn test_best_quality_url() {
+        let post_with = |post_type: &str, file_size: u64| -> PostResource {
+            serde_json::from_value(serde_json::json!({
+                "type": post_type,
+                "fileSize": file_size,
+                "contentUrl": "data/posts/1.bin",
+                "thumbnailUrl": "data/generated-thumbnails/1.jpg"
+            }))
+            .unwrap()
+        };
+        let threshold = PostResource::DEFAULT_LARGE_VIDEO_THRESHOLD;
+
+        let image = post_with("image", threshold * 2);
+        assert_eq!(
+            image.best_quality_url(threshold, LargeVideoBehavior::Thumbnail),
+            Some(ViewerUrl::Content("data/posts/1.bin"))
+        );
+
+        let small_video = post_with("video", 1024);
+        assert_eq!(
+            small_video.best_quality_url(threshold, LargeVideoBehavior::Thumbnail),
+            Some(ViewerUrl::Content("data/posts/1.bin"))
+        );
+
+        let large_video = post_with("video", threshold + 1);
+        assert_eq!(
+            large_video.best_quality_url(threshold, LargeVideoBehavior::Thumbnail),
+            Some(ViewerUrl::Thumbnail("data/generated-thumbnails/1.jpg"))
+        );
+        assert_eq!(
+            large_video.best_quality_url(threshold, LargeVideoBehavior::Stream),
+            Some(ViewerUrl::Stream("data/posts/1.bin"))
+        );
+        assert_eq!(
+            large_video.best_quality_url(threshold * 2, LargeVideoBehavior::Stream),
+            Some(ViewerUrl::Content("data/posts/1.bin"))
+        );
+    }
+
+    #[test]
+    fn test_parse_post_flags() {
+        let flags: Vec<PostFlag> = serde_json::from_str(r#"["loop","customflag"]"#).unwrap();
+        assert_eq!(
+            flags,
+            vec![PostFlag::Loop, PostFlag::Other("customflag".to_string())]
+        );
+        assert_eq!(
+            serde_json::to_string(&flags).unwrap(),
+            r#"["loop","customflag"]"#
+        );
+    }
+
+    #[test]
+    fn test_parse_post_notes() {
+        let post: PostResource = serde_json::from_str(
+            r#"{"id": 3, "notes": [
+                {"polygon": [[0, 0], [0, 1], [1, 1], [1, 0]], "text": "Whole post"},
+                {"polygon": [[0.25, 0.1], [0.5, 0.1], [0.375, 0.6]], "text": "*Triangle*"}
+            ]}"#,
+        )
+        .expect("Unable to parse post");
+
+        assert_eq!(
+            post.notes(),
+            [
+                NoteResource {
+                    polygon: vec![[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]],
+                    text: "Whole post".to_string(),
+                },
+                NoteResource {
+                    polygon: vec![[0.25, 0.1], [0.5, 0.1], [0.375, 0.6]],
+                    text: "*Triangle*".to_string(),
+                },
+            ]
+        );
+
+        let post: PostResource = serde_json::from_str(r#"{"id": 3}"#).unwrap();
+        assert!(post.notes().is_empty());
+    }
+
+    #[test]
+    fn test_post_tag_names() {
+        let resources: PostResource = serde_json::from_str(
+            r#"{"id": 3, "tags": [
+                {"names": ["cat", "feline"], "category": "default", "usages": 12},
+                {"names": ["sleeping"], "category": "meta", "usages": 4}
+            ]}"#,
+        )
+        .expect("Unable to parse post with tag resources");
+        let names: PostResource = serde_json::from_str(r#"{"id": 3, "tags": ["cat", "sleeping"]}"#)
+            .expect("Unable to parse post with tag names");
+
+        assert_eq!(resources.tag_names(), vec!["cat", "sleeping"]);
+        assert_eq!(names.tag_names(), resources.tag_names());
+        assert_eq!(resources.tags.as_ref().unwrap()[0].usages, 12);
+        assert_eq!(names.tags.as_ref().unwrap()[0].category, "");
+
+        let post: PostResource = serde_json::from_str(r#"{"id": 3}"#).unwrap();
+        assert!(post.tags.is_none());
+        assert!(post.tag_names().is_empty());
+        let post: PostResource = serde_json::from_str(r#"{"id": 3, "tags": null}"#).unwrap();
+        assert!(post.tag_names().is_empty());
+    }
+
+    #[test]
+    fn test_post_diff() {
+        let old: PostResource = serde_json::from_value(serde_json::json!({
+            "safety": "safe",
+            "source": "http://example.com",
+            "checksum": "abc",
+            "tags": [
+                {"names": ["cat"], "category": "default", "usages": 2},
+                {"names": ["kitten", "kitty"], "category": "default", "usages": 1}
+            ]
+        }))
+        .unwrap();
+        let new: PostResource = serde_json::from_value(serde_json::json!({
+            "safety": "sketchy",
+            "source": "http://example.com",
+            "checksum": "abc",
+            "tags": [
+                {"names": ["cat"], "category": "default", "usages": 2},
+                {"names": ["tabby"], "category": "default", "usages": 1}
+            ]
+        }))
+        .unwrap();
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added_tags, vec!["tabby".to_string()]);
+        assert_eq!(diff.removed_tags, vec!["kitten".to_string()]);
+        assert_eq!(
+            diff.safety,
+            Some((Some(PostSafety::Safe), Some(PostSafety::Sketchy)))
+        );
+        assert_eq!(diff.source, None);
+        assert!(!diff.content_changed);
+        assert!(!diff.is_empty());
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn test_validate_sources() {
+        let sources = |source: &str| {
+            CreateUpdatePostBuilder::default()
+                .source(source.to_string())
+                .build()
+                .unwrap()
+                .validate_sources()
+        };
+        assert!(sources("https://example.com/art/1\nFound on a forum, artist unknown").is_empty());
+        assert!(
+            sources("ftp://example.com/art.png\nmagnet:?xt=urn:btih:abc\nnote: scanned").is_empty()
+        );
+        assert!(CreateUpdatePostBuilder::default()
+            .build()
+            .unwrap()
+            .validate_sources()
+            .is_empty());
+
+        let warnings = sources(
+            "www.example.com/art\nhttps://example.com\n\nhtp://example.com\nhttp:/example.com\n\
+             https//example.com\nhttps://example.com",
+        );
+        assert!(matches!(
+            &warnings[..],
+            [
+                SourceWarning::MissingScheme(0),
+                SourceWarning::MisspelledScheme(3, scheme),
+                SourceWarning::MalformedUrl(4, _),
+                SourceWarning::MalformedUrl(5, _),
+                SourceWarning::Duplicate(6),
+            ] if scheme == "htp"
+        ));
+        assert_eq!(
+            warnings[3].to_string(),
+            "Source 6 is not a valid URL: relative URL without a base"
+        );
+    }
 }
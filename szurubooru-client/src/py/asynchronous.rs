@@ -1,24 +1,58 @@
 use crate::models::*;
-use crate::py::PyPagedSearchResult;
+use crate::py::{background_runtime, PyPagedSearchResult};
 use crate::tokens::QueryToken;
 use crate::SzurubooruClient;
 use chrono::{DateTime, Utc};
-use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use futures_util::StreamExt;
+use pyo3::exceptions::{PyRuntimeError, PyStopAsyncIteration, PyValueError};
 use pyo3::prelude::*;
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex};
 
 #[pyclass(name = "SzurubooruAsyncClient", module = "szurubooru_client")]
 /// An asynchronous client for Szurubooru
 ///
 /// :see: :class:`~szurubooru_client.SzurubooruSyncClient` for supported parameters
 pub struct PythonAsyncClient {
-    client: SzurubooruClient,
+    client: Arc<SzurubooruClient>,
+}
+
+#[pyclass(name = "PostStream", module = "szurubooru_client")]
+/// An async iterator over posts, returned by :func:`~szurubooru_client.SzurubooruAsyncClient.stream_posts`.
+/// Pages are fetched in the background as the posts are consumed
+pub struct PyPostStream {
+    receiver: Arc<Mutex<mpsc::Receiver<crate::errors::SzurubooruResult<PostResource>>>>,
+}
+
+#[pymethods]
+impl PyPostStream {
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __anext__(slf: &Bound<'_, Self>) -> PyResult<Option<PyObject>> {
+        // Magic methods can't be async, so hand out the coroutine of an async method instead
+        slf.call_method0("_next_post").map(|next| Some(next.unbind()))
+    }
+
+    #[doc(hidden)]
+    async fn _next_post(&self) -> PyResult<PostResource> {
+        let receiver = self.receiver.clone();
+        let next = receiver.lock().await.recv().await;
+        match next {
+            Some(post) => post.map_err(Into::into),
+            None => Err(PyStopAsyncIteration::new_err(())),
+        }
+    }
 }
 
 #[pymethods]
 impl PythonAsyncClient {
     #[new]
-    #[pyo3(signature = (host, username=None, token=None, password=None, allow_insecure=None))]
+    #[pyo3(signature = (host, username=None, token=None, password=None, allow_insecure=None, timeout=None, rate_limit=None))]
     /// Creates a new instance of the Asynchornous client
     ///
     /// :see: :class:`~szurubooru_client.SzurubooruSyncClient` for supported parameters
@@ -28,26 +62,40 @@ impl PythonAsyncClient {
         token: Option<String>,
         password: Option<String>,
         allow_insecure: Option<bool>,
+        timeout: Option<f64>,
+        rate_limit: Option<f64>,
     ) -> PyResult<Self> {
         let allow_insecure = allow_insecure.unwrap_or(false);
+        let timeout = timeout
+            .map(Duration::try_from_secs_f64)
+            .transpose()
+            .map_err(|e| PyValueError::new_err(format!("Invalid timeout: {e}")))?;
 
-        match (username, token, password) {
+        let client = match (username, token, password) {
             (Some(u), Some(t), None) => {
-                let client = SzurubooruClient::new_with_token(&host, &u, &t, allow_insecure)?;
-                Ok(PythonAsyncClient { client })
+                SzurubooruClient::new_with_token(&host, &u, &t, allow_insecure)?
             }
             (Some(u), None, Some(p)) => {
-                let client = SzurubooruClient::new_with_basic_auth(&host, &u, &p, allow_insecure)?;
-                Ok(PythonAsyncClient { client })
+                SzurubooruClient::new_with_basic_auth(&host, &u, &p, allow_insecure)?
             }
-            (None, None, None) => {
-                let client = SzurubooruClient::new_anonymous(&host, allow_insecure)?;
-                Ok(PythonAsyncClient { client })
+            (None, None, None) => SzurubooruClient::new_anonymous(&host, allow_insecure)?,
+            _ => {
+                return Err(PyRuntimeError::new_err(
+                    "(Username and Token) or (Username and Password) must be provided",
+                ))
             }
-            _ => Err(PyRuntimeError::new_err(
-                "(Username and Token) or (Username and Password) must be provided",
-            )),
-        }
+        };
+        let client = match timeout {
+            Some(timeout) => client.with_timeout(timeout),
+            None => client,
+        };
+        let client = match rate_limit {
+            Some(rate_limit) => client.with_rate_limit(rate_limit)?,
+            None => client,
+        };
+        Ok(PythonAsyncClient {
+            client: Arc::new(client),
+        })
     }
 
     #[pyo3(signature = (fields=None))]
@@ -165,6 +213,17 @@ impl PythonAsyncClient {
             .map_err(Into::into)
     }
 
+    /// Lists the tag categories along with the number of tags in each category (async version)
+    ///
+    /// :see: :func:`~szurubooru_client.SzurubooruSyncClient.tag_category_stats` for parameters and return type
+    pub async fn tag_category_stats(&self) -> PyResult<Vec<(TagCategoryResource, u32)>> {
+        self.client
+            .request()
+            .tag_category_stats()
+            .await
+            .map_err(Into::into)
+    }
+
     #[pyo3(signature = (query=None, fields=None, limit=None, offset=None))]
     /// List the tags currently available on the site (async version)
     ///
@@ -301,6 +360,39 @@ impl PythonAsyncClient {
             .map_err(Into::into)
     }
 
+    #[pyo3(signature = (names, fields=None))]
+    /// Fetches several tags by name in as few requests as possible (async version)
+    ///
+    /// :see: :func:`~szurubooru_client.SzurubooruSyncClient.get_tags` for parameters and return type
+    pub async fn get_tags(
+        &self,
+        names: Vec<String>,
+        fields: Option<Vec<String>>,
+    ) -> PyResult<HashMap<String, TagResource>> {
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        self.client
+            .with_optional_fields(fields)
+            .get_tags(&names)
+            .await
+            .map_err(Into::into)
+    }
+
+    #[pyo3(signature = (name, fields=None))]
+    /// Looks up the tag with exactly the given name (async version)
+    ///
+    /// :see: :func:`~szurubooru_client.SzurubooruSyncClient.get_tag_exact` for parameters and return type
+    pub async fn get_tag_exact(
+        &self,
+        name: String,
+        fields: Option<Vec<String>>,
+    ) -> PyResult<Option<TagResource>> {
+        self.client
+            .with_optional_fields(fields)
+            .get_tag_exact(&name)
+            .await
+            .map_err(Into::into)
+    }
+
     #[pyo3(signature = (name, version))]
     /// Deletes an existing tag (async version)
     ///
@@ -374,6 +466,129 @@ impl PythonAsyncClient {
             .map(Into::into)
     }
 
+    #[pyo3(signature = (query=None, fields=None, page_size=None))]
+    /// Streams every post matching the query, fetching the next page once the current one is
+    /// used up. Use it with ``async for``:
+    ///
+    /// ```python
+    /// async for post in client.stream_posts([anonymous_token("cat")], page_size=50):
+    ///     print(post.id)
+    /// ```
+    ///
+    /// .. note::
+    ///     This method supports :doc:`Query Tokens </tokens>`
+    ///
+    /// .. note::
+    ///     This method supports :doc:`Field selection </fields>`
+    ///
+    /// :param Optional[list[QueryToken]] query: A list of query tokens used to filter the results
+    /// :param Optional[list[str]] fields: A list of fields to select for the returned objects
+    /// :param Optional[int] page_size: How many posts to fetch per request. Defaults to the server's page size
+    ///
+    /// :return: An async iterator of :class:`~szurubooru_client.models.PostResource`
+    /// :rtype: PostStream
+    pub fn stream_posts(
+        &self,
+        query: Option<Vec<QueryToken>>,
+        fields: Option<Vec<String>>,
+        page_size: Option<u32>,
+    ) -> PyPostStream {
+        let client = self.client.clone();
+        let (sender, receiver) = mpsc::channel(1);
+        background_runtime().spawn(async move {
+            let request = client
+                .with_optional_fields(fields)
+                .with_optional_limit(page_size);
+            let mut posts = std::pin::pin!(request.stream_posts(query.as_ref()));
+            while let Some(post) = posts.next().await {
+                if sender.send(post).await.is_err() {
+                    break;
+                }
+            }
+        });
+        PyPostStream {
+            receiver: Arc::new(Mutex::new(receiver)),
+        }
+    }
+
+    #[pyo3(signature = (post_type, query=None, fields=None, limit=None, offset=None))]
+    /// Lists the posts of the given type (async version)
+    ///
+    /// :see: :func:`~szurubooru_client.SzurubooruSyncClient.list_posts_of_type` for parameters and return type
+    pub async fn list_posts_of_type(
+        &self,
+        post_type: PostType,
+        query: Option<Vec<QueryToken>>,
+        fields: Option<Vec<String>>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> PyResult<PyPagedSearchResult> {
+        self.client
+            .with_optional_fields(fields)
+            .with_optional_limit(limit)
+            .with_optional_offset(offset)
+            .list_posts_of_type(post_type, query.as_ref())
+            .await
+            .map_err(Into::into)
+            .map(Into::into)
+    }
+
+    #[pyo3(signature = (limit, query=None, fields=None))]
+    /// Returns the highest scored posts (async version)
+    ///
+    /// :see: :func:`~szurubooru_client.SzurubooruSyncClient.top_scored_posts` for parameters and return type
+    pub async fn top_scored_posts(
+        &self,
+        limit: u32,
+        query: Option<Vec<QueryToken>>,
+        fields: Option<Vec<String>>,
+    ) -> PyResult<Vec<PostResource>> {
+        self.client
+            .with_optional_fields(fields)
+            .top_scored_posts(limit, query.as_ref())
+            .await
+            .map_err(Into::into)
+    }
+
+    #[pyo3(signature = (file_path, tags, safety, fields=None))]
+    /// Creates a new post from a local file (async version)
+    ///
+    /// :see: :func:`~szurubooru_client.SzurubooruSyncClient.create_post_from_path` for parameters and return type
+    pub async fn create_post_from_path(
+        &self,
+        file_path: PathBuf,
+        tags: Vec<String>,
+        safety: PostSafety,
+        fields: Option<Vec<String>>,
+    ) -> PyResult<PostResource> {
+        let tags = tags.iter().map(String::as_str).collect::<Vec<_>>();
+        self.client
+            .with_optional_fields(fields)
+            .create_post_from_path(file_path, &tags, safety)
+            .await
+            .map_err(Into::into)
+    }
+
+    #[pyo3(signature = (content, content_type, tags, safety, fields=None))]
+    /// Creates a new post from in-memory content (async version)
+    ///
+    /// :see: :func:`~szurubooru_client.SzurubooruSyncClient.create_post_from_bytes` for parameters and return type
+    pub async fn create_post_from_bytes(
+        &self,
+        content: Vec<u8>,
+        content_type: String,
+        tags: Vec<String>,
+        safety: PostSafety,
+        fields: Option<Vec<String>>,
+    ) -> PyResult<PostResource> {
+        let tags = tags.iter().map(String::as_str).collect::<Vec<_>>();
+        self.client
+            .with_optional_fields(fields)
+            .create_post_from_bytes(content, &content_type, &tags, safety)
+            .await
+            .map_err(Into::into)
+    }
+
     #[pyo3(signature = (url=None, upload_token=None, file_path=None, thumbnail_path=None, tags=None, safety=None, source=None,
             relations=None, notes=None, flags=None, anonymous=None, fields=None))]
     #[allow(clippy::too_many_arguments)]
@@ -391,7 +606,7 @@ impl PythonAsyncClient {
         source: Option<String>,
         relations: Option<Vec<u32>>,
         notes: Option<Vec<NoteResource>>,
-        flags: Option<Vec<String>>,
+        flags: Option<Vec<PostFlag>>,
         anonymous: Option<bool>,
         fields: Option<Vec<String>>,
     ) -> PyResult<PostResource> {
@@ -468,7 +683,7 @@ impl PythonAsyncClient {
         source: Option<String>,
         relations: Option<Vec<u32>>,
         notes: Option<Vec<NoteResource>>,
-        flags: Option<Vec<String>>,
+        flags: Option<Vec<PostFlag>>,
         fields: Option<Vec<String>>,
     ) -> PyResult<PostResource> {
         let mut cupost = CreateUpdatePostBuilder::default();
@@ -593,6 +808,22 @@ impl PythonAsyncClient {
             .map_err(Into::into)
     }
 
+    #[pyo3(signature = (content, content_type))]
+    /// Reverse image searches for in-memory image content (async version)
+    ///
+    /// :see: :func:`~szurubooru_client.SzurubooruSyncClient.reverse_image_search_bytes` for parameters and return type
+    pub async fn reverse_image_search_bytes(
+        &self,
+        content: Vec<u8>,
+        content_type: String,
+    ) -> PyResult<ImageSearchResult> {
+        self.client
+            .request()
+            .reverse_search_bytes(content, &content_type)
+            .await
+            .map_err(Into::into)
+    }
+
     #[pyo3(signature = (image_path))]
     /// Searches for an *exact* image match of an image from the filesystem (async version)
     ///
@@ -684,15 +915,13 @@ impl PythonAsyncClient {
         rating: i8,
         fields: Option<Vec<String>>,
     ) -> PyResult<PostResource> {
-        if !(-1..=1).contains(&rating) {
-            Err(PyValueError::new_err("Rating must be -1, 0, or 1"))
-        } else {
-            self.client
-                .with_optional_fields(fields)
-                .rate_post(post_id, rating)
-                .await
-                .map_err(Into::into)
-        }
+        let rating = PostRating::try_from(rating)
+            .map_err(|_| PyValueError::new_err("Rating must be -1, 0, or 1"))?;
+        self.client
+            .with_optional_fields(fields)
+            .rate_post(post_id, rating)
+            .await
+            .map_err(Into::into)
     }
 
     #[pyo3(signature = (post_id, fields=None))]
@@ -987,6 +1216,18 @@ impl PythonAsyncClient {
             .map_err(Into::into)
     }
 
+    #[pyo3(signature = (post_id))]
+    /// Lists the pools the given post belongs to (async version)
+    ///
+    /// :see: :func:`~szurubooru_client.SzurubooruSyncClient.pools_containing_post` for parameters and return type
+    pub async fn pools_containing_post(&self, post_id: u32) -> PyResult<Vec<PoolResource>> {
+        self.client
+            .request()
+            .pools_containing_post(post_id)
+            .await
+            .map_err(Into::into)
+    }
+
     /// Deletes existing pool (async version)
     ///
     /// :see: :func:`~szurubooru_client.SzurubooruSyncClient.delete_pool` for parameters and return type
@@ -1023,6 +1264,23 @@ impl PythonAsyncClient {
             .map_err(Into::into)
     }
 
+    #[pyo3(signature = (remove_id, into_id, fields=None))]
+    /// Merges the source pool into the target pool after fetching their versions (async version)
+    ///
+    /// :see: :func:`~szurubooru_client.SzurubooruSyncClient.merge_pools_by_id` for parameters and return type
+    pub async fn merge_pools_by_id(
+        &self,
+        remove_id: u32,
+        into_id: u32,
+        fields: Option<Vec<String>>,
+    ) -> PyResult<PoolResource> {
+        self.client
+            .with_optional_fields(fields)
+            .merge_pools_by_id(remove_id, into_id)
+            .await
+            .map_err(Into::into)
+    }
+
     #[pyo3(signature = (query=None, fields=None, limit=None, offset=None))]
     /// List the comments currently available on the site (async version)
     ///
@@ -1044,6 +1302,23 @@ impl PythonAsyncClient {
             .map(Into::into)
     }
 
+    #[pyo3(signature = (limit, query=None, fields=None))]
+    /// Returns the highest scored comments (async version)
+    ///
+    /// :see: :func:`~szurubooru_client.SzurubooruSyncClient.top_comments` for parameters and return type
+    pub async fn top_comments(
+        &self,
+        limit: usize,
+        query: Option<Vec<QueryToken>>,
+        fields: Option<Vec<String>>,
+    ) -> PyResult<Vec<CommentResource>> {
+        self.client
+            .with_optional_fields(fields)
+            .top_comments(limit, query.as_ref())
+            .await
+            .map_err(Into::into)
+    }
+
     #[pyo3(signature = (text, post_id, fields=None))]
     /// Creates a new comment under a given post (async version)
     ///
@@ -1426,6 +1701,13 @@ impl PythonAsyncClient {
             .map_err(Into::into)
     }
 
+    /// Checks that the server is reachable (async version)
+    ///
+    /// :see: :func:`~szurubooru_client.SzurubooruSyncClient.ping` for parameters and return type
+    pub async fn ping(&self) -> PyResult<Duration> {
+        self.client.ping().await.map_err(Into::into)
+    }
+
     /// Puts a file from a given file path in temporary storage and assigns it a token that can be
     /// used in other requests. (async version)
     ///
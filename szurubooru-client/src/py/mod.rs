@@ -1,6 +1,8 @@
 use crate::models::PagedSearchResult;
 use pyo3::prelude::*;
-use pyo3::types::PyList;
+use pyo3::types::{PyIterator, PyList};
+use std::sync::OnceLock;
+use tokio::runtime::{Builder, Runtime};
 
 // rustfmt likes to break the Python docstrings
 #[rustfmt::skip]
@@ -10,7 +12,9 @@ pub mod synchronous;
 
 #[derive(Debug)]
 #[pyclass(name = "PagedResult", get_all, module = "szurubooru_client")]
-/// A paged result generated by most of the ``list`` methods of the Szurubooru clients
+/// A paged result generated by most of the ``list`` methods of the Szurubooru clients.
+/// Iterating over it, indexing it and calling ``len()`` on it all work on the ``results`` of
+/// the current page
 pub struct PyPagedSearchResult {
     /// The query string that was used to generate these results
     pub query: String,
@@ -30,11 +34,37 @@ impl PyPagedSearchResult {
         format!("{:?}", self)
     }
 
-    /*fn __len__(&self) -> PyResult<usize> {
-        Python::with_gil(|py| {
-            Ok(self.results.bind_borrowed(py).len())
-        })
-    }*/
+    /// The number of results on this page, not the ``total`` number of matches
+    fn __len__(&self, py: Python<'_>) -> usize {
+        self.results.bind(py).len()
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        PyIterator::from_bound_object(self.results.bind(py))
+    }
+
+    /// Indexes into the results with Python semantics, so negative indices and slices work
+    fn __getitem__<'py>(
+        &self,
+        py: Python<'py>,
+        index: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.results.bind(py).as_any().get_item(index)
+    }
+}
+
+/// Background runtime for work that has to outlive a single call, such as the streams handed out
+/// by the async client. asyncio doesn't drive Tokio's IO, so the work runs here and its results
+/// are passed back over channels
+pub(crate) fn background_runtime() -> &'static Runtime {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .expect("Unable to start the background Tokio runtime")
+    })
 }
 
 impl<T: IntoPy<PyObject>> From<PagedSearchResult<T>> for PyPagedSearchResult {
@@ -4,7 +4,9 @@ use crate::py::PyPagedSearchResult;
 use crate::tokens::QueryToken;
 use chrono::{DateTime, Utc};
 use pyo3::prelude::*;
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Duration;
 use tokio::runtime::{Builder, Runtime};
 
 #[pyclass(name = "SzurubooruSyncClient", module = "szurubooru_client")]
@@ -17,6 +19,8 @@ use tokio::runtime::{Builder, Runtime};
 /// :param str password: The password to use for ``Basic`` authentication. Token authentication should be preferred
 /// :param str token: The token to use for ``Bearer`` authentication.
 /// :param bool allow_insecure: Disable cert validation. Disables SSL authentication
+/// :param float timeout: How many seconds a single request may take before failing. Defaults to no timeout
+/// :param float rate_limit: The most requests to send per second. Calls wait as long as needed to stay under it. Defaults to no limit
 ///
 /// :rtype: SzurubooruSyncClient
 pub struct PythonSyncClient {
@@ -27,7 +31,7 @@ pub struct PythonSyncClient {
 #[pymethods]
 impl PythonSyncClient {
     #[new]
-    #[pyo3(signature = (host, username=None, token=None, password=None, allow_insecure=None))]
+    #[pyo3(signature = (host, username=None, token=None, password=None, allow_insecure=None, timeout=None, rate_limit=None))]
     /// This method is for creating new instances of the SzurubooruSyncClient
     pub fn new(
         host: String,
@@ -35,9 +39,19 @@ impl PythonSyncClient {
         token: Option<String>,
         password: Option<String>,
         allow_insecure: Option<bool>,
+        timeout: Option<f64>,
+        rate_limit: Option<f64>,
     ) -> PyResult<Self> {
         let runtime = Builder::new_current_thread().enable_all().build()?;
-        let client = PythonAsyncClient::new(host, username, token, password, allow_insecure)?;
+        let client = PythonAsyncClient::new(
+            host,
+            username,
+            token,
+            password,
+            allow_insecure,
+            timeout,
+            rate_limit,
+        )?;
         Ok(Self { client, runtime })
     }
 
@@ -158,6 +172,15 @@ impl PythonSyncClient {
             .block_on(self.client.set_default_tag_category(name))
     }
 
+    /// Lists the tag categories along with the number of tags in each category. The tag count
+    /// is fetched with a separate ``category:<name>`` tag search for each category
+    ///
+    /// :return: A ``list`` of Tag Category resources and their tag counts
+    /// :rtype: list[tuple[TagCategoryResource, int]]
+    pub fn tag_category_stats(&self) -> PyResult<Vec<(TagCategoryResource, u32)>> {
+        self.runtime.block_on(self.client.tag_category_stats())
+    }
+
     #[pyo3(signature = (query=None, fields=None, limit=None, offset=None))]
     /// List the tags currently available on the site
     ///
@@ -297,6 +320,49 @@ impl PythonSyncClient {
         self.runtime.block_on(self.client.get_tag(name, fields))
     }
 
+    #[pyo3(signature = (names, fields=None))]
+    /// Fetches several tags by name in as few requests as possible. The names are batched into
+    /// ``name:a,b,c`` searches
+    ///
+    /// .. note::
+    ///     This method supports :doc:`Field selection </fields>`. The selected fields must
+    ///     include ``names`` and ``version``
+    ///
+    /// :param list[str] names: The names of the tags to fetch
+    /// :param Optional[list[str]] fields: A list of fields to select for the returned objects
+    ///
+    /// :return: A ``dict`` of the requested names to their Tag resources. Names that don't match
+    ///     any tag are left out
+    /// :rtype: dict[str, TagResource]
+    pub fn get_tags(
+        &self,
+        names: Vec<String>,
+        fields: Option<Vec<String>>,
+    ) -> PyResult<HashMap<String, TagResource>> {
+        self.runtime.block_on(self.client.get_tags(names, fields))
+    }
+
+    #[pyo3(signature = (name, fields=None))]
+    /// Looks up the tag with exactly the given name or alias. Unlike a ``name:`` search, ``*``
+    /// is matched literally rather than as a wildcard, so ``foo`` never matches ``foobar``
+    ///
+    /// .. note::
+    ///     This method supports :doc:`Field selection </fields>`. The selected fields must
+    ///     include ``names`` and ``version``
+    ///
+    /// :param str name: The exact name of the tag to look up
+    /// :param Optional[list[str]] fields: A list of fields to select for the returned object
+    ///
+    /// :return: The Tag resource, or ``None`` if there's no tag with that name
+    /// :rtype: Optional[TagResource]
+    pub fn get_tag_exact(
+        &self,
+        name: String,
+        fields: Option<Vec<String>>,
+    ) -> PyResult<Option<TagResource>> {
+        self.runtime.block_on(self.client.get_tag_exact(name, fields))
+    }
+
     #[pyo3(signature = (name, version))]
     /// Deletes an existing tag
     ///
@@ -394,6 +460,122 @@ impl PythonSyncClient {
             .block_on(self.client.list_posts(query, fields, limit, offset))
     }
 
+    #[pyo3(signature = (post_type, query=None, fields=None, limit=None, offset=None))]
+    /// Lists the posts of the given type, optionally narrowed down by more query tokens
+    ///
+    /// .. note::
+    ///     This method supports :doc:`Query Tokens </tokens>`
+    ///
+    /// .. note::
+    ///     This method supports :doc:`Field selection </fields>`
+    ///
+    /// .. note::
+    ///     This method supports :ref:`Result limits <limits>`
+    ///
+    /// .. note::
+    ///     This method supports :ref:`Result offsets <offsets>`
+    ///
+    /// :param PostType post_type: The type of post to return
+    /// :param Optional[list[QueryToken]] query: Extra query tokens used to filter the results
+    /// :param Optional[list[str]] fields: A list of fields to select for the returned object
+    /// :param Optional[int] limit: The maximum number of resources to return
+    /// :param Optional[int] offset: The number of results to skip before returning the result
+    ///
+    /// :return: A :class:`~szurubooru_client.PagedResult` of Post resources
+    /// :rtype: :class:`~szurubooru_client.models.PostResource`
+    pub fn list_posts_of_type(
+        &self,
+        post_type: PostType,
+        query: Option<Vec<QueryToken>>,
+        fields: Option<Vec<String>>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> PyResult<PyPagedSearchResult> {
+        self.runtime.block_on(
+            self.client
+                .list_posts_of_type(post_type, query, fields, limit, offset),
+        )
+    }
+
+    #[pyo3(signature = (limit, query=None, fields=None))]
+    /// Returns up to ``limit`` of the highest scored posts, e.g. to pick a featured post
+    ///
+    /// .. note::
+    ///     This method supports :doc:`Query Tokens </tokens>`
+    ///
+    /// .. note::
+    ///     This method supports :doc:`Field selection </fields>`
+    ///
+    /// :param int limit: The maximum number of posts to return
+    /// :param Optional[list[QueryToken]] query: Extra query tokens used to filter the results,
+    ///     such as a safety token
+    /// :param Optional[list[str]] fields: A list of fields to select for the returned object
+    ///
+    /// :rtype: list[PostResource]
+    pub fn top_scored_posts(
+        &self,
+        limit: u32,
+        query: Option<Vec<QueryToken>>,
+        fields: Option<Vec<String>>,
+    ) -> PyResult<Vec<PostResource>> {
+        self.runtime
+            .block_on(self.client.top_scored_posts(limit, query, fields))
+    }
+
+    #[pyo3(signature = (file_path, tags, safety, fields=None))]
+    /// Creates a new post from a local file. The file is streamed to the server and its MIME
+    /// type is guessed from the extension. For every other post property use :func:`create_post`
+    ///
+    /// .. note::
+    ///     This method supports :doc:`Field selection </fields>`
+    ///
+    /// :param str file_path: The path of the file to upload
+    /// :param list[str] tags: The tags to give the new post
+    /// :param PostSafety safety: The safety of the new post
+    /// :param Optional[list[str]] fields: A list of fields to select for the returned object
+    ///
+    /// :rtype: PostResource
+    pub fn create_post_from_path(
+        &self,
+        file_path: PathBuf,
+        tags: Vec<String>,
+        safety: PostSafety,
+        fields: Option<Vec<String>>,
+    ) -> PyResult<PostResource> {
+        self.runtime
+            .block_on(self.client.create_post_from_path(file_path, tags, safety, fields))
+    }
+
+    #[pyo3(signature = (content, content_type, tags, safety, fields=None))]
+    /// Creates a new post from in-memory content, such as an image generated with Pillow
+    ///
+    /// .. note::
+    ///     This method supports :doc:`Field selection </fields>`
+    ///
+    /// :param bytes content: The file content to upload
+    /// :param str content_type: The MIME type of ``content``, e.g. ``image/png``
+    /// :param list[str] tags: The tags to give the new post
+    /// :param PostSafety safety: The safety of the new post
+    /// :param Optional[list[str]] fields: A list of fields to select for the returned object
+    ///
+    /// :rtype: PostResource
+    pub fn create_post_from_bytes(
+        &self,
+        content: Vec<u8>,
+        content_type: String,
+        tags: Vec<String>,
+        safety: PostSafety,
+        fields: Option<Vec<String>>,
+    ) -> PyResult<PostResource> {
+        self.runtime.block_on(self.client.create_post_from_bytes(
+            content,
+            content_type,
+            tags,
+            safety,
+            fields,
+        ))
+    }
+
     #[pyo3(signature = (url=None, upload_token=None, file_path=None, thumbnail_path=None, tags=None, safety=None, source=None,
             relations=None, notes=None, flags=None, anonymous=None, fields=None))]
     #[allow(clippy::too_many_arguments)]
@@ -434,7 +616,7 @@ impl PythonSyncClient {
         source: Option<String>,
         relations: Option<Vec<u32>>,
         notes: Option<Vec<NoteResource>>,
-        flags: Option<Vec<String>>,
+        flags: Option<Vec<PostFlag>>,
         anonymous: Option<bool>,
         fields: Option<Vec<String>>,
     ) -> PyResult<PostResource> {
@@ -500,7 +682,7 @@ impl PythonSyncClient {
         source: Option<String>,
         relations: Option<Vec<u32>>,
         notes: Option<Vec<NoteResource>>,
-        flags: Option<Vec<String>>,
+        flags: Option<Vec<PostFlag>>,
         fields: Option<Vec<String>>,
     ) -> PyResult<PostResource> {
         self.runtime.block_on(self.client.update_post(
@@ -576,6 +758,24 @@ impl PythonSyncClient {
             .block_on(self.client.reverse_image_search(image_path))
     }
 
+    #[pyo3(signature = (content, content_type))]
+    /// Reverse image searches for in-memory image content. Returns exact and visually
+    /// similar matches
+    ///
+    /// :param bytes content: The image content to search for
+    /// :param str content_type: The MIME type of ``content``, e.g. ``image/png``
+    ///
+    /// :return: An object containing the exact match and similar posts
+    /// :rtype: :class:`~szurubooru_client.models.ImageSearchResult`
+    pub fn reverse_image_search_bytes(
+        &self,
+        content: Vec<u8>,
+        content_type: String,
+    ) -> PyResult<ImageSearchResult> {
+        self.runtime
+            .block_on(self.client.reverse_image_search_bytes(content, content_type))
+    }
+
     #[pyo3(signature = (image_path))]
     /// Searches for an *exact* image match of an image from the filesystem
     ///
@@ -1021,6 +1221,18 @@ impl PythonSyncClient {
         self.runtime.block_on(self.client.get_pool(pool_id, fields))
     }
 
+    #[pyo3(signature = (post_id))]
+    /// Lists the pools the given post belongs to
+    ///
+    /// :param int post_id: The ID of the post
+    ///
+    /// :return: The pools containing the post
+    /// :rtype: list[:class:`~szurubooru_client.models.PoolResource`]
+    pub fn pools_containing_post(&self, post_id: u32) -> PyResult<Vec<PoolResource>> {
+        self.runtime
+            .block_on(self.client.pools_containing_post(post_id))
+    }
+
     #[pyo3(signature = (pool_id, version))]
     /// Deletes existing pool. All posts in the pool will only have their relation to the pool
     /// removed.
@@ -1070,6 +1282,30 @@ impl PythonSyncClient {
         ))
     }
 
+    #[pyo3(signature = (remove_id, into_id, fields=None))]
+    /// Merges the source pool into the target pool. Unlike
+    /// :func:`~szurubooru_client.SzurubooruSyncClient.merge_pools` the current versions of both
+    /// pools are fetched before merging.
+    ///
+    /// .. note::
+    ///     This method supports :doc:`Field selection </fields>`
+    ///
+    /// :param int remove_id: The ID of the source pool
+    /// :param int into_id: The ID of the destination pool
+    /// :param Optional[list[str]] fields: A list of fields to select for the returned object
+    ///
+    /// :return: The surviving pool resource
+    /// :rtype: :class:`~szurubooru_client.models.PoolResource`
+    pub fn merge_pools_by_id(
+        &self,
+        remove_id: u32,
+        into_id: u32,
+        fields: Option<Vec<String>>,
+    ) -> PyResult<PoolResource> {
+        self.runtime
+            .block_on(self.client.merge_pools_by_id(remove_id, into_id, fields))
+    }
+
     #[pyo3(signature = (query=None, fields=None, limit=None, offset=None))]
     /// List the comments currently available on the site.
     ///
@@ -1106,6 +1342,33 @@ impl PythonSyncClient {
             .block_on(self.client.list_comments(query, fields, limit, offset))
     }
 
+    #[pyo3(signature = (limit, query=None, fields=None))]
+    /// Returns up to ``limit`` of the highest scored comments. The server can't sort comments
+    /// by score, so every matching comment is fetched and sorted client-side. Narrow down the
+    /// query, e.g. to a single post, to keep the number of requests down
+    ///
+    /// .. note::
+    ///     This method supports :doc:`Query Tokens </tokens>`
+    ///
+    /// .. note::
+    ///     This method supports :doc:`Field selection </fields>`
+    ///
+    /// :param int limit: The maximum number of comments to return
+    /// :param Optional[list[QueryToken]] query: A list of query tokens used to filter the comments
+    /// :param Optional[list[str]] fields: A list of fields to select for the returned object.
+    ///     Include ``score`` so the comments can be sorted
+    ///
+    /// :rtype: list[CommentResource]
+    pub fn top_comments(
+        &self,
+        limit: usize,
+        query: Option<Vec<QueryToken>>,
+        fields: Option<Vec<String>>,
+    ) -> PyResult<Vec<CommentResource>> {
+        self.runtime
+            .block_on(self.client.top_comments(limit, query, fields))
+    }
+
     #[pyo3(signature = (text, post_id, fields=None))]
     /// Creates a new comment under a given post
     ///
@@ -1549,6 +1812,15 @@ impl PythonSyncClient {
         self.runtime.block_on(self.client.global_info())
     }
 
+    /// Checks that the server is reachable by fetching the global info, and measures how long
+    /// the round trip took
+    ///
+    /// :return: The round trip time
+    /// :rtype: datetime.timedelta
+    pub fn ping(&self) -> PyResult<Duration> {
+        self.runtime.block_on(self.client.ping())
+    }
+
     /// Puts a file from a given file path in temporary storage and assigns it a token that can be
     /// used in other requests.
     /// The files uploaded that way are deleted after a short while so clients shouldn't use it
@@ -2,11 +2,15 @@
 //! warned that the types here help with the Type safety for the Tag names only. It does
 //! not guarantee that a given API endpoint will support the given tag.
 
+use crate::errors::{SzurubooruClientError, SzurubooruResult, TokenParseError};
+use crate::models::{PostSafety, PostType, UserResource};
 #[cfg(feature = "python")]
-use crate::models::{PostSafety, PostType, SnapshotOperationType, SnapshotResourceType, UserRank};
+use crate::models::{SnapshotOperationType, SnapshotResourceType, UserRank};
 #[cfg(feature = "python")]
 use pyo3::{exceptions::PyValueError, prelude::*};
+use std::collections::HashSet;
 use std::fmt::Display;
+use std::str::FromStr;
 use strum_macros::AsRefStr;
 
 /// A named token such as `foo:bar`
@@ -23,10 +27,29 @@ pub trait SpecialToken: AsRef<str> {}
 pub trait ToQueryString {
     /// Convert `&self` into a HTML query string
     fn to_query_string(&self) -> String;
+
+    /// Convert `&self` into a query string like [to_query_string](Self::to_query_string), then
+    /// URL-encode it for use as the value of a `query` URL parameter. This is the same encoding
+    /// the client uses for its own requests: `application/x-www-form-urlencoded`, so spaces
+    /// become `+` and everything else outside `[A-Za-z0-9*-._]`, including the `\` used for
+    /// escaping, is percent-encoded.
+    ///
+    /// ```
+    /// use szurubooru_client::tokens::{QueryToken, TagNamedToken, ToQueryString};
+    /// let query = vec![
+    ///     QueryToken::anonymous("cat"),
+    ///     QueryToken::token(TagNamedToken::Name, "re:zero"),
+    /// ];
+    /// assert_eq!(query.to_query_string(), r"cat name:re\:zero");
+    /// assert_eq!(query.to_encoded_query_string(), "cat+name%3Are%5C%3Azero");
+    /// ```
+    fn to_encoded_query_string(&self) -> String {
+        url::form_urlencoded::byte_serialize(self.to_query_string().as_bytes()).collect()
+    }
 }
 
 /// A query token using for searching posts, tags and pools
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(all(feature = "python"), pyclass(module = "szurubooru_client.tokens"))]
 pub struct QueryToken {
     /// The key for this token. For `foo:bar` this would be `foo`
@@ -38,7 +61,9 @@ pub struct QueryToken {
 impl QueryToken {
     ///
     /// Construct a named token for a search query. Final results takes the form of
-    /// `key:value`. Values containing `:` and `-` are automatically escaped.
+    /// `key:value`. Any `:` in the value is automatically escaped, as is a leading `-` so it
+    /// isn't mistaken for negation. Dashes elsewhere are left alone so dates and ranges like
+    /// `2020-01-01..2021-01-01` work as-is.
     ///
     /// `key` can either be one of the existing [NamedToken] types for convenience, or anything
     /// that implements [`AsRef<str>`] for custom tokens.
@@ -55,13 +80,34 @@ impl QueryToken {
     /// client.request().list_posts(Some(&vec![qt, custom]));
     /// ```
     pub fn token(key: impl AsRef<str>, value: impl AsRef<str>) -> Self {
-        let escaped = value.as_ref().replace(":", "\\:").replace("-", "\\-");
+        let mut escaped = value.as_ref().replace(":", "\\:");
+        if escaped.starts_with('-') {
+            escaped.insert(0, '\\');
+        }
         Self {
             key: key.as_ref().to_string(),
             value: escaped,
         }
     }
 
+    ///
+    /// Constructs a named token like [QueryToken::token] that matches `value` literally. On top
+    /// of what [QueryToken::token] escapes, `*` is escaped so it isn't treated as a wildcard,
+    /// and `,` so the value isn't split into several OR'd values. Use this for exact lookups of
+    /// names that may contain either, e.g. a tag literally called `a*b`.
+    ///
+    /// ```
+    /// use szurubooru_client::tokens::{QueryToken, TagNamedToken};
+    /// let literal = QueryToken::token_literal(TagNamedToken::Name, "a*b");
+    /// assert_eq!(literal.to_string(), r"name:a\*b");
+    /// // The plain token keeps `*` as a wildcard
+    /// assert_eq!(QueryToken::token(TagNamedToken::Name, "a*b").to_string(), "name:a*b");
+    /// ```
+    pub fn token_literal(key: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        let literal = value.as_ref().replace('*', "\\*").replace(',', "\\,");
+        QueryToken::token(key, literal)
+    }
+
     ///
     /// Constructs a token for sorting purposes. Final results take the form of
     /// `sort:value`.
@@ -85,6 +131,47 @@ impl QueryToken {
         }
     }
 
+    ///
+    /// Constructs a sort token like [QueryToken::sort], but only accepts a [SortableToken] such
+    /// as [PostSortToken::Random], so a named token can't be passed by mistake. Use
+    /// [QueryToken::sort] for sort values that don't have a token type, such as plain strings.
+    ///
+    /// ```
+    /// use szurubooru_client::tokens::{PostSortToken, QueryToken};
+    /// let sort_token = QueryToken::sort_typed(PostSortToken::Random);
+    /// assert_eq!(sort_token.to_string(), "sort:random");
+    /// // The permissive form still takes any string
+    /// assert_eq!(QueryToken::sort("random"), sort_token);
+    /// ```
+    ///
+    /// Named tokens are rejected at compile time:
+    ///
+    /// ```compile_fail
+    /// use szurubooru_client::tokens::{PostNamedToken, QueryToken};
+    /// let sort_token = QueryToken::sort_typed(PostNamedToken::Score);
+    /// ```
+    pub fn sort_typed(value: impl SortableToken) -> Self {
+        QueryToken::sort(value)
+    }
+
+    ///
+    /// Constructs a sort token with an explicit [SortDirection].
+    /// [Ascending](SortDirection::Ascending) is the same as [QueryToken::sort], while
+    /// [Descending](SortDirection::Descending) produces `-sort:value`, reversing the order.
+    ///
+    /// ```
+    /// use szurubooru_client::tokens::{PostSortToken, QueryToken, SortDirection};
+    /// let sort_token = QueryToken::sort_with(PostSortToken::FavCount, SortDirection::Descending);
+    /// assert_eq!(sort_token.to_string(), "-sort:fav-count");
+    /// ```
+    pub fn sort_with(value: impl AsRef<str>, direction: SortDirection) -> Self {
+        let token = QueryToken::sort(value);
+        match direction {
+            SortDirection::Ascending => token,
+            SortDirection::Descending => token.negate(),
+        }
+    }
+
     ///
     /// Constructs a new anonymous token. These are resource specific, e.g for [crate::models::PostResource] it's
     /// the same as [PostNamedToken::Tag].
@@ -125,6 +212,28 @@ impl QueryToken {
         QueryToken::anonymous(key)
     }
 
+    ///
+    /// Constructs an anonymous token matching any one of `values`, e.g. `tagme,fixme` for posts
+    /// tagged with either `tagme` or `fixme`. Each value is escaped the same way as
+    /// [anonymous](QueryToken::anonymous), and any `,` in a value is escaped as well so it
+    /// doesn't split it into two alternatives.
+    ///
+    /// ```
+    /// use szurubooru_client::tokens::QueryToken;
+    /// let either = QueryToken::any_of(["tagme", "fixme"]);
+    /// assert_eq!(either.to_string(), "tagme,fixme");
+    /// ```
+    pub fn any_of(values: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        let alternatives: Vec<String> = values
+            .into_iter()
+            .map(|value| QueryToken::anonymous(value.as_ref().replace(',', "\\,")).key)
+            .collect();
+        Self {
+            key: alternatives.join(","),
+            value: "".to_string(),
+        }
+    }
+
     ///
     /// Negate the existing token. Include becomes Exclude and vice versa.
     ///
@@ -142,6 +251,417 @@ impl QueryToken {
             value: self.value.clone(),
         }
     }
+
+    ///
+    /// Whether this is a sort token, negated or not. E.g: `sort:random` or `-sort:score`
+    ///
+    pub fn is_sort(&self) -> bool {
+        self.key.trim_start_matches('-') == "sort"
+    }
+
+    ///
+    /// Constructs a named token comparing an integer value using the given [Comparison].
+    /// Szurubooru only understands inclusive ranges (`a..`, `..b`, `a..b`), so strict comparisons
+    /// are shifted by one. E.g: `score > 5` becomes `score:6..` and `score <= 5` becomes
+    /// `score:..5`. Nothing is greater than [i64::MAX] or less than [i64::MIN], so those two
+    /// comparisons give the empty range `1..0`, which matches nothing.
+    ///
+    /// ```no_run
+    /// # use szurubooru_client::SzurubooruClient;
+    /// # let client = SzurubooruClient::new_with_token("http://foo", "user", "pwd", true).unwrap();
+    /// // let client = SzurubooruClient::new(...)
+    /// use szurubooru_client::tokens::{Comparison, PostNamedToken, QueryToken};
+    /// // Find all posts with more than 3 comments
+    /// let qt = QueryToken::compare(PostNamedToken::CommentCount, Comparison::GreaterThan, 3);
+    /// assert_eq!(qt.to_string(), "comment-count:4..");
+    /// client.request().list_posts(Some(&vec![qt]));
+    /// ```
+    pub fn compare(key: impl AsRef<str>, op: Comparison, value: i64) -> Self {
+        const EMPTY_RANGE: &str = "1..0";
+        let range = match op {
+            Comparison::GreaterThan => value
+                .checked_add(1)
+                .map_or(EMPTY_RANGE.to_string(), |min| format!("{min}..")),
+            Comparison::GreaterOrEqual => format!("{value}.."),
+            Comparison::LessThan => value
+                .checked_sub(1)
+                .map_or(EMPTY_RANGE.to_string(), |max| format!("..{max}")),
+            Comparison::LessOrEqual => format!("..{value}"),
+            Comparison::Equal => value.to_string(),
+        };
+        Self {
+            key: key.as_ref().to_string(),
+            value: range,
+        }
+    }
+
+    ///
+    /// Constructs a named token matching an inclusive integer range. Leave out a bound for an
+    /// open-ended range. This works with any count token, such as
+    /// [FavCount](PostNamedToken::FavCount) and [CommentCount](PostNamedToken::CommentCount)
+    /// for posts, [UsageCount](TagNamedToken::UsageCount) for tags and
+    /// [PostCount](PoolNamedToken::PostCount) for pools.
+    ///
+    /// ```
+    /// use szurubooru_client::tokens::{PoolNamedToken, PostNamedToken, QueryToken};
+    /// // Posts favorited at least 10 times
+    /// let qt = QueryToken::num_range(PostNamedToken::FavCount, Some(10), None);
+    /// assert_eq!(qt.to_string(), "fav-count:10..");
+    /// // Pools with between 2 and 20 posts
+    /// let qt = QueryToken::num_range(PoolNamedToken::PostCount, Some(2), Some(20));
+    /// assert_eq!(qt.to_string(), "post-count:2..20");
+    /// ```
+    pub fn num_range(key: impl AsRef<str>, min: Option<i64>, max: Option<i64>) -> Self {
+        Self {
+            key: key.as_ref().to_string(),
+            value: RangeToken::from_bounds(min, max).to_string(),
+        }
+    }
+
+    ///
+    /// Constructs a [NoteText](PostNamedToken::NoteText) token searching annotation text.
+    /// `*` wildcards in `pattern` are kept as-is, while `:`, `,` and a leading `-` are escaped
+    /// so they're matched literally instead of being read as token syntax. Spaces separate
+    /// query tokens, so use `*` in their place to match multiple words.
+    ///
+    /// ```
+    /// use szurubooru_client::tokens::QueryToken;
+    /// let qt = QueryToken::note_text("hello*world");
+    /// assert_eq!(qt.to_string(), "note-text:hello*world");
+    /// ```
+    pub fn note_text(pattern: impl AsRef<str>) -> Self {
+        QueryToken::token(
+            PostNamedToken::NoteText,
+            pattern.as_ref().replace(',', "\\,"),
+        )
+    }
+
+    ///
+    /// Constructs an [ImageAspectRatio](PostNamedToken::ImageAspectRatio) range token, where
+    /// the aspect ratio is the image width divided by its height. Leave out a bound for an
+    /// open-ended range. Bounds are always written with a `.` decimal separator, and
+    /// non-finite bounds such as `NaN` are left out.
+    ///
+    /// ```
+    /// use szurubooru_client::tokens::QueryToken;
+    /// // Roughly 16:9
+    /// let qt = QueryToken::aspect_ratio_range(Some(1.7), Some(1.8));
+    /// assert_eq!(qt.to_string(), "image-aspect-ratio:1.7..1.8");
+    /// // Anything wider than it is tall
+    /// let qt = QueryToken::aspect_ratio_range(Some(1.0), None);
+    /// assert_eq!(qt.to_string(), "image-aspect-ratio:1..");
+    /// ```
+    pub fn aspect_ratio_range(from: Option<f64>, to: Option<f64>) -> Self {
+        let finite = |bound: Option<f64>| bound.filter(|b| b.is_finite());
+        Self {
+            key: PostNamedToken::ImageAspectRatio.as_ref().to_string(),
+            value: RangeToken::from_bounds(finite(from), finite(to)).to_string(),
+        }
+    }
+
+    ///
+    /// Constructs an [Uploader](PostNamedToken::Uploader) token matching the posts uploaded by
+    /// the user called `name`, escaped the same way as [token](QueryToken::token).
+    ///
+    /// ```
+    /// use szurubooru_client::tokens::QueryToken;
+    /// assert_eq!(QueryToken::uploaded_by_name("alice").to_string(), "uploader:alice");
+    /// ```
+    pub fn uploaded_by_name(name: impl AsRef<str>) -> Self {
+        QueryToken::token(PostNamedToken::Uploader, name)
+    }
+
+    ///
+    /// Constructs an [Uploader](PostNamedToken::Uploader) token matching the posts uploaded by
+    /// `user`. Returns `None` if the user's [name](UserResource::name) wasn't included in the
+    /// response, e.g. because other fields were selected.
+    pub fn uploaded_by(user: &UserResource) -> Option<Self> {
+        user.name.as_ref().map(QueryToken::uploaded_by_name)
+    }
+
+    ///
+    /// Constructs a [Safety](PostNamedToken::Safety) token matching the posts with the given
+    /// `safety` rating
+    ///
+    /// ```
+    /// use szurubooru_client::models::PostSafety;
+    /// use szurubooru_client::tokens::QueryToken;
+    /// assert_eq!(QueryToken::safety(PostSafety::Sketchy).to_string(), "safety:sketchy");
+    /// ```
+    pub fn safety(safety: PostSafety) -> Self {
+        QueryToken::token(PostNamedToken::Safety, safety)
+    }
+
+    ///
+    /// Constructs a [Type](PostNamedToken::Type) token matching the posts of the given
+    /// `post_type`
+    ///
+    /// ```
+    /// use szurubooru_client::models::PostType;
+    /// use szurubooru_client::tokens::QueryToken;
+    /// assert_eq!(QueryToken::post_type(PostType::Video).to_string(), "type:video");
+    /// ```
+    pub fn post_type(post_type: PostType) -> Self {
+        QueryToken::token(PostNamedToken::Type, post_type)
+    }
+}
+
+/// A range value for named tokens such as `score` or `file-size`. Works with anything that
+/// implements [Display], so integers, floats and formatted dates can all be used as bounds.
+///
+/// ```
+/// use szurubooru_client::tokens::{PostNamedToken, QueryToken, RangeToken};
+/// let qt = QueryToken::token(PostNamedToken::Score, RangeToken::at_least(0));
+/// assert_eq!(qt.to_string(), "score:0..");
+/// let qt = QueryToken::token(PostNamedToken::FileSize, RangeToken::between(1024, 2048));
+/// assert_eq!(qt.to_string(), "file-size:1024..2048");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeToken(String);
+
+impl RangeToken {
+    /// `min..`
+    pub fn at_least(min: impl Display) -> Self {
+        RangeToken(format!("{min}.."))
+    }
+
+    /// `..max`
+    pub fn at_most(max: impl Display) -> Self {
+        RangeToken(format!("..{max}"))
+    }
+
+    /// `min..max`, both inclusive
+    pub fn between(min: impl Display, max: impl Display) -> Self {
+        RangeToken(format!("{min}..{max}"))
+    }
+
+    /// Exactly `value`
+    pub fn exact(value: impl Display) -> Self {
+        RangeToken(value.to_string())
+    }
+
+    /// A range with optional bounds. Leaving out both gives the fully open range `..`
+    pub fn from_bounds<T: Display>(min: Option<T>, max: Option<T>) -> Self {
+        let min = min.map(|v| v.to_string()).unwrap_or_default();
+        let max = max.map(|v| v.to_string()).unwrap_or_default();
+        RangeToken(format!("{min}..{max}"))
+    }
+}
+
+impl AsRef<str> for RangeToken {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for RangeToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A token key the crate doesn't enumerate, such as a field added by a newer server or a
+/// plugin. It implements [NamedToken] and [SortableToken] so it can be passed to code that
+/// bounds on them, like [QueryToken::sort_typed]. Nothing checks that the server actually
+/// supports the key.
+///
+/// ```
+/// use szurubooru_client::tokens::{CustomToken, QueryToken};
+/// let qt = QueryToken::token(CustomToken::new("md5"), "d41d8cd98f00b204e9800998ecf8427e");
+/// assert_eq!(qt.to_string(), "md5:d41d8cd98f00b204e9800998ecf8427e");
+/// let sort_token = QueryToken::sort_typed(CustomToken::new("md5"));
+/// assert_eq!(sort_token.to_string(), "sort:md5");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CustomToken(String);
+
+impl CustomToken {
+    /// Wraps `key` as-is
+    pub fn new(key: impl Into<String>) -> Self {
+        CustomToken(key.into())
+    }
+}
+
+impl AsRef<str> for CustomToken {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for CustomToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl NamedToken for CustomToken {}
+impl SortableToken for CustomToken {}
+
+/// How the values of a [QueryGroup] are combined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupMode {
+    /// Matches resources that have every one of the values
+    All,
+    /// Matches resources that have at least one of the values
+    Any,
+}
+
+/// A group of values for one token that is matched, or excluded, as a whole.
+///
+/// Szurubooru's search syntax has no parentheses. Terms are always combined with AND, and the
+/// only alternation is a comma-separated list of values within a single term, like
+/// `tag:a,b,c`. Negating such a term excludes resources that have any of the values. So a group
+/// renders to:
+///
+/// | Group                  | Meaning                       | Query       |
+/// |------------------------|-------------------------------|-------------|
+/// | `any([a, b])`          | has `a` or `b`                | `a,b`       |
+/// | `any([a, b]).negate()` | has neither `a` nor `b`       | `-a,b`      |
+/// | `all([a, b])`          | has both `a` and `b`          | `a b`       |
+/// | `all([a, b]).negate()` | doesn't have both `a` and `b` | unsupported |
+///
+/// The server can't express the last one, so [to_tokens](Self::to_tokens) fails for it unless
+/// the group has a single value.
+///
+/// ```
+/// use szurubooru_client::tokens::{PostNamedToken, QueryGroup, ToQueryString};
+/// let no_drafts = QueryGroup::any(["tagme", "fixme"]).negate();
+/// assert_eq!(no_drafts.to_tokens().unwrap().to_query_string(), "-tagme,fixme");
+/// let pools = QueryGroup::any(["3", "7"]).with_key(PostNamedToken::Pool);
+/// assert_eq!(pools.to_tokens().unwrap().to_query_string(), "pool:3,7");
+/// assert!(QueryGroup::all(["cat", "dog"]).negate().to_tokens().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryGroup {
+    key: Option<String>,
+    values: Vec<String>,
+    mode: GroupMode,
+    negated: bool,
+}
+
+impl QueryGroup {
+    /// A group matching resources that have every one of `values`, as anonymous tokens
+    pub fn all(values: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        Self::new(values, GroupMode::All)
+    }
+
+    /// A group matching resources that have at least one of `values`, as anonymous tokens
+    pub fn any(values: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        Self::new(values, GroupMode::Any)
+    }
+
+    fn new(values: impl IntoIterator<Item = impl AsRef<str>>, mode: GroupMode) -> Self {
+        Self {
+            key: None,
+            values: values
+                .into_iter()
+                .map(|value| value.as_ref().to_string())
+                .collect(),
+            mode,
+            negated: false,
+        }
+    }
+
+    /// Matches the values against the named token `key`, e.g. `pool:3,7`, instead of as
+    /// anonymous tokens
+    pub fn with_key(mut self, key: impl AsRef<str>) -> Self {
+        self.key = Some(key.as_ref().to_string());
+        self
+    }
+
+    /// How the values are combined
+    pub fn mode(&self) -> GroupMode {
+        self.mode
+    }
+
+    /// Negates the whole group. Negating twice gives back the original group
+    pub fn negate(&self) -> Self {
+        Self {
+            negated: !self.negated,
+            ..self.clone()
+        }
+    }
+
+    /// Renders the group as query tokens, escaping each value like [QueryToken::token] or
+    /// [QueryToken::anonymous] would, plus any `,` in [Any](GroupMode::Any) groups. An empty
+    /// group gives no tokens.
+    ///
+    /// Fails with a [ValidationError](SzurubooruClientError::ValidationError) for a negated
+    /// [All](GroupMode::All) group of more than one value, which the server can't express.
+    pub fn to_tokens(&self) -> SzurubooruResult<Vec<QueryToken>> {
+        let tokens = match self.mode {
+            _ if self.values.is_empty() => vec![],
+            GroupMode::Any => vec![self.any_token()],
+            GroupMode::All if self.negated && self.values.len() > 1 => {
+                return Err(SzurubooruClientError::ValidationError(format!(
+                    "Szurubooru can't exclude only resources that have all of {}",
+                    self.values.join(", ")
+                )));
+            }
+            GroupMode::All => self.values.iter().map(|value| self.token(value)).collect(),
+        };
+        Ok(if self.negated {
+            tokens.negate_all()
+        } else {
+            tokens
+        })
+    }
+
+    fn token(&self, value: &str) -> QueryToken {
+        match &self.key {
+            Some(key) => QueryToken::token(key, value),
+            None => QueryToken::anonymous(value),
+        }
+    }
+
+    fn any_token(&self) -> QueryToken {
+        let alternatives: Vec<String> = self
+            .values
+            .iter()
+            .map(|value| {
+                let token = self.token(&value.replace(',', "\\,"));
+                if self.key.is_some() {
+                    token.value
+                } else {
+                    token.key
+                }
+            })
+            .collect();
+        match &self.key {
+            Some(key) => QueryToken {
+                key: key.clone(),
+                value: alternatives.join(","),
+            },
+            None => QueryToken {
+                key: alternatives.join(","),
+                value: "".to_string(),
+            },
+        }
+    }
+}
+
+/// The direction of a sort token created with [QueryToken::sort_with]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    /// `sort:value`
+    Ascending,
+    /// `-sort:value`
+    Descending,
+}
+
+/// A comparison operator used with [QueryToken::compare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    /// `>`
+    GreaterThan,
+    /// `>=`
+    GreaterOrEqual,
+    /// `<`
+    LessThan,
+    /// `<=`
+    LessOrEqual,
+    /// `=`
+    Equal,
 }
 
 #[cfg(feature = "python")]
@@ -250,6 +770,28 @@ pub fn special_token(key: &Bound<'_, PyAny>) -> PyResult<QueryToken> {
     QueryToken::special_py(key)
 }
 
+#[cfg(feature = "python")]
+#[cfg_attr(all(feature = "python"), pyfunction)]
+/// Joins a list of query tokens into the query string that's sent to the server. The
+/// ``list`` methods on the client already do this, so it's mostly useful for logging or for
+/// building search URLs for the web UI.
+///
+/// :param list[QueryToken] tokens: The tokens to join
+/// :returns: The space-separated query string
+/// :rtype: str
+///
+/// -----
+/// Usage
+/// -----
+///
+/// ```python
+/// to_query_string([anonymous_token("cat"), -sort_token(PostSortToken.Score)])
+/// # 'cat -sort:score'
+/// ```
+pub fn to_query_string(tokens: Vec<QueryToken>) -> String {
+    ToQueryString::to_query_string(&tokens)
+}
+
 #[cfg(feature = "python")]
 #[cfg_attr(all(feature = "python"), pymethods)]
 impl QueryToken {
@@ -369,6 +911,55 @@ impl Display for QueryToken {
     }
 }
 
+/// Parses a single token as produced by [Display], e.g. `key:value`, `-key:value`, `key` or
+/// `re\:zero`. The token is split on the first `:` that isn't escaped, and the negation prefix
+/// stays part of the key. Escapes are kept as-is, so the parsed token is equal to the one the
+/// constructors would build.
+///
+/// ```
+/// use szurubooru_client::tokens::{QueryToken, TagNamedToken};
+/// let qt: QueryToken = r"name:re\:zero".parse().unwrap();
+/// assert_eq!(qt, QueryToken::token(TagNamedToken::Name, "re:zero"));
+///
+/// // To parse a whole query, split it on whitespace first
+/// let query: Vec<QueryToken> = "cat -dog sort:score"
+///     .split_whitespace()
+///     .map(str::parse)
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(query.len(), 3);
+/// ```
+impl FromStr for QueryToken {
+    type Err = TokenParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(TokenParseError::Empty);
+        }
+        if s.chars().any(char::is_whitespace) {
+            return Err(TokenParseError::ContainsWhitespace(s.to_string()));
+        }
+
+        let mut escaped = false;
+        let split = s.char_indices().find(|(_, c)| {
+            let found = *c == ':' && !escaped;
+            escaped = *c == '\\' && !escaped;
+            found
+        });
+        let (key, value) = match split {
+            Some((index, _)) => (&s[..index], &s[index + 1..]),
+            None => (s, ""),
+        };
+        if key.trim_start_matches('-').is_empty() {
+            return Err(TokenParseError::MissingKey(s.to_string()));
+        }
+        Ok(Self {
+            key: key.to_string(),
+            value: value.to_string(),
+        })
+    }
+}
+
 impl ToQueryString for Vec<QueryToken> {
     fn to_query_string(&self) -> String {
         let query_vec: Vec<String> = self.iter().map(|qv| qv.to_string()).collect();
@@ -376,6 +967,157 @@ impl ToQueryString for Vec<QueryToken> {
     }
 }
 
+/// Helper methods for working with a list of [QueryToken]s
+pub trait QueryTokenList {
+    ///
+    /// Returns a copy of the tokens with exact duplicates removed, preserving the order in which
+    /// they first appear. Only the first sort token is kept since the first one is the one that
+    /// determines the order of the results.
+    ///
+    /// Named `dedup_tokens` so it doesn't get confused with [Vec::dedup], which only removes
+    /// consecutive duplicates.
+    ///
+    /// ```
+    /// use szurubooru_client::tokens::{PostNamedToken, PostSortToken, QueryToken, QueryTokenList,
+    ///     ToQueryString};
+    /// let query = vec![
+    ///     QueryToken::anonymous("cat"),
+    ///     QueryToken::sort(PostSortToken::Score),
+    ///     QueryToken::anonymous("cat"),
+    ///     QueryToken::sort(PostSortToken::Random),
+    /// ];
+    /// assert_eq!(query.dedup_tokens().to_query_string(), "cat sort:score");
+    /// ```
+    fn dedup_tokens(&self) -> Vec<QueryToken>;
+
+    ///
+    /// Checks the tokens for combinations the server accepts but probably doesn't do what was
+    /// intended. This is purely diagnostic, the tokens can still be sent as-is.
+    ///
+    /// ```
+    /// use szurubooru_client::tokens::{PostSortToken, QueryToken, QueryTokenList, QueryWarning};
+    /// let query = vec![
+    ///     QueryToken::sort(PostSortToken::Score),
+    ///     QueryToken::sort(PostSortToken::Random),
+    /// ];
+    /// assert!(matches!(query.validate()[..], [QueryWarning::MultipleSorts(_)]));
+    /// ```
+    fn validate(&self) -> Vec<QueryWarning>;
+
+    ///
+    /// Returns a copy of the tokens with every one of them [negated](QueryToken::negate), so
+    /// included tokens become excluded and vice versa. Negating twice gives back the original
+    /// tokens.
+    ///
+    /// ```
+    /// use szurubooru_client::tokens::{QueryToken, QueryTokenList, ToQueryString};
+    /// let query = vec![QueryToken::anonymous("cat"), QueryToken::anonymous("dog").negate()];
+    /// assert_eq!(query.negate_all().to_query_string(), "-cat dog");
+    /// ```
+    fn negate_all(&self) -> Vec<QueryToken>;
+
+    ///
+    /// Fails with [SzurubooruClientError::TooManySearchTerms] if the query has more than
+    /// `max_terms` terms. Every token counts as one term, including sort and special tokens,
+    /// except empty ones that are dropped from the query anyway. A `key:a,b,c` token is a
+    /// single term however many values it has.
+    ///
+    /// ```
+    /// use szurubooru_client::tokens::{QueryToken, QueryTokenList};
+    /// let query = vec![QueryToken::anonymous("cat"), QueryToken::anonymous("dog")];
+    /// assert!(query.check_term_count(2).is_ok());
+    /// assert!(query.check_term_count(1).is_err());
+    /// ```
+    fn check_term_count(&self, max_terms: usize) -> SzurubooruResult<()>;
+}
+
+impl QueryTokenList for Vec<QueryToken> {
+    fn dedup_tokens(&self) -> Vec<QueryToken> {
+        let mut seen = HashSet::new();
+        let mut has_sort = false;
+        self.iter()
+            .filter(|qt| {
+                if qt.is_sort() {
+                    if has_sort {
+                        return false;
+                    }
+                    has_sort = true;
+                }
+                seen.insert(*qt)
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn negate_all(&self) -> Vec<QueryToken> {
+        self.iter().map(QueryToken::negate).collect()
+    }
+
+    fn check_term_count(&self, max_terms: usize) -> SzurubooruResult<()> {
+        let terms = self
+            .iter()
+            .filter(|qt| !qt.key.trim_start_matches('-').is_empty())
+            .count();
+        if terms > max_terms {
+            return Err(SzurubooruClientError::TooManySearchTerms {
+                terms,
+                max: max_terms,
+            });
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Vec<QueryWarning> {
+        let mut warnings = Vec::new();
+        let sorts: Vec<QueryToken> = self.iter().filter(|qt| qt.is_sort()).cloned().collect();
+        if sorts.len() > 1 {
+            warnings.push(QueryWarning::MultipleSorts(sorts.clone()));
+        }
+        warnings.extend(
+            sorts
+                .into_iter()
+                .filter(|qt| qt.key.starts_with('-'))
+                .map(QueryWarning::NegatedSort),
+        );
+        warnings.extend(
+            self.iter()
+                .enumerate()
+                .filter(|(_, qt)| qt.key.trim_start_matches('-').is_empty())
+                .map(|(index, _)| QueryWarning::EmptyToken(index)),
+        );
+        warnings
+    }
+}
+
+/// A possible problem with a query, as returned by [QueryTokenList::validate]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryWarning {
+    /// The query contains more than one sort token. The server only uses one of them and
+    /// silently ignores the rest
+    MultipleSorts(Vec<QueryToken>),
+    /// A sort token is negated, e.g. `-sort:score`. This reverses the sort order rather than
+    /// excluding anything
+    NegatedSort(QueryToken),
+    /// The token at the given index has no key and will be dropped from the query
+    EmptyToken(usize),
+}
+
+impl Display for QueryWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryWarning::MultipleSorts(sorts) => write!(
+                f,
+                "Query contains {} sort tokens, only one will be used",
+                sorts.len()
+            ),
+            QueryWarning::NegatedSort(qt) => {
+                write!(f, "Sort token {qt} is negated and will reverse the order")
+            }
+            QueryWarning::EmptyToken(index) => write!(f, "Token at index {index} is empty"),
+        }
+    }
+}
+
 #[derive(Debug, AsRefStr, PartialEq, Eq, Clone)]
 #[strum(serialize_all = "kebab-case")]
 #[cfg_attr(
@@ -507,8 +1249,8 @@ pub enum PostNamedToken {
     /// having been featured given number of times
     FeatureCount,
     /// given type of posts. The value can be either `image`, `animation` (or `animated` or `anim`),
-    /// `flash` (or `swf`) or `video` (or `webm`). Use [PostType](crate::models::PostType)
-    /// for type-safe values
+    /// `flash` (or `swf`) or `video` (or `webm`). Use [PostType] with
+    /// [QueryToken::post_type] for type-safe values
     Type,
     /// having given SHA1 checksum
     ContentChecksum,
@@ -561,7 +1303,7 @@ pub enum PostNamedToken {
     /// alias of [PostNamedToken::FeatureDate]
     FeatureTime,
     /// Post safety. Can be either `safe`, `sketchy` (or `questionable`) or `unsafe`
-    /// Use [PostSafety](crate::models::PostSafety) for the type-safe version
+    /// Use [PostSafety] with [QueryToken::safety] for the type-safe version
     Safety,
     /// alias of [PostNamedToken::Safety]
     Rating,
@@ -760,6 +1502,9 @@ impl NamedToken for CommentNamedToken {}
 )]
 /// Type-safe sort query tokens for use with
 /// [list_comments](crate::SzurubooruRequest::list_comments)
+///
+/// The server can't sort comments by score, use
+/// [top_comments](crate::SzurubooruRequest::top_comments) to sort them client-side instead
 pub enum CommentSortToken {
     /// as random as it can get
     Random,
@@ -885,6 +1630,282 @@ mod tests {
         assert_eq!(qt.to_string(), "foo");
     }
 
+    #[test]
+    fn test_parse_token() {
+        let tokens = vec![
+            QueryToken::token(PostNamedToken::CommentCount, "1"),
+            QueryToken::token(PostNamedToken::CommentCount, "1").negate(),
+            QueryToken::token(TagNamedToken::Name, "re:zero"),
+            QueryToken::token("source", "-leading"),
+            QueryToken::sort(PostSortToken::Random),
+            QueryToken::sort(PostSortToken::Random).negate(),
+            QueryToken::anonymous("re:zero"),
+            QueryToken::anonymous("foo").negate(),
+            QueryToken::special(PostSpecialToken::Liked),
+        ];
+        for qt in tokens {
+            assert_eq!(qt.to_string().parse::<QueryToken>(), Ok(qt));
+        }
+
+        let qt: QueryToken = "foo".parse().unwrap();
+        assert_eq!(qt.key, "foo");
+        assert_eq!(qt.value, "");
+
+        let qt: QueryToken = r"a\\:b".parse().unwrap();
+        assert_eq!(qt.key, r"a\\");
+        assert_eq!(qt.value, "b");
+
+        assert_eq!("".parse::<QueryToken>(), Err(TokenParseError::Empty));
+        assert_eq!(
+            "cat dog".parse::<QueryToken>(),
+            Err(TokenParseError::ContainsWhitespace("cat dog".to_string()))
+        );
+        assert_eq!(
+            ":value".parse::<QueryToken>(),
+            Err(TokenParseError::MissingKey(":value".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_range_token() {
+        assert_eq!(RangeToken::at_least(0).to_string(), "0..");
+        assert_eq!(RangeToken::at_most(2.5).to_string(), "..2.5");
+        assert_eq!(
+            RangeToken::between("2020-01-01", "2021-01-01").to_string(),
+            "2020-01-01..2021-01-01"
+        );
+        assert_eq!(RangeToken::exact(7).to_string(), "7");
+        assert_eq!(RangeToken::from_bounds(Some(1), None).to_string(), "1..");
+        assert_eq!(RangeToken::from_bounds(None::<u32>, None).to_string(), "..");
+
+        let qt = QueryToken::token(
+            PostNamedToken::CreationDate,
+            RangeToken::at_most("2021-01-01"),
+        );
+        assert_eq!(qt.to_string(), "creation-date:..2021-01-01");
+    }
+
+    #[test]
+    fn test_sort_with() {
+        let qt = QueryToken::sort_with(PostSortToken::FavCount, SortDirection::Descending);
+        assert_eq!(qt.to_string(), "-sort:fav-count");
+        assert!(qt.is_sort());
+        assert_eq!(qt.negate().to_string(), "sort:fav-count");
+
+        let qt = QueryToken::sort_with(PostSortToken::FavCount, SortDirection::Ascending);
+        assert_eq!(qt, QueryToken::sort(PostSortToken::FavCount));
+        assert_eq!(qt.negate().to_string(), "-sort:fav-count");
+    }
+
+    #[test]
+    fn test_custom_token() {
+        fn named(key: impl NamedToken, value: &str) -> QueryToken {
+            QueryToken::token(key, value)
+        }
+
+        let key = CustomToken::new("md5");
+        let qt = named(key.clone(), "abc:def");
+        assert_eq!(qt.key, "md5");
+        assert_eq!(qt.to_string(), r"md5:abc\:def");
+        assert_eq!(qt.negate().to_string(), r"-md5:abc\:def");
+
+        let qt = QueryToken::sort_typed(key.clone());
+        assert_eq!(qt, QueryToken::sort("md5"));
+        assert_eq!(qt.to_string(), "sort:md5");
+        let qt = QueryToken::sort_with(key, SortDirection::Descending);
+        assert_eq!(qt.to_string(), "-sort:md5");
+    }
+
+    #[test]
+    fn test_query_group() {
+        let either = QueryGroup::any(["tagme", "fixme"]);
+        assert_eq!(either.mode(), GroupMode::Any);
+        let tokens = either.to_tokens().unwrap();
+        assert_eq!(tokens, vec![QueryToken::any_of(["tagme", "fixme"])]);
+        assert_eq!(tokens.to_query_string(), "tagme,fixme");
+
+        let neither = either.negate();
+        assert_eq!(
+            neither.to_tokens().unwrap().to_query_string(),
+            "-tagme,fixme"
+        );
+        assert_eq!(neither.negate(), either);
+
+        let sources = QueryGroup::any(["a,b", "-c", "http://x"]).with_key("source");
+        assert_eq!(
+            sources.to_tokens().unwrap().to_query_string(),
+            r"source:a\,b,\-c,http\://x"
+        );
+        assert_eq!(
+            sources.negate().to_tokens().unwrap().to_query_string(),
+            r"-source:a\,b,\-c,http\://x"
+        );
+
+        let both = QueryGroup::all(["cat", "re:zero"]);
+        assert_eq!(both.to_tokens().unwrap().to_query_string(), r"cat re\:zero");
+        match both.negate().to_tokens() {
+            Err(SzurubooruClientError::ValidationError(message)) => {
+                assert!(message.contains("cat, re:zero"))
+            }
+            other => panic!("Expected a validation error, got {other:?}"),
+        }
+        let single = QueryGroup::all(["cat"]).negate();
+        assert_eq!(single.to_tokens().unwrap().to_query_string(), "-cat");
+
+        let empty = QueryGroup::any(Vec::<String>::new()).negate();
+        assert!(empty.to_tokens().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_token_escaping() {
+        let qt = QueryToken::token(PostNamedToken::CreationDate, "2020-01-01..2021-01-01");
+        assert_eq!(qt.to_string(), "creation-date:2020-01-01..2021-01-01");
+
+        let qt = QueryToken::token(PostNamedToken::Score, "1..10");
+        assert_eq!(qt.to_string(), "score:1..10");
+
+        let qt = QueryToken::token("source", "-leading-dash");
+        assert_eq!(qt.to_string(), r"source:\-leading-dash");
+
+        let qt = QueryToken::token("source", "http://example.com");
+        assert_eq!(qt.to_string(), r"source:http\://example.com");
+
+        let qt = QueryToken::token(TagNamedToken::Name, "a*b");
+        assert_eq!(qt.to_string(), "name:a*b");
+
+        let qt = QueryToken::token_literal(TagNamedToken::Name, "a*b");
+        assert_eq!(qt.to_string(), r"name:a\*b");
+
+        let qt = QueryToken::token_literal(TagNamedToken::Name, "-re:zero,*");
+        assert_eq!(qt.to_string(), r"name:\-re\:zero\,\*");
+    }
+
+    #[test]
+    fn test_encoded_query_string() {
+        let query = vec![
+            QueryToken::anonymous("blue sky"),
+            QueryToken::token("source", "http://example.com/a b"),
+            QueryToken::token(PostNamedToken::Score, "1..10"),
+            QueryToken::sort(PostSortToken::Score).negate(),
+        ];
+        assert_eq!(
+            query.to_query_string(),
+            r"blue sky source:http\://example.com/a b score:1..10 -sort:score"
+        );
+        assert_eq!(
+            query.to_encoded_query_string(),
+            "blue+sky+source%3Ahttp%5C%3A%2F%2Fexample.com%2Fa+b+score%3A1..10+-sort%3Ascore"
+        );
+        assert_eq!(Vec::<QueryToken>::new().to_encoded_query_string(), "");
+    }
+
+    #[test]
+    fn test_validate() {
+        let query = vec![
+            QueryToken::anonymous("cat"),
+            QueryToken::sort(PostSortToken::Score),
+            QueryToken::sort(PostSortToken::Random),
+        ];
+        assert_eq!(
+            query.validate(),
+            vec![QueryWarning::MultipleSorts(vec![
+                QueryToken::sort(PostSortToken::Score),
+                QueryToken::sort(PostSortToken::Random),
+            ])]
+        );
+
+        let query = vec![
+            QueryToken::sort(PostSortToken::Score).negate(),
+            QueryToken::anonymous(""),
+        ];
+        assert_eq!(
+            query.validate(),
+            vec![
+                QueryWarning::NegatedSort(QueryToken::sort(PostSortToken::Score).negate()),
+                QueryWarning::EmptyToken(1),
+            ]
+        );
+
+        assert!(vec![QueryToken::anonymous("cat")].validate().is_empty());
+    }
+
+    #[test]
+    fn test_compare() {
+        let key = PostNamedToken::Score;
+        let cases = [
+            (Comparison::GreaterThan, "score:6.."),
+            (Comparison::GreaterOrEqual, "score:5.."),
+            (Comparison::LessThan, "score:..4"),
+            (Comparison::LessOrEqual, "score:..5"),
+            (Comparison::Equal, "score:5"),
+        ];
+        for (op, expected) in cases {
+            assert_eq!(QueryToken::compare(&key, op, 5).to_string(), expected);
+        }
+
+        let qt = QueryToken::compare(&key, Comparison::GreaterThan, -1);
+        assert_eq!(qt.to_string(), "score:0..");
+
+        let qt = QueryToken::compare(&key, Comparison::GreaterThan, i64::MAX);
+        assert_eq!(qt.to_string(), "score:1..0");
+        let qt = QueryToken::compare(&key, Comparison::LessThan, i64::MIN);
+        assert_eq!(qt.to_string(), "score:1..0");
+        let qt = QueryToken::compare(&key, Comparison::GreaterOrEqual, i64::MAX);
+        assert_eq!(qt.to_string(), format!("score:{}..", i64::MAX));
+        let qt = QueryToken::compare(&key, Comparison::LessThan, i64::MAX);
+        assert_eq!(qt.to_string(), format!("score:..{}", i64::MAX - 1));
+    }
+
+    #[test]
+    fn test_note_text() {
+        assert_eq!(
+            QueryToken::note_text("hello*").to_string(),
+            "note-text:hello*"
+        );
+        assert_eq!(
+            QueryToken::note_text("-note: one, two*").to_string(),
+            r"note-text:\-note\: one\, two*"
+        );
+    }
+
+    #[test]
+    fn test_num_range() {
+        let qt = QueryToken::num_range(PostNamedToken::FavCount, Some(10), None);
+        assert_eq!(qt.to_string(), "fav-count:10..");
+        let qt = QueryToken::num_range(PostNamedToken::CommentCount, None, Some(5));
+        assert_eq!(qt.to_string(), "comment-count:..5");
+        let qt = QueryToken::num_range(TagNamedToken::UsageCount, Some(1), Some(3));
+        assert_eq!(qt.to_string(), "usage-count:1..3");
+        let qt = QueryToken::num_range(PoolNamedToken::PostCount, Some(0), Some(0));
+        assert_eq!(qt.to_string(), "post-count:0..0");
+        assert_eq!(
+            QueryToken::num_range(PostNamedToken::Score, Some(-2), None).to_string(),
+            "score:-2.."
+        );
+    }
+
+    #[test]
+    fn test_aspect_ratio_range() {
+        let sixteen_nine = 16.0 / 9.0;
+        let cases = [
+            (
+                (Some(sixteen_nine), Some(sixteen_nine)),
+                "1.7777777777777777..1.7777777777777777",
+            ),
+            ((Some(1.7), Some(1.8)), "1.7..1.8"),
+            ((Some(0.5), None), "0.5.."),
+            ((None, Some(1.0)), "..1"),
+            ((None, None), ".."),
+            ((Some(f64::NAN), Some(f64::INFINITY)), ".."),
+        ];
+        for ((from, to), expected) in cases {
+            assert_eq!(
+                QueryToken::aspect_ratio_range(from, to).to_string(),
+                format!("image-aspect-ratio:{expected}")
+            );
+        }
+    }
+
     #[test]
     fn test_vec_query() {
         let query_vec = vec![
@@ -894,4 +1915,103 @@ mod tests {
 
         assert_eq!(query_vec.to_query_string(), "comment-count:1 sort:random");
     }
+
+    #[test]
+    fn test_dedup_tokens() {
+        let query_vec = vec![
+            QueryToken::token(PostNamedToken::CommentCount, "1"),
+            QueryToken::anonymous("cat"),
+            QueryToken::sort(PostSortToken::Random),
+            QueryToken::token(PostNamedToken::CommentCount, "1"),
+            QueryToken::anonymous("dog"),
+            QueryToken::sort(PostSortToken::Score).negate(),
+            QueryToken::anonymous("cat"),
+        ];
+
+        let deduped = query_vec.dedup_tokens();
+        assert_eq!(deduped.len(), 4);
+        assert_eq!(
+            deduped.to_query_string(),
+            "comment-count:1 cat sort:random dog"
+        );
+    }
+
+    #[test]
+    fn test_uploaded_by() {
+        let user: UserResource = serde_json::from_str(r#"{"name": "alice"}"#).unwrap();
+        let by_user = QueryToken::uploaded_by(&user).expect("User has a name");
+        assert_eq!(by_user.to_string(), "uploader:alice");
+        assert_eq!(by_user, QueryToken::uploaded_by_name("alice"));
+
+        let nameless: UserResource = serde_json::from_str(r#"{"rank": "regular"}"#).unwrap();
+        assert_eq!(QueryToken::uploaded_by(&nameless), None);
+        assert_eq!(
+            QueryToken::uploaded_by_name("-odd:name").to_string(),
+            "uploader:\\-odd\\:name"
+        );
+    }
+
+    #[test]
+    fn test_safety_and_post_type() {
+        let safeties = [
+            (PostSafety::Safe, "safety:safe"),
+            (PostSafety::Sketchy, "safety:sketchy"),
+            (PostSafety::Questionable, "safety:questionable"),
+            (PostSafety::Unsafe, "safety:unsafe"),
+        ];
+        for (safety, expected) in safeties {
+            assert_eq!(QueryToken::safety(safety).to_string(), expected);
+        }
+
+        let post_types = [
+            (PostType::Image, "type:image"),
+            (PostType::Animation, "type:animation"),
+            (PostType::Animated, "type:animated"),
+            (PostType::Anim, "type:anim"),
+            (PostType::Flash, "type:flash"),
+            (PostType::Swf, "type:swf"),
+            (PostType::Video, "type:video"),
+            (PostType::Webm, "type:webm"),
+        ];
+        for (post_type, expected) in post_types {
+            assert_eq!(QueryToken::post_type(post_type).to_string(), expected);
+        }
+
+        let qt = QueryToken::safety(PostSafety::Unsafe).negate();
+        assert_eq!(qt.to_string(), "-safety:unsafe");
+    }
+
+    #[test]
+    fn test_any_of() {
+        assert_eq!(QueryToken::any_of(["tagme"]).to_string(), "tagme");
+        assert_eq!(
+            QueryToken::any_of(vec!["tagme".to_string(), "fixme".to_string()]).to_string(),
+            "tagme,fixme"
+        );
+        assert_eq!(
+            QueryToken::any_of(["re:zero", "x-men", "one,two"]).to_string(),
+            "re\\:zero,x\\-men,one\\,two"
+        );
+        assert_eq!(
+            QueryToken::any_of(["tagme", "fixme"]).negate().to_string(),
+            "-tagme,fixme"
+        );
+    }
+
+    #[test]
+    fn test_negate_all() {
+        let query_vec = vec![
+            QueryToken::token(PostNamedToken::Safety, "unsafe"),
+            QueryToken::anonymous("cat"),
+            QueryToken::token(PostNamedToken::Uploader, "bob").negate(),
+            QueryToken::anonymous("dog").negate(),
+        ];
+
+        let negated = query_vec.negate_all();
+        assert_eq!(
+            negated.to_query_string(),
+            "-safety:unsafe -cat uploader:bob dog"
+        );
+        assert_eq!(negated.negate_all(), query_vec);
+    }
 }
@@ -1,4 +1,4 @@
 from .szurubooru_client import *
 
 __doc__ = szurubooru_client.__doc__
-__all__ = ["SzurubooruSyncClient", "SzurubooruAsyncClient", "SzuruClientError", "PagedResult"]
+__all__ = ["SzurubooruSyncClient", "SzurubooruAsyncClient", "SzuruClientError", "PagedResult", "PostStream"]
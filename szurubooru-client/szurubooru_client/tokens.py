@@ -4,6 +4,7 @@ anonymous_token = _tokens.anonymous_token
 named_token = _tokens.named_token
 sort_token = _tokens.sort_token
 special_token = _tokens.special_token
+to_query_string = _tokens.to_query_string
 CommentNamedToken = _tokens.CommentNamedToken
 CommentSortToken = _tokens.CommentSortToken
 PoolNamedToken = _tokens.PoolNamedToken
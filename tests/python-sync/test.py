@@ -5,6 +5,8 @@ import time, sys
 from loguru import logger
 import hashlib
 import tempfile, pathlib
+import json, multiprocessing
+from http.server import BaseHTTPRequestHandler, HTTPServer
 
 def connect():
     logger.info("Connecting to Szurubooru instance")
@@ -30,6 +32,52 @@ def create_auth_client(client):
                                        password="integration_password", allow_insecure=True)
     return auth_client
 
+def test_tokens():
+    logger.info("Testing query tokens")
+    score = QueryToken.token(PostNamedToken.Score, "0..")
+    assert to_query_string([score]) == "score:0.."
+    assert to_query_string([-score, QueryToken.sort(PostSortToken.Random)]) == "-score:0.. sort:random"
+    assert to_query_string([QueryToken.anonymous("re:zero"), QueryToken.special(PostSpecialToken.Fav)]) == "re\\:zero fav"
+
+def test_paged_result():
+    logger.info("Testing paged results against a mocked server")
+    page = {"query": "", "offset": 0, "limit": 3, "total": 10,
+            "results": [{"id": 1, "text": "first"}, {"id": 2, "text": "second"}, {"id": 3, "text": "third"}]}
+
+    class MockHandler(BaseHTTPRequestHandler):
+        def do_GET(self):
+            body = json.dumps(page).encode()
+            self.send_response(200)
+            self.send_header("Content-Type", "application/json")
+            self.send_header("Content-Length", str(len(body)))
+            self.end_headers()
+            self.wfile.write(body)
+
+        def log_message(self, *args):
+            pass
+
+    # The sync client holds the GIL while it waits, so serve from another process
+    server = HTTPServer(("127.0.0.1", 0), MockHandler)
+    serving = multiprocessing.get_context("fork").Process(target=server.serve_forever, daemon=True)
+    serving.start()
+    try:
+        client = SzurubooruSyncClient(f"http://127.0.0.1:{server.server_port}")
+        comments = client.list_comments(limit=3)
+        assert len(comments) == 3
+        assert [c.id for c in comments] == [1, 2, 3]
+        assert comments[0].text == "first"
+        assert comments[-1].text == "third"
+        assert [c.id for c in comments[1:]] == [2, 3]
+        try:
+            comments[3]
+        except IndexError:
+            pass
+        else:
+            assert False, "Expected an IndexError past the end of the page"
+    finally:
+        serving.terminate()
+        server.server_close()
+
 def test_tag_categories(client):
     logger.info("Testing tag categories")
     logger.info("Listing tag categories")
@@ -369,6 +417,8 @@ def test_downloads(client):
     assert f3_hasher.hexdigest() == dl_hasher.hexdigest()
 
 if __name__ == "__main__":
+    test_tokens()
+    test_paged_result()
     client = connect()
     client = create_auth_client(client)
     test_tag_categories(client)
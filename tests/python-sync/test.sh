@@ -4,6 +4,7 @@ set -e
 pip uninstall -y szurubooru_client || true
 pip install -r requirements.txt
 maturin develop -F python -m ../../szurubooru-client/Cargo.toml
+python -m pytest test_stream_posts.py
 docker compose down
 docker compose up -d
 python test.py && docker compose down
@@ -0,0 +1,61 @@
+import json, multiprocessing
+from http.server import BaseHTTPRequestHandler, HTTPServer
+from urllib.parse import urlparse, parse_qs
+
+import pytest
+from szurubooru_client import SzurubooruAsyncClient
+from szurubooru_client.tokens import *
+
+POST_IDS = [10, 9, 8, 7, 6]
+
+
+class PagedPostsHandler(BaseHTTPRequestHandler):
+    def do_GET(self):
+        params = parse_qs(urlparse(self.path).query)
+        offset = int(params.get("offset", ["0"])[0])
+        limit = int(params.get("limit", ["100"])[0])
+        page = {
+            "query": params.get("query", [""])[0],
+            "offset": offset,
+            "limit": limit,
+            "total": len(POST_IDS),
+            "results": [{"id": post_id} for post_id in POST_IDS[offset:offset + limit]],
+        }
+        body = json.dumps(page).encode()
+        self.send_response(200)
+        self.send_header("Content-Type", "application/json")
+        self.send_header("Content-Length", str(len(body)))
+        self.end_headers()
+        self.wfile.write(body)
+
+    def log_message(self, *args):
+        pass
+
+
+@pytest.fixture
+def mock_host():
+    # Serve from another process so a blocked event loop can't stall the server
+    server = HTTPServer(("127.0.0.1", 0), PagedPostsHandler)
+    serving = multiprocessing.get_context("fork").Process(target=server.serve_forever, daemon=True)
+    serving.start()
+    yield f"http://127.0.0.1:{server.server_port}"
+    serving.terminate()
+    server.server_close()
+
+
+@pytest.mark.asyncio
+async def test_stream_posts_across_pages(mock_host):
+    client = SzurubooruAsyncClient(mock_host)
+    post_ids = []
+    async for post in client.stream_posts([anonymous_token("cat")], page_size=2):
+        post_ids.append(post.id)
+    assert post_ids == POST_IDS
+
+
+@pytest.mark.asyncio
+async def test_stream_posts_stops_early(mock_host):
+    client = SzurubooruAsyncClient(mock_host)
+    stream = client.stream_posts(page_size=2)
+    first = await stream.__anext__()
+    second = await stream.__anext__()
+    assert [first.id, second.id] == POST_IDS[:2]
@@ -290,6 +290,7 @@ async fn test_tags(client: &SzurubooruClient) {
 }
 
 #[instrument(skip(client))]
+#[allow(clippy::expect_fun_call)]
 async fn test_creating_posts(client: &SzurubooruClient) {
     info!("Testing creating posts");
 
@@ -480,7 +481,7 @@ async fn test_creating_posts(client: &SzurubooruClient) {
     info!("Rating post");
     let folly3_post = client
         .request()
-        .rate_post(folly3_post.id.unwrap(), 1)
+        .rate_post(folly3_post.id.unwrap(), PostRating::Up)
         .await
         .expect("Could not rate post");
     assert_eq!(folly3_post.own_score, Some(1));